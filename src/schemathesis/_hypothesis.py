@@ -50,7 +50,11 @@ def create_test(
         wrapped_test = settings(wrapped_test)
     existing_settings = getattr(wrapped_test, "_hypothesis_internal_use_settings", None)
     if existing_settings and Phase.explicit in existing_settings.phases:
-        wrapped_test = add_examples(wrapped_test, operation, hook_dispatcher=hook_dispatcher)
+        # Schema examples are valid, they don't make sense for negative testing
+        if data_generation_method == DataGenerationMethod.positive:
+            wrapped_test = add_examples(wrapped_test, operation, hook_dispatcher=hook_dispatcher)
+        elif data_generation_method == DataGenerationMethod.boundary:
+            wrapped_test = add_boundary_examples(wrapped_test, operation)
     return wrapped_test
 
 
@@ -97,6 +101,21 @@ def add_examples(test: Callable, operation: APIOperation, hook_dispatcher: Optio
     return test
 
 
+def add_boundary_examples(test: Callable, operation: APIOperation) -> Callable:
+    """Add examples with values at the edges of the API schema constraints.
+
+    They run in the explicit phase, i.e. before any randomly generated data.
+    """
+    try:
+        examples: List[Case] = [get_single_example(strategy) for strategy in operation.get_strategies_from_boundaries()]
+    except (InvalidSchema, HypothesisRefResolutionError, Unsatisfiable):
+        # The same reasons as in `add_examples`
+        examples = []
+    for example in examples:
+        test = hypothesis.example(case=example)(test)
+    return test
+
+
 def get_single_example(strategy: st.SearchStrategy[Case]) -> Case:
     @hypothesis.given(strategy)  # type: ignore
     @hypothesis.settings(  # type: ignore
@@ -0,0 +1,200 @@
+"""OAuth 2 authentication for requests made during testing."""
+import threading
+import time
+from typing import TYPE_CHECKING, Any, Dict, Optional, Set, Tuple, Union
+
+import attr
+import requests
+from requests.auth import AuthBase
+
+from .constants import USER_AGENT
+from .exceptions import OAuth2Error
+from .sessions import SessionConfig, create_session
+from .types import RequestCert
+
+if TYPE_CHECKING:
+    from .schemas import BaseSchema
+
+# Tokens are refreshed a bit earlier than they expire, so they are still valid when requests reach the server
+EXPIRATION_LEEWAY = 10  # pragma: no mutate
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class OAuth2Config:
+    """Parameters to obtain OAuth 2 access tokens.
+
+    The password grant is used if ``username`` is set, otherwise the client credentials grant is used.
+    If ``token_url`` is not set, it is taken from the corresponding OAuth 2 flow in the API schema.
+    """
+
+    client_id: str = attr.ib()  # pragma: no mutate
+    client_secret: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+    token_url: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+    scopes: Tuple[str, ...] = attr.ib(default=())  # pragma: no mutate
+    username: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+    password: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+
+    @property
+    def grant_type(self) -> str:
+        if self.username is not None:
+            return "password"
+        return "client_credentials"
+
+
+class OAuth2Auth(AuthBase):
+    """Add an OAuth 2 access token to requests.
+
+    The token is refreshed when it expires or when the API responds with 401 to a request that used it.
+    Requests with other credentials, e.g. modified by checks, are not retried.
+    The same instance can be shared between threads.
+    """
+
+    def __init__(
+        self,
+        config: OAuth2Config,
+        token_url: str,
+        verify: Union[bool, str] = True,
+        cert: Optional[RequestCert] = None,
+        session_config: Optional[SessionConfig] = None,
+    ) -> None:
+        self.config = config
+        self.token_url = token_url
+        self.verify = verify
+        self.cert = cert
+        self.session_config = session_config
+        self._lock = threading.Lock()
+        self._access_token: Optional[str] = None
+        self._refresh_token: Optional[str] = None
+        self._expires_at: Optional[float] = None
+        # All tokens obtained by this instance. Requests might still use expired ones
+        self._issued_tokens: Set[str] = set()
+
+    @classmethod
+    def from_schema(
+        cls,
+        schema: "BaseSchema",
+        config: OAuth2Config,
+        verify: Union[bool, str] = True,
+        cert: Optional[RequestCert] = None,
+        session_config: Optional[SessionConfig] = None,
+    ) -> "OAuth2Auth":
+        token_url = config.token_url or schema.get_oauth2_token_url(config.grant_type)
+        if token_url is None:
+            raise OAuth2Error(
+                f"Token URL for the `{config.grant_type}` OAuth 2 grant type is not found in the API schema. "
+                "Pass it explicitly via the `--oauth2-token-url` CLI option"
+            )
+        return cls(config, token_url, verify, cert, session_config)
+
+    def __call__(self, request: requests.PreparedRequest) -> requests.PreparedRequest:
+        request.headers["Authorization"] = f"Bearer {self.get_token()}"
+        request.register_hook("response", self._handle_unauthorized)
+        return request
+
+    def get_token(self) -> str:
+        """Valid access token. A new one is obtained if there is none yet, or if it has expired."""
+        with self._lock:
+            if self._access_token is None or (self._expires_at is not None and time.monotonic() >= self._expires_at):
+                self._obtain_token()
+            return self._access_token  # type: ignore
+
+    def refresh(self, expired_token: Optional[str] = None) -> None:
+        """Obtain a new access token.
+
+        If ``expired_token`` is passed, then the token is refreshed only if it is still the current one, so concurrent
+        requests that fail with the same token lead to a single refresh.
+        """
+        with self._lock:
+            if expired_token is None or expired_token == self._access_token:
+                self._obtain_token()
+
+    def _handle_unauthorized(self, response: requests.Response, **kwargs: Any) -> requests.Response:
+        request = response.request
+        if response.status_code != 401 or getattr(request, "_schemathesis_retried", False):
+            return response
+        used_token = _get_bearer_token(request.headers.get("Authorization"))
+        if used_token not in self._issued_tokens:
+            # The request was sent with credentials that are not issued by this instance, e.g. they were removed
+            # or replaced by checks. Retrying it with a valid token would hide the actual response
+            return response
+        self.refresh(used_token)
+        # Consume the content to release the connection back to the pool
+        response.content  # pylint: disable=pointless-statement
+        response.close()
+        retry = request.copy()
+        retry.headers["Authorization"] = f"Bearer {self.get_token()}"
+        retry._schemathesis_retried = True  # type: ignore
+        sign = getattr(request, "_schemathesis_sign", None)
+        if sign is not None:
+            # The original signature covers the expired token
+            sign(retry)
+        new_response = response.connection.send(retry, **kwargs)
+        new_response.history.append(response)
+        new_response.request = retry
+        return new_response
+
+    def _obtain_token(self) -> None:
+        if self._refresh_token is not None:
+            data = {"grant_type": "refresh_token", "refresh_token": self._refresh_token}
+            try:
+                self._store_token(self._request_token(data))
+                return
+            except OAuth2Error:
+                # The refresh token might be expired or revoked, the token is obtained via the original grant then
+                self._refresh_token = None
+        self._store_token(self._request_token(self._get_grant_data()))
+
+    def _get_grant_data(self) -> Dict[str, str]:
+        data = {"grant_type": self.config.grant_type}
+        if self.config.username is not None:
+            data["username"] = self.config.username
+            data["password"] = self.config.password or ""
+        if self.config.scopes:
+            data["scope"] = " ".join(self.config.scopes)
+        return data
+
+    def _request_token(self, data: Dict[str, str]) -> Dict[str, Any]:
+        auth = None
+        if self.config.client_secret is not None:
+            # Confidential clients authenticate via HTTP Basic authentication, see RFC 6749, Section 2.3.1
+            auth = (self.config.client_id, self.config.client_secret)
+        else:
+            data = {**data, "client_id": self.config.client_id}
+        try:
+            with create_session(self.session_config) as session:
+                response = session.post(
+                    self.token_url,
+                    data=data,
+                    auth=auth,
+                    headers={"User-Agent": USER_AGENT},
+                    verify=self.verify,
+                    cert=self.cert,
+                )
+        except requests.RequestException as exc:
+            raise OAuth2Error(f"Failed to obtain an OAuth 2 access token from {self.token_url}: {exc}") from exc
+        try:
+            payload = response.json()
+        except ValueError:
+            payload = None
+        if response.status_code != 200 or not isinstance(payload, dict) or "access_token" not in payload:
+            raise OAuth2Error(
+                f"Failed to obtain an OAuth 2 access token from {self.token_url}. "
+                f"Received {response.status_code}: {response.text}"
+            )
+        return payload
+
+    def _store_token(self, payload: Dict[str, Any]) -> None:
+        self._access_token = payload["access_token"]
+        self._issued_tokens.add(self._access_token)
+        self._refresh_token = payload.get("refresh_token", self._refresh_token)
+        expires_in = payload.get("expires_in")
+        if expires_in is not None:
+            self._expires_at = time.monotonic() + max(float(expires_in) - EXPIRATION_LEEWAY, 0)
+        else:
+            self._expires_at = None
+
+
+def _get_bearer_token(authorization: Optional[str]) -> Optional[str]:
+    if authorization is not None and authorization.startswith("Bearer "):
+        return authorization[len("Bearer ") :]
+    return None
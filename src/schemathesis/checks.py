@@ -1,16 +1,27 @@
-from typing import TYPE_CHECKING, Optional, Tuple
+from typing import TYPE_CHECKING, Any, Callable, Dict, List, Optional, Tuple
 
-from .exceptions import get_status_code_error
+import attr
+import requests
+
+from .exceptions import (
+    get_negative_data_accepted_error,
+    get_object_level_authorization_error,
+    get_resource_availability_error,
+    get_status_code_error,
+    get_use_after_free_error,
+)
 from .specs.openapi.checks import (
     content_type_conformance,
+    ignored_auth,
     response_headers_conformance,
     response_schema_conformance,
     status_code_conformance,
 )
+from .types import AuthProfiles
 from .utils import GenericResponse
 
 if TYPE_CHECKING:
-    from .models import Case, CheckFunction
+    from .models import Case, CaseSource, CheckFunction
 
 
 def not_a_server_error(response: GenericResponse, case: "Case") -> Optional[bool]:  # pylint: disable=useless-return
@@ -21,11 +32,168 @@ def not_a_server_error(response: GenericResponse, case: "Case") -> Optional[bool
     return None
 
 
+def negative_data_rejection(response: GenericResponse, case: "Case") -> Optional[bool]:
+    """A check to verify that data, which doesn't match the API schema, is not accepted with a 2xx status code.
+
+    It is applicable only to cases generated with the `negative` data generation method.
+    """
+    if case.mutation is not None and 200 <= response.status_code < 300:
+        message = (
+            f"Accepted a request that violates the API schema: {case.mutation}\n\n"
+            f"Expected a 4xx response, received: {response.status_code}"
+        )
+        exc_class = get_negative_data_accepted_error(message)
+        raise exc_class(message)
+    return None
+
+
+def use_after_free(response: GenericResponse, case: "Case") -> Optional[bool]:
+    """A check to verify that resources are not available after they were deleted.
+
+    It is applicable only to stateful tests, where resources are deleted via links.
+    """
+    if case.deleted_by is not None and 200 <= response.status_code < 300:
+        message = (
+            f"Resource `{case.deleted_by.case.formatted_path}` is available after it was deleted\n\n"
+            f"Expected a 404 or 410 response, received: {response.status_code}\n\n"
+            f"Call sequence:\n\n{_format_call_sequence(case.deleted_by, case, response)}"
+        )
+        exc_class = get_use_after_free_error(case.deleted_by.case.operation.verbose_name, case.operation.verbose_name)
+        raise exc_class(message)
+    return None
+
+
+def ensure_resource_availability(response: GenericResponse, case: "Case") -> Optional[bool]:
+    """A check to verify that a freshly created resource is available.
+
+    It is applicable only to stateful tests, where the created resource is read via a link. Which links lead from
+    creating a resource to reading it is decided by the stateful testing machinery, see `Case.created_by`.
+    """
+    created_by = case.created_by
+    if created_by is not None and response.status_code == 404:
+        message = (
+            f"Resource created by `{created_by.case.operation.verbose_name}` is not available\n\n"
+            f"Expected a 2xx response for `{case.method} {case.formatted_path}`, received: 404\n\n"
+            f"Call sequence:\n\n{_format_call_sequence(created_by, case, response)}"
+        )
+        exc_class = get_resource_availability_error(created_by.case.operation.verbose_name, case.operation.verbose_name)
+        raise exc_class(message)
+    return None
+
+
+def make_object_level_authorization_check(profiles: AuthProfiles) -> "ObjectLevelAuthorizationCheck":
+    """Create a check that verifies that resources of the first identity are not available to other identities.
+
+    It is applicable to cases that use data from earlier calls, i.e. to ones generated via links or the `add_case`
+    hook. Successful read and update requests of the first identity are replayed with credentials of each other
+    identity. ``DELETE`` requests are sent with credentials of other identities before the call of the first one,
+    as the resource no longer exists after it.
+    """
+    return ObjectLevelAuthorizationCheck(profiles)
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class ObjectLevelAuthorizationCheck:
+    """See `make_object_level_authorization_check`."""
+
+    profiles: AuthProfiles = attr.ib()  # pragma: no mutate
+    # Responses to `DELETE` requests sent with credentials of other identities, by the `id` of their cases
+    _deletions: Dict[int, Dict[str, GenericResponse]] = attr.ib(factory=dict)  # pragma: no mutate
+    # Used to report results of this check
+    __name__ = "broken_object_level_authorization"
+
+    def before_call(
+        self, case: "Case", headers: Dict[str, Any], send: Callable[[Dict[str, Any]], GenericResponse]
+    ) -> None:
+        """Send a `DELETE` request with credentials of each other identity before the call of the first one.
+
+        :param headers: Headers of the first identity's call.
+        :param send: Sends the case with the given headers.
+        """
+        if case.method != "DELETE" or case.source is None:
+            return
+        owner, *others = self.profiles
+        self._deletions[id(case)] = {
+            identity: send(_with_identity_headers(headers, self.profiles[owner], self.profiles[identity]))
+            for identity in others
+        }
+
+    def __call__(self, response: GenericResponse, case: "Case") -> Optional[bool]:
+        deletions = self._deletions.pop(id(case), {})
+        if case.source is None or case.method not in ("GET", "PUT", "PATCH", "DELETE"):
+            return None
+        # If another identity deleted the resource, then the first one can't do it and receives an error
+        if case.method != "DELETE" and not 200 <= response.status_code < 300:
+            return None
+        owner, *others = self.profiles
+        for identity in others:
+            if case.method == "DELETE":
+                replayed = deletions.get(identity)
+                if replayed is None:
+                    continue
+            else:
+                replayed = case.replay(_with_identity(response.request, self.profiles[owner], self.profiles[identity]))
+            if 200 <= replayed.status_code < 300:
+                message = (
+                    f"`{identity}` has access to a resource of `{owner}`\n\n"
+                    f"Expected a 401, 403 or 404 response for `{identity}`, received: {replayed.status_code}\n\n"
+                    f"Call sequence as `{owner}`:\n\n{_format_call_sequence(case.source, case, response)}"
+                )
+                exc_class = get_object_level_authorization_error(case.operation.verbose_name, identity)
+                raise exc_class(message)
+        return None
+
+
+def _with_identity_headers(headers: Dict[str, Any], owner: Dict[str, str], identity: Dict[str, str]) -> Dict[str, Any]:
+    """Replace credentials of the owner with credentials of another identity."""
+    owner_names = {name.lower() for name in owner}
+    return {**{key: value for key, value in headers.items() if key.lower() not in owner_names}, **identity}
+
+
+def _with_identity(
+    request: requests.PreparedRequest, owner: Dict[str, str], identity: Dict[str, str]
+) -> requests.PreparedRequest:
+    """Replace credentials of the owner with credentials of another identity."""
+    request = request.copy()
+    # Authentication hooks, e.g. Digest, would send the owner's credentials on a 401 response
+    request.hooks = requests.hooks.default_hooks()
+    for name in owner:
+        request.headers.pop(name, None)
+    request.headers.update(identity)
+    return request
+
+
+def _format_call_sequence(source: "CaseSource", case: "Case", response: GenericResponse) -> str:
+    """Numbered list of calls that led to the given one, including itself."""
+    calls = [
+        f"{item.case.method} {item.case.formatted_path} -> {item.response.status_code}"
+        for item in _get_call_sequence(source)
+    ]
+    calls.append(f"{case.method} {case.formatted_path} -> {response.status_code}")
+    return "\n".join(f"    {idx}. {call}" for idx, call in enumerate(calls, 1))
+
+
+def _get_call_sequence(source: "CaseSource") -> List["CaseSource"]:
+    """Calls that led to the given one, including itself. The earliest call goes first."""
+    sequence: List["CaseSource"] = []
+    current: Optional["CaseSource"] = source
+    while current is not None:
+        sequence.append(current)
+        current = current.case.source
+    return sequence[::-1]
+
+
 DEFAULT_CHECKS: Tuple["CheckFunction", ...] = (not_a_server_error,)
 OPTIONAL_CHECKS = (
     status_code_conformance,
     content_type_conformance,
     response_headers_conformance,
     response_schema_conformance,
+    negative_data_rejection,
+    use_after_free,
+    ensure_resource_availability,
 )
 ALL_CHECKS: Tuple["CheckFunction", ...] = DEFAULT_CHECKS + OPTIONAL_CHECKS
+# Checks that send additional requests for each test case. They are not a part of `ALL_CHECKS` and should be selected
+# explicitly
+EXTRA_CHECKS: Tuple["CheckFunction", ...] = (ignored_auth,)
@@ -6,7 +6,9 @@ from collections import defaultdict
 from enum import Enum
 from typing import Any, Callable, Dict, Generator, Iterable, List, Optional, Tuple, Union
 from urllib.parse import urlparse
+from xml.etree import ElementTree
 
+import attr
 import click
 import hypothesis
 import yaml
@@ -15,6 +17,7 @@ from .. import checks as checks_module
 from .. import fixups as _fixups
 from .. import runner
 from .. import targets as targets_module
+from ..auth import OAuth2Config
 from ..constants import (
     DEFAULT_DATA_GENERATION_METHODS,
     DEFAULT_STATEFUL_RECURSION_LIMIT,
@@ -25,19 +28,24 @@ from ..fixups import ALL_FIXUPS
 from ..hooks import GLOBAL_HOOK_DISPATCHER, HookContext, HookDispatcher, HookScope
 from ..models import CheckFunction
 from ..runner import events, prepare_hypothesis_settings
+from ..runstate import RerunFailed, RunState
 from ..schemas import BaseSchema
+from ..sessions import SessionConfig
+from ..sharding import Shard, load_junit_durations
 from ..specs.openapi import loaders as oas_loaders
 from ..stateful import Stateful
+from ..throttling import RateLimiter
 from ..targets import Target
-from ..types import Filter
+from ..types import AuthProfiles, Filter, RequestCert
 from ..utils import file_exists, get_requests_auth, import_app
 from . import callbacks, cassettes, output
-from .constants import DEFAULT_WORKERS, MAX_WORKERS, MIN_WORKERS
+from .constants import DEFAULT_RUN_STATE_FILE, DEFAULT_WORKERS, MAX_WORKERS, MIN_WORKERS
 from .context import ExecutionContext
 from .debug import DebugOutputHandler
 from .handlers import EventHandler
 from .junitxml import JunitXMLHandler
-from .options import CSVOption, CustomHelpMessageChoice, NotSet, OptionalInt
+from .options import CSVOption, CustomHelpMessageChoice, NotSet, OptionalInt, not_set
+from .runstate import RunStateHandler
 
 try:
     from yaml import CSafeLoader as SafeLoader
@@ -53,7 +61,7 @@ def _get_callable_names(items: Tuple[Callable, ...]) -> Tuple[str, ...]:
 CONTEXT_SETTINGS = {"help_option_names": ["-h", "--help"]}
 
 DEFAULT_CHECKS_NAMES = _get_callable_names(checks_module.DEFAULT_CHECKS)
-ALL_CHECKS_NAMES = _get_callable_names(checks_module.ALL_CHECKS)
+ALL_CHECKS_NAMES = _get_callable_names(checks_module.ALL_CHECKS + checks_module.EXTRA_CHECKS)
 CHECKS_TYPE = click.Choice((*ALL_CHECKS_NAMES, "all"))
 
 DEFAULT_TARGETS_NAMES = _get_callable_names(targets_module.DEFAULT_TARGETS)
@@ -92,7 +100,7 @@ def reset_checks() -> None:
     """Get checks list to their default state."""
     # Useful in tests
     checks_module.ALL_CHECKS = checks_module.DEFAULT_CHECKS + checks_module.OPTIONAL_CHECKS
-    CHECKS_TYPE.choices = _get_callable_names(checks_module.ALL_CHECKS) + ("all",)
+    CHECKS_TYPE.choices = _get_callable_names(checks_module.ALL_CHECKS + checks_module.EXTRA_CHECKS) + ("all",)
 
 
 def reset_targets() -> None:
@@ -212,6 +220,20 @@ class GroupedOption(click.Option):
     help="Exit instantly on first error or failed test.",
     show_default=True,
 )
+@click.option(
+    "--last-failed",
+    "last_failed",
+    is_flag=True,
+    default=False,
+    help="Test only API operations that failed in the previous run. Their failing test cases are replayed first.",
+)
+@click.option(
+    "--failed-first",
+    "failed_first",
+    is_flag=True,
+    default=False,
+    help="Test API operations that failed in the previous run before the other ones.",
+)
 @click.option(
     "--dry-run",
     "dry_run",
@@ -220,6 +242,19 @@ class GroupedOption(click.Option):
     help="Disable sending data to the application and checking responses. "
     "Helpful to verify whether data is generated at all.",
 )
+@click.option(
+    "--max-duration",
+    help="Maximum duration of the whole test run in seconds. When it is reached, no new tests are started, "
+    "and the running ones are finished.",
+    type=click.IntRange(1),
+    default=None,
+)
+@click.option(
+    "--max-operation-duration",
+    help="Maximum duration of testing a single API operation in seconds.",
+    type=click.IntRange(1),
+    default=None,
+)
 @click.option(
     "--auth", "-a", help="Server user and password. Example: USER:PASSWORD", type=str, callback=callbacks.validate_auth
 )
@@ -240,6 +275,26 @@ class GroupedOption(click.Option):
     type=str,
     callback=callbacks.validate_headers,
 )
+@click.option(
+    "--auth-profile",
+    "auth_profiles",
+    help=r"Named credentials header to test access to resources of other users. Resources are created with the "
+    r"first profile and are accessed with the other ones. Example: alice:Authorization: Bearer\ 123",
+    multiple=True,
+    type=str,
+    callback=callbacks.validate_auth_profiles,
+)
+@click.option("--oauth2-client-id", help="OAuth 2 client ID. Enables OAuth 2 authentication.", type=str)
+@click.option("--oauth2-client-secret", help="OAuth 2 client secret.", type=str)
+@click.option("--oauth2-token-url", help="OAuth 2 token URL. By default, it is taken from the API schema.", type=str)
+@click.option("--oauth2-scope", "oauth2_scopes", help="OAuth 2 scope to request.", multiple=True, type=str)
+@click.option(
+    "--oauth2-user",
+    help="Resource owner credentials for the OAuth 2 password grant. The client credentials grant is used if not set. "
+    "Example: USER:PASSWORD",
+    type=str,
+    callback=callbacks.validate_auth,
+)
 @click.option(
     "--endpoint",
     "-E",
@@ -319,6 +374,54 @@ class GroupedOption(click.Option):
     show_default=True,
     callback=callbacks.convert_request_tls_verify,
 )
+@click.option(
+    "--request-cert",
+    help="File path of unencrypted client certificate for authentication. "
+    "The certificate can be bundled with a private key (e.g. PEM) or the private key "
+    "can be provided with the --request-cert-key argument.",
+    type=click.Path(exists=True),
+    default=None,
+)
+@click.option(
+    "--request-cert-key",
+    help="File path of the private key of the client certificate.",
+    type=click.Path(exists=True),
+    default=None,
+)
+@click.option(
+    "--proxy",
+    help="Proxy URL for all network requests. Hosts listed in the NO_PROXY environment variable are not proxied.",
+    type=str,
+    default=None,
+    callback=callbacks.validate_proxy,
+)
+@click.option(
+    "--request-pool-size",
+    help="Maximum number of connections to keep in the pool for each host.",
+    type=click.IntRange(1),
+    default=None,
+)
+@click.option(
+    "--resolve",
+    help="Connect to ADDRESS instead of the address that HOST is resolved to. Could be used multiple times.",
+    metavar="HOST:ADDRESS",
+    type=str,
+    multiple=True,
+    callback=callbacks.validate_resolve,
+)
+@click.option(
+    "--rate-limit",
+    help="The maximum rate of requests to the API, e.g. 100/s, 1000/m or 5000/h. It is shared by all workers.",
+    type=str,
+    default=None,
+    callback=callbacks.convert_rate_limit,
+)
+@click.option(
+    "--request-retries",
+    help="How many times to retry requests that receive 429 or 503 responses. The Retry-After header is respected.",
+    type=click.IntRange(0),
+    default=0,
+)
 @click.option(
     "--validate-schema",
     help="Enable or disable validation of input schema.",
@@ -338,6 +441,25 @@ class GroupedOption(click.Option):
     cls=GroupedOption,
     group=ParameterGroup.filtering,
 )
+@click.option(
+    "--shard",
+    help="Test only a part of API operations, e.g. 2/4 for the second part out of four. "
+    "Use it to split testing between parallel jobs.",
+    type=str,
+    default=None,
+    callback=callbacks.convert_shard,
+    cls=GroupedOption,
+    group=ParameterGroup.filtering,
+)
+@click.option(
+    "--shard-durations",
+    help="JUnit XML report of a previous run to balance the parts by testing durations. "
+    "Can be passed multiple times.",
+    type=click.Path(exists=True),
+    multiple=True,
+    cls=GroupedOption,
+    group=ParameterGroup.filtering,
+)
 @click.option(
     "--junit-xml", help="Create junit-xml style report file at given path.", type=click.File("w", encoding="utf-8")
 )
@@ -346,6 +468,14 @@ class GroupedOption(click.Option):
     help="Save debug output as JSON lines in the given file.",
     type=click.File("w", encoding="utf-8"),
 )
+@click.option(
+    "--run-state-file",
+    help=(
+        "File to store failures between runs. They are stored only if this option, --last-failed, or --failed-first "
+        f"is passed, in {DEFAULT_RUN_STATE_FILE} by default."
+    ),
+    type=click.Path(dir_okay=False),
+)
 @click.option(
     "--show-errors-tracebacks",
     help="Show full tracebacks for internal errors.",
@@ -388,7 +518,7 @@ class GroupedOption(click.Option):
 @click.option(
     "--force-schema-version",
     help="Force Schemathesis to parse the input schema with the specified spec version.",
-    type=click.Choice(["20", "30"]),
+    type=click.Choice(["20", "30", "31"]),
 )
 @click.option(
     "--hypothesis-deadline",
@@ -459,12 +589,22 @@ def run(
     auth: Optional[Tuple[str, str]],
     auth_type: str,
     headers: Dict[str, str],
+    auth_profiles: AuthProfiles,
+    oauth2_client_id: Optional[str] = None,
+    oauth2_client_secret: Optional[str] = None,
+    oauth2_token_url: Optional[str] = None,
+    oauth2_scopes: Tuple[str, ...] = (),
+    oauth2_user: Optional[Tuple[str, str]] = None,
     checks: Iterable[str] = DEFAULT_CHECKS_NAMES,
     data_generation_methods: Tuple[DataGenerationMethod, ...] = DEFAULT_DATA_GENERATION_METHODS,
     max_response_time: Optional[int] = None,
     targets: Iterable[str] = DEFAULT_TARGETS_NAMES,
     exit_first: bool = False,
+    last_failed: bool = False,
+    failed_first: bool = False,
     dry_run: bool = False,
+    max_duration: Optional[int] = None,
+    max_operation_duration: Optional[int] = None,
     endpoints: Optional[Filter] = None,
     methods: Optional[Filter] = None,
     tags: Optional[Filter] = None,
@@ -474,10 +614,20 @@ def run(
     app: Optional[str] = None,
     request_timeout: Optional[int] = None,
     request_tls_verify: bool = True,
+    request_cert: Optional[str] = None,
+    request_cert_key: Optional[str] = None,
+    proxy: Optional[str] = None,
+    request_pool_size: Optional[int] = None,
+    resolve: Optional[Dict[str, str]] = None,
+    rate_limit: Optional[RateLimiter] = None,
+    request_retries: int = 0,
     validate_schema: bool = True,
     skip_deprecated_operations: bool = False,
+    shard: Optional[Shard] = None,
+    shard_durations: Tuple[str, ...] = (),
     junit_xml: Optional[click.utils.LazyFile] = None,
     debug_output_file: Optional[click.utils.LazyFile] = None,
+    run_state_file: Optional[str] = None,
     show_errors_tracebacks: bool = False,
     code_sample_style: CodeSampleStyle = CodeSampleStyle.default(),
     store_network_log: Optional[click.utils.LazyFile] = None,
@@ -503,18 +653,36 @@ def run(
     # pylint: disable=too-many-locals
     maybe_disable_color(ctx, no_color)
     check_auth(auth, headers)
+    check_auth_profiles(auth, auth_profiles)
+    oauth2 = get_oauth2_config(
+        oauth2_client_id, oauth2_client_secret, oauth2_token_url, oauth2_scopes, oauth2_user, auth, headers
+    )
+    prepared_request_cert = prepare_request_cert(request_cert, request_cert_key)
+    session_config = get_session_config(proxy, request_pool_size, resolve, rate_limit, request_retries)
+    shard = get_shard(shard, shard_durations)
+    if run_state_file is None and (last_failed or failed_first):
+        run_state_file = DEFAULT_RUN_STATE_FILE
+    run_state = load_run_state(run_state_file, is_required=last_failed or failed_first)
+    rerun_failed = RerunFailed(state=run_state, only_failed=last_failed) if last_failed or failed_first else None
     selected_targets = tuple(target for target in targets_module.ALL_TARGETS if target.__name__ in targets)
 
+    # Extra checks send additional requests, therefore `all` doesn't include them
+    extra_checks = tuple(check for check in checks_module.EXTRA_CHECKS if check.__name__ in checks)
     if "all" in checks:
-        selected_checks = checks_module.ALL_CHECKS
+        selected_checks = checks_module.ALL_CHECKS + extra_checks
     else:
-        selected_checks = tuple(check for check in checks_module.ALL_CHECKS if check.__name__ in checks)
+        selected_checks = (
+            tuple(check for check in checks_module.ALL_CHECKS if check.__name__ in checks) + extra_checks
+        )
 
     if fixups:
         if "all" in fixups:
             _fixups.install()
         else:
             _fixups.install(fixups)
+    if hypothesis_deadline is None and session_config is not None and session_config.has_waits:
+        # These waits happen inside tests and could be much longer than the default deadline
+        hypothesis_deadline = not_set
     hypothesis_settings = prepare_hypothesis_settings(
         deadline=hypothesis_deadline,
         derandomize=hypothesis_derandomize,
@@ -533,9 +701,13 @@ def run(
         data_generation_methods=data_generation_methods,
         force_schema_version=force_schema_version,
         request_tls_verify=request_tls_verify,
+        request_cert=prepared_request_cert,
+        session_config=session_config,
         auth=auth,
         auth_type=auth_type,
         headers=headers,
+        auth_profiles=auth_profiles,
+        oauth2=oauth2,
         endpoint=endpoints or None,
         method=methods or None,
         tag=tags or None,
@@ -544,6 +716,10 @@ def run(
         seed=hypothesis_seed,
         exit_first=exit_first,
         dry_run=dry_run,
+        max_duration=max_duration,
+        max_operation_duration=max_operation_duration,
+        shard=shard,
+        rerun_failed=rerun_failed,
         store_interactions=store_network_log is not None,
         checks=selected_checks,
         max_response_time=max_response_time,
@@ -563,6 +739,9 @@ def run(
         verbosity,
         code_sample_style,
         debug_output_file,
+        # Nothing is checked in dry runs, and their results should not replace the real ones
+        run_state_file if not dry_run else None,
+        run_state,
     )
 
 
@@ -576,10 +755,14 @@ def into_event_stream(
     data_generation_methods: Tuple[DataGenerationMethod, ...],
     force_schema_version: Optional[str],
     request_tls_verify: Union[bool, str],
+    request_cert: Optional[RequestCert],
+    session_config: Optional[SessionConfig],
     # Network request parameters
     auth: Optional[Tuple[str, str]],
     auth_type: Optional[str],
     headers: Optional[Dict[str, str]],
+    auth_profiles: Optional[AuthProfiles],
+    oauth2: Optional[OAuth2Config],
     request_timeout: Optional[int],
     # Schema filters
     endpoint: Optional[Filter],
@@ -595,6 +778,10 @@ def into_event_stream(
     seed: Optional[int],
     exit_first: bool,
     dry_run: bool,
+    max_duration: Optional[int],
+    max_operation_duration: Optional[int],
+    shard: Optional[Shard],
+    rerun_failed: Optional[RerunFailed],
     store_interactions: bool,
     stateful: Optional[Stateful],
     stateful_recursion_limit: int,
@@ -611,6 +798,8 @@ def into_event_stream(
             data_generation_methods=data_generation_methods,
             force_schema_version=force_schema_version,
             request_tls_verify=request_tls_verify,
+            request_cert=request_cert,
+            session_config=session_config,
             auth=auth,
             auth_type=auth_type,
             headers=headers,
@@ -624,11 +813,19 @@ def into_event_stream(
             auth=auth,
             auth_type=auth_type,
             headers=headers,
+            auth_profiles=auth_profiles,
+            oauth2=oauth2,
             request_timeout=request_timeout,
             request_tls_verify=request_tls_verify,
+            request_cert=request_cert,
+            session_config=session_config,
             seed=seed,
             exit_first=exit_first,
             dry_run=dry_run,
+            max_duration=max_duration,
+            max_operation_duration=max_operation_duration,
+            shard=shard,
+            rerun_failed=rerun_failed,
             store_interactions=store_interactions,
             checks=checks,
             max_response_time=max_response_time,
@@ -652,6 +849,8 @@ def load_schema(
     data_generation_methods: Tuple[DataGenerationMethod, ...],
     force_schema_version: Optional[str],
     request_tls_verify: Union[bool, str],
+    request_cert: Optional[RequestCert],
+    session_config: Optional[SessionConfig],
     # Network request parameters
     auth: Optional[Tuple[str, str]],
     auth_type: Optional[str],
@@ -673,6 +872,8 @@ def load_schema(
         data_generation_methods=data_generation_methods,
         force_schema_version=force_schema_version,
         request_tls_verify=request_tls_verify,
+        request_cert=request_cert,
+        session_config=session_config,
         auth=auth,
         auth_type=auth_type,
         headers=headers,
@@ -715,6 +916,8 @@ def get_loader_kwargs(
     auth_type: Optional[str],
     headers: Optional[Dict[str, str]],
     request_tls_verify: Union[bool, str],
+    request_cert: Optional[RequestCert],
+    session_config: Optional[SessionConfig],
 ) -> Dict[str, Any]:
     """Detect the proper set of parameters for a loader."""
     # These kwargs are shared by all loaders
@@ -734,8 +937,12 @@ def get_loader_kwargs(
         kwargs["headers"] = headers
     if loader in (oas_loaders.from_uri, oas_loaders.from_aiohttp):
         kwargs["verify"] = request_tls_verify
+        if request_cert is not None:
+            kwargs["cert"] = request_cert
         if auth is not None:
             kwargs["auth"] = get_requests_auth(auth, auth_type)
+    if loader is oas_loaders.from_uri and session_config is not None:
+        kwargs["session_config"] = session_config
     return kwargs
 
 
@@ -744,6 +951,92 @@ def check_auth(auth: Optional[Tuple[str, str]], headers: Dict[str, str]) -> None
         raise click.BadParameter("Passing `--auth` together with `--header` that sets `Authorization` is not allowed.")
 
 
+def prepare_request_cert(cert: Optional[str], key: Optional[str]) -> Optional[RequestCert]:
+    if key is not None:
+        if cert is None:
+            raise click.BadParameter("Passing `--request-cert-key` without `--request-cert` is not allowed.")
+        return (cert, key)
+    return cert
+
+
+def get_session_config(
+    proxy: Optional[str],
+    pool_size: Optional[int],
+    resolve: Optional[Dict[str, str]],
+    rate_limiter: Optional[RateLimiter],
+    retries: int,
+) -> Optional[SessionConfig]:
+    if proxy is None and pool_size is None and not resolve and rate_limiter is None and not retries:
+        return None
+    return SessionConfig(
+        proxy=proxy, pool_size=pool_size, resolve=resolve or {}, rate_limiter=rate_limiter, retries=retries
+    )
+
+
+def get_shard(shard: Optional[Shard], durations_paths: Tuple[str, ...]) -> Optional[Shard]:
+    if not durations_paths:
+        return shard
+    if shard is None:
+        raise click.BadParameter("`--shard-durations` can be used only together with `--shard`.")
+    durations: Dict[str, float] = {}
+    for path in durations_paths:
+        try:
+            report = load_junit_durations(path)
+        except (ElementTree.ParseError, ValueError) as exc:
+            raise click.BadParameter(f"Invalid JUnit XML report at {path}: {exc}") from exc
+        for name, duration in report.items():
+            durations[name] = durations.get(name, 0.0) + duration
+    return attr.evolve(shard, durations=durations)
+
+
+def load_run_state(path: Optional[str], is_required: bool) -> RunState:
+    if path is None:
+        # Failures are not stored in this run
+        return RunState()
+    try:
+        return RunState.load(path)
+    except ValueError as exc:
+        if is_required:
+            raise click.BadParameter(f"Invalid run state file at {path}: {exc}") from exc
+        # The state is not used in this run, and the file is overwritten when the run is finished
+        return RunState()
+
+
+def check_auth_profiles(auth: Optional[Tuple[str, str]], auth_profiles: AuthProfiles) -> None:
+    if auth is not None and auth_profiles:
+        raise click.BadParameter("Passing `--auth` together with `--auth-profile` is not allowed.")
+
+
+def get_oauth2_config(
+    client_id: Optional[str],
+    client_secret: Optional[str],
+    token_url: Optional[str],
+    scopes: Tuple[str, ...],
+    user: Optional[Tuple[str, str]],
+    auth: Optional[Tuple[str, str]],
+    headers: Dict[str, str],
+) -> Optional[OAuth2Config]:
+    if client_id is None:
+        if client_secret is not None or token_url is not None or scopes or user is not None:
+            raise click.BadParameter("OAuth 2 options require `--oauth2-client-id`.")
+        return None
+    if auth is not None:
+        raise click.BadParameter("Passing `--auth` together with `--oauth2-client-id` is not allowed.")
+    if "authorization" in {header.lower() for header in headers}:
+        raise click.BadParameter(
+            "Passing `--oauth2-client-id` together with `--header` that sets `Authorization` is not allowed."
+        )
+    username, password = user if user is not None else (None, None)
+    return OAuth2Config(
+        client_id=client_id,
+        client_secret=client_secret,
+        token_url=token_url,
+        scopes=scopes,
+        username=username,
+        password=password,
+    )
+
+
 def get_output_handler(workers_num: int) -> EventHandler:
     if workers_num > 1:
         output_style = OutputStyle.short
@@ -781,6 +1074,8 @@ def execute(
     verbosity: int,
     code_sample_style: CodeSampleStyle,
     debug_output_file: Optional[click.utils.LazyFile],
+    run_state_file: Optional[str] = None,
+    run_state: Optional[RunState] = None,
 ) -> None:
     """Execute a prepared runner by drawing events from it and passing to a proper handler."""
     handlers: List[EventHandler] = []
@@ -792,6 +1087,9 @@ def execute(
         # This handler should be first to have logs writing completed when the output handler will display statistic
         handlers.append(cassettes.CassetteWriter(store_network_log))
     handlers.append(get_output_handler(workers_num))
+    if run_state_file is not None:
+        # After the output handler, so possible warnings are displayed after the summary
+        handlers.append(RunStateHandler(run_state_file, run_state or RunState()))
     execution_context = ExecutionContext(
         workers_num=workers_num,
         show_errors_tracebacks=show_errors_tracebacks,
@@ -10,7 +10,10 @@ from requests import PreparedRequest, RequestException
 
 from .. import utils
 from ..constants import CodeSampleStyle
+from ..sharding import Shard
 from ..stateful import Stateful
+from ..throttling import RateLimiter
+from ..types import AuthProfiles
 from .constants import DEFAULT_WORKERS
 
 
@@ -113,6 +116,68 @@ def validate_headers(
     return headers
 
 
+def validate_auth_profiles(
+    ctx: click.core.Context, param: click.core.Parameter, raw_value: Tuple[str, ...]
+) -> AuthProfiles:
+    profiles: AuthProfiles = {}
+    for profile in raw_value:
+        try:
+            name, header = profile.split(":", maxsplit=1)
+        except ValueError as exc:
+            raise click.BadParameter(f"Should be in NAME:HEADER:VALUE format. Got: {profile}") from exc
+        name = name.strip()
+        if not name:
+            raise click.BadParameter("Profile name should not be empty")
+        profiles.setdefault(name, {}).update(validate_headers(ctx, param, (header,)))
+    if len(profiles) == 1:
+        raise click.BadParameter("At least two profiles are required to test access to resources of other users")
+    return profiles
+
+
+def validate_proxy(ctx: click.core.Context, param: click.core.Parameter, raw_value: Optional[str]) -> Optional[str]:
+    if raw_value is not None:
+        parsed = urlparse(raw_value)
+        if not parsed.scheme or not parsed.netloc:
+            raise click.BadParameter(f"Should be a URL with a scheme, e.g. http://127.0.0.1:8080. Got: {raw_value}")
+    return raw_value
+
+
+def validate_resolve(
+    ctx: click.core.Context, param: click.core.Parameter, raw_value: Tuple[str, ...]
+) -> Dict[str, str]:
+    resolve = {}
+    for item in raw_value:
+        try:
+            host, address = item.split(":", maxsplit=1)
+        except ValueError as exc:
+            raise click.BadParameter(f"Should be in HOST:ADDRESS format. Got: {item}") from exc
+        host, address = host.strip(), address.strip()
+        if not host or not address:
+            raise click.BadParameter(f"Should be in HOST:ADDRESS format. Got: {item}")
+        resolve[host] = address
+    return resolve
+
+
+def convert_shard(ctx: click.core.Context, param: click.core.Parameter, raw_value: Optional[str]) -> Optional[Shard]:
+    if raw_value is None:
+        return None
+    try:
+        return Shard.from_string(raw_value)
+    except ValueError as exc:
+        raise click.BadParameter(str(exc)) from exc
+
+
+def convert_rate_limit(
+    ctx: click.core.Context, param: click.core.Parameter, raw_value: Optional[str]
+) -> Optional[RateLimiter]:
+    if raw_value is None:
+        return None
+    try:
+        return RateLimiter.from_string(raw_value)
+    except ValueError as exc:
+        raise click.BadParameter(str(exc)) from exc
+
+
 def validate_regex(ctx: click.core.Context, param: click.core.Parameter, raw_value: Tuple[str, ...]) -> Tuple[str, ...]:
     for value in raw_value:
         try:
@@ -42,7 +42,7 @@ class CassetteWriter(EventHandler):
     def handle_event(self, context: ExecutionContext, event: events.ExecutionEvent) -> None:
         if isinstance(event, events.Initialized):
             # In the beginning we write metadata and start `http_interactions` list
-            self.queue.put(Initialize())
+            self.queue.put(Initialize(shard=event.shard))
         if isinstance(event, events.AfterExecution):
             # Seed is always present at this point, the original Optional[int] type is there because `TestResult`
             # instance is created before `seed` is generated on the hypothesis side
@@ -68,6 +68,8 @@ class CassetteWriter(EventHandler):
 class Initialize:
     """Start up, the first message to make preparations before proceeding the input data."""
 
+    shard: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+
 
 @attr.s(slots=True)  # pragma: no mutate
 class Process:
@@ -114,11 +116,17 @@ def worker(file_handle: click.utils.LazyFile, queue: Queue) -> None:
         return "~" if message is None else f"{repr(message)}"
 
     def format_checks(checks: List[SerializedCheck]) -> str:
+        if not checks:
+            # E.g. for retried requests
+            return "    []"
         return "\n".join(
             f"    - name: '{check.name}'\n      status: '{check.value.name.upper()}'\n      message: {format_check_message(check.message)}"
             for check in checks
         )
 
+    def format_shard(shard: Optional[str]) -> str:
+        return f"shard: '{shard}'\n" if shard is not None else ""
+
     def format_request_body(request: Request) -> str:
         if request.body is not None:
             return f"""    body:
@@ -139,7 +147,7 @@ def worker(file_handle: click.utils.LazyFile, queue: Queue) -> None:
             stream.write(
                 f"""command: '{get_command_representation()}'
 recorded_with: 'Schemathesis {constants.__version__}'
-http_interactions:"""
+{format_shard(item.shard)}http_interactions:"""
             )
         elif isinstance(item, Process):
             for interaction in item.interactions:
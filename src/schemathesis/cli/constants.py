@@ -1,3 +1,4 @@
 MIN_WORKERS = 1
 DEFAULT_WORKERS = MIN_WORKERS
 MAX_WORKERS = 64
+DEFAULT_RUN_STATE_FILE = ".schemathesis/run-state.json"
@@ -15,10 +15,12 @@ class JunitXMLHandler(EventHandler):
     file_handle: LazyFile = attr.ib()  # pragma: no mutate
     test_cases: List = attr.ib(factory=list)  # pragma: no mutate
     start_time: Optional[float] = attr.ib(default=None)  # pragma: no mutate
+    shard: Optional[str] = attr.ib(default=None)  # pragma: no mutate
 
     def handle_event(self, context: ExecutionContext, event: events.ExecutionEvent) -> None:
         if isinstance(event, events.Initialized):
             self.start_time = event.start_time
+            self.shard = event.shard
         if isinstance(event, events.AfterExecution):
             test_case = TestCase(
                 f"{event.result.method} {event.result.path}",
@@ -36,5 +38,9 @@ class JunitXMLHandler(EventHandler):
                 )
             self.test_cases.append(test_case)
         if isinstance(event, events.Finished):
-            test_suites = [TestSuite("schemathesis", test_cases=self.test_cases, hostname=platform.node())]
+            # Reports from different parallel jobs could be told apart by the shard they are produced by
+            properties = {"shard": self.shard} if self.shard is not None else None
+            test_suites = [
+                TestSuite("schemathesis", test_cases=self.test_cases, hostname=platform.node(), properties=properties)
+            ]
             to_xml_report_file(file_descriptor=self.file_handle, test_suites=test_suites, prettyprint=True)
@@ -330,6 +330,8 @@ def handle_initialized(context: ExecutionContext, event: events.Initialized) ->
     click.echo(f"Base URL: {event.base_url}")
     click.echo(f"Specification version: {event.specification_name}")
     click.echo(f"Workers: {context.workers_num}")
+    if event.shard is not None:
+        click.echo(f"Shard: {event.shard}")
     click.secho(f"Collected API operations: {context.operations_count}", bold=True)
     if context.operations_count >= 1:
         click.echo()
@@ -378,6 +380,11 @@ def handle_interrupted(context: ExecutionContext, event: events.Interrupted) ->
     display_section_name("KeyboardInterrupt", "!", bold=False)
 
 
+def handle_max_duration_reached(context: ExecutionContext, event: events.MaxDurationReached) -> None:
+    click.echo()
+    display_section_name(f"Max duration of {event.max_duration}s is reached", "!", bold=False)
+
+
 def handle_internal_error(context: ExecutionContext, event: events.InternalError) -> None:
     display_internal_error(context, event)
     raise click.Abort
@@ -397,5 +404,7 @@ class DefaultOutputStyleHandler(EventHandler):
             handle_finished(context, event)
         if isinstance(event, events.Interrupted):
             handle_interrupted(context, event)
+        if isinstance(event, events.MaxDurationReached):
+            handle_max_duration_reached(context, event)
         if isinstance(event, events.InternalError):
             handle_internal_error(context, event)
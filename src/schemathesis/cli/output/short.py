@@ -36,5 +36,7 @@ class ShortOutputStyleHandler(EventHandler):
             default.handle_finished(context, event)
         if isinstance(event, events.Interrupted):
             default.handle_interrupted(context, event)
+        if isinstance(event, events.MaxDurationReached):
+            default.handle_max_duration_reached(context, event)
         if isinstance(event, events.InternalError):
             default.handle_internal_error(context, event)
@@ -0,0 +1,48 @@
+from typing import Any, Dict, List, Optional, Set, Tuple
+
+import attr
+import click
+
+from ..constants import DataGenerationMethod
+from ..models import Status
+from ..runner import events
+from ..runner.serialization import SerializedCase
+from ..runstate import FailedTest, RunState
+from .handlers import EventHandler, ExecutionContext, get_unique_failures
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class RunStateHandler(EventHandler):
+    """Store failed tests in the run state file when the run is finished."""
+
+    path: str = attr.ib()  # pragma: no mutate
+    previous: RunState = attr.ib()  # pragma: no mutate
+    tested: Set[Tuple[str, DataGenerationMethod]] = attr.ib(factory=set)  # pragma: no mutate
+    failed: Dict[Tuple[str, DataGenerationMethod], FailedTest] = attr.ib(factory=dict)  # pragma: no mutate
+
+    def handle_event(self, context: ExecutionContext, event: events.ExecutionEvent) -> None:
+        if isinstance(event, events.AfterExecution):
+            key = (event.current_operation, DataGenerationMethod.from_short_name(event.result.data_generation_method))
+            self.tested.add(key)
+            if event.status in (Status.failure, Status.error):
+                # Additional stateful tests of the same API operation add their examples to the same failed test
+                failed = self.failed.setdefault(
+                    key, FailedTest(reference=key[0], data_generation_method=key[1], seed=event.result.seed)
+                )
+                # Only the final examples of distinct failures, without the ones found during shrinking
+                examples: List[Optional[SerializedCase]] = [
+                    check.example for check in get_unique_failures(event.result.checks)
+                ]
+                examples.extend(error.example for error in event.result.errors)
+                for data in get_examples_data(examples):
+                    if data not in failed.examples:
+                        failed.examples.append(data)
+        if isinstance(event, events.Finished):
+            try:
+                self.previous.update(self.tested, self.failed.values()).save(self.path)
+            except OSError as exc:
+                click.secho(f"Unable to save the run state to {self.path}: {exc}", fg="yellow")
+
+
+def get_examples_data(examples: List[Optional[SerializedCase]]) -> List[Dict[str, Any]]:
+    return [example.data for example in examples if example is not None and example.data is not None]
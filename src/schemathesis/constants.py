@@ -12,6 +12,8 @@ except metadata.PackageNotFoundError:
 USER_AGENT = f"schemathesis/{__version__}"
 DEFAULT_DEADLINE = 500  # pragma: no mutate
 DEFAULT_STATEFUL_RECURSION_LIMIT = 5  # pragma: no mutate
+# How many times recursive references are inlined before their optional parts are cut off
+DEFAULT_RECURSION_DEPTH = 5  # pragma: no mutate
 RECURSIVE_REFERENCE_ERROR_MESSAGE = (
     "Currently, Schemathesis can't generate data for this operation due to "
     "recursive references in the operation definition. See more information in "
@@ -29,6 +31,10 @@ class DataGenerationMethod(str, Enum):
 
     # Generate data, that fits the API schema
     positive = "positive"
+    # Generate data, that doesn't fit the API schema
+    negative = "negative"
+    # Generate data, that fits the API schema, starting from values at the edges of its constraints
+    boundary = "boundary"
 
     @classmethod
     def default(cls) -> "DataGenerationMethod":
@@ -37,8 +43,14 @@ class DataGenerationMethod(str, Enum):
     def as_short_name(self) -> str:
         return {
             DataGenerationMethod.positive: "P",
+            DataGenerationMethod.negative: "N",
+            DataGenerationMethod.boundary: "B",
         }[self]
 
+    @classmethod
+    def from_short_name(cls, value: str) -> "DataGenerationMethod":
+        return {method.as_short_name(): method for method in cls}[value]
+
 
 DEFAULT_DATA_GENERATION_METHODS = (DataGenerationMethod.default(),)
 
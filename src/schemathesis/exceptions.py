@@ -53,6 +53,32 @@ def get_status_code_error(status_code: int) -> Type[CheckFailed]:
     return get_exception(name)
 
 
+def get_negative_data_accepted_error(message: str) -> Type[CheckFailed]:
+    """Return new exception for invalid data that was accepted by the application."""
+    # Different mutations are reported as different errors
+    return _get_hashed_exception("NegativeDataAccepted", message)
+
+
+def get_use_after_free_error(deleted_by: str, accessed_by: str) -> Type[CheckFailed]:
+    """Return new exception for a resource that is available after it was deleted."""
+    return _get_hashed_exception("UseAfterFree", f"{deleted_by}{accessed_by}")
+
+
+def get_resource_availability_error(created_by: str, accessed_by: str) -> Type[CheckFailed]:
+    """Return new exception for a created resource that is not available."""
+    return _get_hashed_exception("EnsureResourceAvailability", f"{created_by}{accessed_by}")
+
+
+def get_object_level_authorization_error(operation: str, identity: str) -> Type[CheckFailed]:
+    """Return new exception for a resource that is available to an identity that does not own it."""
+    return _get_hashed_exception("BrokenObjectLevelAuthorization", f"{operation}{identity}")
+
+
+def get_ignored_auth_error(operation: str) -> Type[CheckFailed]:
+    """Return new exception for an API operation that does not enforce its authentication."""
+    return _get_hashed_exception("IgnoredAuth", operation)
+
+
 def get_response_type_error(expected: str, received: str) -> Type[CheckFailed]:
     """Return new exception for an unexpected response type."""
     name = f"SchemaValidationError{expected}_{received}"
@@ -84,6 +110,11 @@ def get_headers_error(message: str) -> Type[CheckFailed]:
     return _get_hashed_exception("MissingHeadersError", message)
 
 
+def get_header_validation_error(name: str, exception: ValidationError) -> Type[CheckFailed]:
+    """Return new exception for a header that does not conform to its schema."""
+    return _get_hashed_exception("HeaderValidationError", f"{name}{exception}")
+
+
 @attr.s(slots=True)
 class InvalidSchema(Exception):
     """Schema associated with an API operation contains an error."""
@@ -150,6 +181,18 @@ class SerializationNotPossible(Exception):
         return cls(SERIALIZATION_NOT_POSSIBLE_MESSAGE.format(", ".join(media_types)))
 
 
+class SchemaConversionError(Exception):
+    """A JSON Schema 2020-12 keyword can't be expressed via Draft 7 keywords."""
+
+    __module__ = "builtins"
+
+
+class OAuth2Error(Exception):
+    """Not possible to obtain an OAuth 2 access token."""
+
+    __module__ = "builtins"
+
+
 class InvalidRegularExpression(Exception):
     __module__ = "builtins"
 
@@ -1,4 +1,5 @@
 import asyncio
+import ssl
 from typing import Optional
 
 from aiohttp import web
@@ -6,7 +7,7 @@ from aiohttp import web
 from . import _server
 
 
-def _run_server(app: web.Application, port: int) -> None:
+def _run_server(app: web.Application, port: int, ssl_context: Optional[ssl.SSLContext] = None) -> None:
     """Run the given app on the given port.
 
     Intended to be called as a target for a separate thread.
@@ -17,11 +18,16 @@ def _run_server(app: web.Application, port: int) -> None:
     asyncio.set_event_loop(loop)
     runner = web.AppRunner(app)
     loop.run_until_complete(runner.setup())
-    site = web.TCPSite(runner, "127.0.0.1", port)
+    site = web.TCPSite(runner, "127.0.0.1", port, ssl_context=ssl_context)
     loop.run_until_complete(site.start())
     loop.run_forever()
 
 
-def run_server(app: web.Application, port: Optional[int] = None, timeout: float = 0.05) -> int:
+def run_server(
+    app: web.Application,
+    port: Optional[int] = None,
+    timeout: float = 0.05,
+    ssl_context: Optional[ssl.SSLContext] = None,
+) -> int:
     """Start a thread with the given aiohttp application."""
-    return _server.run(_run_server, app=app, port=port, timeout=timeout)
+    return _server.run(_run_server, app=app, port=port, timeout=timeout, ssl_context=ssl_context)
@@ -4,6 +4,7 @@ from enum import Enum, unique
 from typing import TYPE_CHECKING, Any, Callable, DefaultDict, Dict, List, Optional, Union, cast
 
 import attr
+import requests
 from hypothesis import strategies as st
 
 from .types import GenericTest
@@ -243,6 +244,14 @@ def add_case(context: HookContext, case: "Case", response: GenericResponse) -> O
     """
 
 
+@all_scopes
+def sign_request(context: HookContext, request: requests.PreparedRequest) -> None:
+    """Called with the final request right before it is sent. Might add or change its headers.
+
+    It is useful for APIs that require every request to be signed, for example, with HMAC.
+    """
+
+
 GLOBAL_HOOK_DISPATCHER = HookDispatcher(scope=HookScope.GLOBAL)
 dispatch = GLOBAL_HOOK_DISPATCHER.dispatch
 get_all_by_name = GLOBAL_HOOK_DISPATCHER.get_all_by_name
@@ -16,6 +16,7 @@ from typing import (
     Generic,
     Iterator,
     List,
+    MutableMapping,
     NoReturn,
     Optional,
     Sequence,
@@ -33,6 +34,7 @@ import requests
 import werkzeug
 from hypothesis import event, note, reject
 from hypothesis import strategies as st
+from starlette.applications import Starlette
 from starlette.testclient import TestClient as ASGIClient
 
 from . import serializers
@@ -42,7 +44,7 @@ from .hooks import GLOBAL_HOOK_DISPATCHER, HookContext, HookDispatcher
 from .parameters import Parameter, ParameterSet, PayloadAlternatives
 from .serializers import Serializer, SerializerContext
 from .types import Body, Cookies, FormData, Headers, NotSet, PathParameters, Query
-from .utils import NOT_SET, GenericResponse, WSGIResponse, deprecated_property, get_response_payload
+from .utils import NOT_SET, GenericResponse, WSGIResponse, deprecated_property, encode_query, get_response_payload
 
 if TYPE_CHECKING:
     from .schemas import BaseSchema
@@ -57,6 +59,16 @@ class CaseSource:
     response: GenericResponse = attr.ib()  # pragma: no mutate
 
 
+@attr.s(slots=True)  # pragma: no mutate
+class SendOptions:
+    """How a request was sent over the network, so other requests derived from it could be sent the same way."""
+
+    # The session is not stored if it was closed after the call
+    session: Optional[requests.Session] = attr.ib(default=None)  # pragma: no mutate
+    # E.g. `timeout`, `verify`, `cert` and `proxies`
+    kwargs: Dict[str, Any] = attr.ib(factory=dict)  # pragma: no mutate
+
+
 def cant_serialize(media_type: str) -> NoReturn:  # type: ignore
     """Reject the current example if we don't know how to send this data to the application."""
     event_text = f"Can't serialize data to `{media_type}`."
@@ -81,6 +93,14 @@ class Case:  # pylint: disable=too-many-public-methods
     source: Optional[CaseSource] = attr.ib(default=None)  # pragma: no mutate
     # The media type for cases with a payload. For example, "application/json"
     media_type: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+    # For negative test cases - how the data violates the API schema. E.g. "required query parameter `id` was missing"
+    mutation: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+    # For stateful tests - the successful call that deleted the resource this case accesses
+    deleted_by: Optional[CaseSource] = attr.ib(default=None)  # pragma: no mutate
+    # For stateful tests - the call that created the resource this case reads via a link
+    created_by: Optional[CaseSource] = attr.ib(default=None)  # pragma: no mutate
+    # Set by `call`, so requests replayed by checks use the same session, TLS settings, proxies and timeout
+    send_options: Optional[SendOptions] = attr.ib(default=None, eq=False)  # pragma: no mutate
 
     def __repr__(self) -> str:
         parts = [f"{self.__class__.__name__}("]
@@ -248,6 +268,17 @@ class Case:  # pylint: disable=too-many-public-methods
             return cls()
         return None
 
+    def _get_query(self) -> Union[Optional[Query], str]:
+        """Query parameters in a form acceptable by `requests` and `werkzeug`.
+
+        They both percent-encode reserved characters in query parameter values. If some parameters allow reserved
+        characters to be sent as is, then the query string is encoded explicitly.
+        """
+        allow_reserved = [parameter.name for parameter in self.operation.query if parameter.allow_reserved]
+        if not self.query or not allow_reserved:
+            return self.query
+        return encode_query(self.query, allow_reserved)
+
     def as_requests_kwargs(
         self, base_url: Optional[str] = None, headers: Optional[Dict[str, str]] = None
     ) -> Dict[str, Any]:
@@ -271,7 +302,7 @@ class Case:  # pylint: disable=too-many-public-methods
             "url": url,
             "cookies": self.cookies,
             "headers": final_headers,
-            "params": self.query,
+            "params": self._get_query(),
             **extra,
         }
 
@@ -290,11 +321,36 @@ class Case:  # pylint: disable=too-many-public-methods
             close_session = False
         data = self.as_requests_kwargs(base_url, headers)
         data.update(kwargs)
-        response = session.request(**data)  # type: ignore
+        self.send_options = SendOptions(
+            session=None if close_session else session,
+            kwargs={key: data[key] for key in REQUESTS_SEND_OPTIONS if key in data},
+        )
+        if self._has_request_signers():
+            response = self._send_signed(session, data)
+        else:
+            response = session.request(**data)  # type: ignore
         if close_session:
             session.close()
         return response
 
+    def _has_request_signers(self) -> bool:
+        schema = self.operation.schema
+        dispatchers = (GLOBAL_HOOK_DISPATCHER, schema.hooks, schema.get_local_hook_dispatcher())
+        return any(dispatcher is not None and dispatcher.get_all_by_name("sign_request") for dispatcher in dispatchers)
+
+    def _sign_request(self, request: requests.PreparedRequest) -> None:
+        """Pass the final request to the `sign_request` hooks."""
+        self.operation.schema.dispatch_hook("sign_request", HookContext(operation=self.operation), request)
+        # Authentication might change the request after it is sent, e.g. OAuth 2 retries it with a new token
+        request._schemathesis_sign = self._sign_request  # type: ignore
+
+    def _send_signed(self, session: requests.Session, data: Dict[str, Any]) -> requests.Response:
+        # The same steps as in `requests.Session.request`, but the request is signed after it is prepared
+        options = {key: data.pop(key) for key in REQUESTS_SEND_OPTIONS if key in data}
+        request = session.prepare_request(requests.Request(**data))
+        self._sign_request(request)
+        return _send_prepared(session, request, options)
+
     def as_werkzeug_kwargs(self, headers: Optional[Dict[str, str]] = None) -> Dict[str, Any]:
         """Convert the case into a dictionary acceptable by werkzeug.Client."""
         final_headers = self._get_headers(headers)
@@ -312,7 +368,7 @@ class Case:  # pylint: disable=too-many-public-methods
             "method": self.method,
             "path": self.operation.schema.get_full_path(self.formatted_path),
             "headers": final_headers,
-            "query_string": self.query,
+            "query_string": self._get_query(),
             **extra,
         }
 
@@ -324,11 +380,16 @@ class Case:  # pylint: disable=too-many-public-methods
                 "Please, set `app` argument in the schema constructor or pass it to `call_wsgi`"
             )
         data = self.as_werkzeug_kwargs(headers)
+        requests_kwargs = self.as_requests_kwargs(base_url=self.get_full_base_url(), headers=headers)
+        request = requests.Request(**requests_kwargs).prepare()
+        if self._has_request_signers():
+            original_headers = dict(request.headers)
+            self._sign_request(request)
+            _apply_header_changes(data["headers"], original_headers, request.headers)
         client = werkzeug.Client(application, WSGIResponse)
         with cookie_handler(client, self.cookies):
             response = client.open(**data, **kwargs)
-        requests_kwargs = self.as_requests_kwargs(base_url=self.get_full_base_url(), headers=headers)
-        response.request = requests.Request(**requests_kwargs).prepare()
+        response.request = request
         return response
 
     def call_asgi(
@@ -348,6 +409,38 @@ class Case:  # pylint: disable=too-many-public-methods
 
         return self.call(base_url=base_url, session=client, headers=headers, **kwargs)
 
+    def replay(self, request: requests.PreparedRequest, sign: bool = True) -> GenericResponse:
+        """Send an already prepared request, e.g. a modified copy of the request made for this case.
+
+        The request is sent to the WSGI / ASGI application if it is set, otherwise it is sent over the network with the
+        same session and options as in the last `call` of this case.
+
+        :param bool sign: Whether to pass the request to the `sign_request` hooks. Requests that should not carry valid
+            credentials must not be signed, as signers could add them back.
+        """
+        if sign and self._has_request_signers():
+            # The request is modified, therefore the original signature is no longer valid
+            self._sign_request(request)
+        if self.app is None:
+            options = self.send_options or SendOptions()
+            if options.session is not None:
+                return _send_prepared(options.session, request, options.kwargs)
+            with requests.Session() as session:
+                return _send_prepared(session, request, options.kwargs)
+        if isinstance(self.app, Starlette):
+            return ASGIClient(self.app).send(request)
+        parts = urlsplit(cast(str, request.url))
+        client = werkzeug.Client(self.app, WSGIResponse)
+        response = client.open(
+            method=request.method,
+            path=parts.path,
+            query_string=parts.query,
+            headers=list(request.headers.items()),
+            data=request.body,
+        )
+        response.request = request
+        return response
+
     def validate_response(
         self,
         response: GenericResponse,
@@ -423,6 +516,7 @@ class Case:  # pylint: disable=too-many-public-methods
             cookies=deepcopy(self.cookies),
             query=deepcopy(self.query),
             body=deepcopy(self.body),
+            mutation=self.mutation,
         )
 
 
@@ -439,6 +533,35 @@ def cookie_handler(client: werkzeug.Client, cookies: Optional[Cookies]) -> Gener
             client.delete_cookie("localhost", key)
 
 
+# Keyword arguments of `requests.Session.request` that are not used to prepare a request, but to send it
+REQUESTS_SEND_OPTIONS = ("timeout", "allow_redirects", "proxies", "stream", "verify", "cert")
+
+
+def _send_prepared(
+    session: requests.Session, request: requests.PreparedRequest, options: Dict[str, Any]
+) -> requests.Response:
+    """Send a prepared request the same way as `requests.Session.request` does, including session-level settings."""
+    options = dict(options)
+    settings = session.merge_environment_settings(
+        request.url,
+        options.pop("proxies", {}),
+        options.pop("stream", None),
+        options.pop("verify", None),
+        options.pop("cert", None),
+    )
+    return session.send(request, **options, **settings)
+
+
+def _apply_header_changes(headers: Dict[str, Any], before: Dict[str, str], after: MutableMapping[str, str]) -> None:
+    """Apply changes, made by request signers, to headers of a WSGI request."""
+    for name in before:
+        if name not in after:
+            headers.pop(name, None)
+    for name, value in after.items():
+        if before.get(name) != value:
+            headers[name] = value
+
+
 P = TypeVar("P", bound=Parameter)
 D = TypeVar("D")
 
@@ -489,6 +612,8 @@ class APIOperation(Generic[P, C]):
     cookies: ParameterSet[P] = attr.ib(factory=ParameterSet)  # pragma: no mutate
     query: ParameterSet[P] = attr.ib(factory=ParameterSet)  # pragma: no mutate
     body: PayloadAlternatives[P] = attr.ib(factory=PayloadAlternatives)  # pragma: no mutate
+    # Alternative sets of security parameters. Each generated case contains parameters from exactly one of them
+    security: List[ParameterSet[P]] = attr.ib(factory=list)  # pragma: no mutate
     case_cls: Type[C] = attr.ib(default=Case)
 
     @verbose_name.default
@@ -547,6 +672,10 @@ class APIOperation(Generic[P, C]):
         """Get examples from the API operation."""
         return self.schema.get_strategies_from_examples(self)
 
+    def get_strategies_from_boundaries(self) -> List[st.SearchStrategy[Case]]:
+        """Get cases with values at the edges of the API operation's constraints."""
+        return self.schema.get_strategies_from_boundaries(self)
+
     def get_stateful_tests(self, response: GenericResponse, stateful: Optional["Stateful"]) -> Sequence["StatefulTest"]:
         return self.schema.get_stateful_tests(response, self, stateful)
 
@@ -561,6 +690,9 @@ class APIOperation(Generic[P, C]):
     def prepare_multipart(self, form_data: FormData) -> Tuple[Optional[List], Optional[Dict[str, Any]]]:
         return self.schema.prepare_multipart(form_data, self)
 
+    def prepare_urlencoded(self, form_data: FormData) -> FormData:
+        return self.schema.prepare_urlencoded(form_data, self)
+
     def get_request_payload_content_types(self) -> List[str]:
         return self.schema.get_request_payload_content_types(self)
 
@@ -577,6 +709,7 @@ class APIOperation(Generic[P, C]):
             cookies=deepcopy(self.cookies),
             query=deepcopy(self.query),
             body=deepcopy(self.body),
+            security=deepcopy(self.security),
         )
 
     def clone(self, **components: Any) -> "APIOperation":
@@ -594,6 +727,7 @@ class APIOperation(Generic[P, C]):
             headers=components["headers"],
             cookies=components["cookies"],
             body=components["body"],
+            security=self.security,
         )
 
     def make_case(
@@ -41,6 +41,11 @@ class Parameter:
         """Parameter example."""
         raise NotImplementedError
 
+    @property
+    def allow_reserved(self) -> bool:
+        """Whether reserved URI characters should be sent without percent-encoding."""
+        return False
+
     def serialize(self) -> str:
         """Get parameter's string representation."""
         raise NotImplementedError
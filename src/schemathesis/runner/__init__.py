@@ -4,7 +4,8 @@ from urllib.parse import urlparse
 import hypothesis.errors
 from starlette.applications import Starlette
 
-from ..checks import DEFAULT_CHECKS
+from ..auth import OAuth2Auth, OAuth2Config
+from ..checks import DEFAULT_CHECKS, make_object_level_authorization_check
 from ..constants import (
     DEFAULT_DATA_GENERATION_METHODS,
     DEFAULT_DEADLINE,
@@ -15,9 +16,12 @@ from ..models import CheckFunction
 from ..schemas import BaseSchema
 from ..specs.graphql import loaders as gql_loaders
 from ..specs.openapi import loaders as oas_loaders
+from ..sessions import SessionConfig
+from ..runstate import RerunFailed
+from ..sharding import Shard
 from ..stateful import Stateful
 from ..targets import DEFAULT_TARGETS, Target
-from ..types import Filter, NotSet, RawAuth
+from ..types import AuthProfiles, Filter, NotSet, RawAuth, RequestCert
 from ..utils import deprecated, dict_not_none_values, dict_true_values, file_exists, get_requests_auth, import_app
 from . import events
 from .impl import (
@@ -41,6 +45,10 @@ def prepare(
     max_response_time: Optional[int] = None,
     targets: Iterable[Target] = DEFAULT_TARGETS,
     workers_num: int = 1,
+    max_duration: Optional[float] = None,
+    max_operation_duration: Optional[float] = None,
+    shard: Optional[Shard] = None,
+    rerun_failed: Optional[RerunFailed] = None,
     seed: Optional[int] = None,
     exit_first: bool = False,
     dry_run: bool = False,
@@ -53,8 +61,12 @@ def prepare(
     auth: Optional[Tuple[str, str]] = None,
     auth_type: Optional[str] = None,
     headers: Optional[Dict[str, str]] = None,
+    auth_profiles: Optional[AuthProfiles] = None,
+    oauth2: Optional[OAuth2Config] = None,
     request_timeout: Optional[int] = None,
     request_tls_verify: Union[bool, str] = True,
+    request_cert: Optional[RequestCert] = None,
+    session_config: Optional[SessionConfig] = None,
     endpoint: Optional[Filter] = None,
     method: Optional[Filter] = None,
     tag: Optional[Filter] = None,
@@ -114,12 +126,20 @@ def prepare(
         auth=auth,
         auth_type=auth_type,
         headers=headers,
+        auth_profiles=auth_profiles,
+        oauth2=oauth2,
         request_timeout=request_timeout,
         request_tls_verify=request_tls_verify,
+        request_cert=request_cert,
+        session_config=session_config,
         store_interactions=store_interactions,
         stateful=stateful,
         stateful_recursion_limit=stateful_recursion_limit,
         count_operations=count_operations,
+        max_duration=max_duration,
+        max_operation_duration=max_operation_duration,
+        shard=shard,
+        rerun_failed=rerun_failed,
     )
 
 
@@ -164,12 +184,20 @@ def execute_from_schema(
     max_response_time: Optional[int] = None,
     targets: Iterable[Target],
     workers_num: int = 1,
+    max_duration: Optional[float] = None,
+    max_operation_duration: Optional[float] = None,
+    shard: Optional[Shard] = None,
+    rerun_failed: Optional[RerunFailed] = None,
     hypothesis_settings: hypothesis.settings,
     auth: Optional[RawAuth] = None,
     auth_type: Optional[str] = None,
     headers: Optional[Dict[str, Any]] = None,
+    auth_profiles: Optional[AuthProfiles] = None,
+    oauth2: Optional[OAuth2Config] = None,
     request_timeout: Optional[int] = None,
     request_tls_verify: Union[bool, str] = True,
+    request_cert: Optional[RequestCert] = None,
+    session_config: Optional[SessionConfig] = None,
     seed: Optional[int] = None,
     exit_first: bool = False,
     dry_run: bool = False,
@@ -203,6 +231,8 @@ def execute_from_schema(
             data_generation_methods=data_generation_methods,
             force_schema_version=force_schema_version,
             request_tls_verify=request_tls_verify,
+            request_cert=request_cert,
+            session_config=session_config,
         )
         yield from from_schema(
             schema,
@@ -213,16 +243,24 @@ def execute_from_schema(
             auth=auth,
             auth_type=auth_type,
             headers=headers,
+            auth_profiles=auth_profiles,
+            oauth2=oauth2,
             seed=seed,
             workers_num=workers_num,
-            request_timeout=request_timeout,
+                request_timeout=request_timeout,
             request_tls_verify=request_tls_verify,
+            request_cert=request_cert,
+            session_config=session_config,
             exit_first=exit_first,
             dry_run=dry_run,
             store_interactions=store_interactions,
             stateful=stateful,
             stateful_recursion_limit=stateful_recursion_limit,
             count_operations=count_operations,
+            max_duration=max_duration,
+            max_operation_duration=max_operation_duration,
+            shard=shard,
+            rerun_failed=rerun_failed,
         ).execute()
     except Exception as exc:
         yield events.InternalError.from_exc(exc)
@@ -239,6 +277,8 @@ def load_schema(
     data_generation_methods: Tuple[DataGenerationMethod, ...] = DEFAULT_DATA_GENERATION_METHODS,
     force_schema_version: Optional[str] = None,
     request_tls_verify: Union[bool, str] = True,
+    request_cert: Optional[RequestCert] = None,
+    session_config: Optional[SessionConfig] = None,
     # Network request parameters
     auth: Optional[Tuple[str, str]] = None,
     auth_type: Optional[str] = None,
@@ -276,6 +316,10 @@ def load_schema(
         loader_options["auth"] = get_requests_auth(loader_options["auth"], loader_options.pop("auth_type", None))
     if loader in (oas_loaders.from_uri, oas_loaders.from_aiohttp):
         loader_options["verify"] = request_tls_verify
+        if request_cert is not None:
+            loader_options["cert"] = request_cert
+    if loader is oas_loaders.from_uri and session_config is not None:
+        loader_options["session_config"] = session_config
 
     return loader(
         schema_uri,
@@ -293,12 +337,20 @@ def from_schema(
     max_response_time: Optional[int] = None,
     targets: Iterable[Target] = DEFAULT_TARGETS,
     workers_num: int = 1,
+    max_duration: Optional[float] = None,
+    max_operation_duration: Optional[float] = None,
+    shard: Optional[Shard] = None,
+    rerun_failed: Optional[RerunFailed] = None,
     hypothesis_settings: Optional[hypothesis.settings] = None,
     auth: Optional[RawAuth] = None,
     auth_type: Optional[str] = None,
     headers: Optional[Dict[str, Any]] = None,
+    auth_profiles: Optional[AuthProfiles] = None,
+    oauth2: Optional[OAuth2Config] = None,
     request_timeout: Optional[int] = None,
     request_tls_verify: Union[bool, str] = True,
+    request_cert: Optional[RequestCert] = None,
+    session_config: Optional[SessionConfig] = None,
     seed: Optional[int] = None,
     exit_first: bool = False,
     dry_run: bool = False,
@@ -307,7 +359,23 @@ def from_schema(
     stateful_recursion_limit: int = DEFAULT_STATEFUL_RECURSION_LIMIT,
     count_operations: bool = True,
 ) -> BaseRunner:
-    hypothesis_settings = hypothesis_settings or hypothesis.settings(deadline=DEFAULT_DEADLINE)
+    if hypothesis_settings is None:
+        # Waiting for the rate limiter or before retries happens inside tests and could exceed the default deadline
+        has_waits = session_config is not None and session_config.has_waits
+        hypothesis_settings = hypothesis.settings(deadline=None if has_waits else DEFAULT_DEADLINE)
+    if auth_profiles:
+        # Requests are made as the first identity, the other ones are used only to verify access to its resources
+        headers = {**(headers or {}), **next(iter(auth_profiles.values()))}
+        if len(auth_profiles) > 1:
+            checks = (*checks, make_object_level_authorization_check(auth_profiles))
+    oauth2_auth = None
+    if oauth2 is not None:
+        if schema.app is not None:
+            raise ValueError("OAuth 2 is not supported for WSGI / ASGI apps")
+        oauth2_auth = OAuth2Auth.from_schema(schema, oauth2, request_tls_verify, request_cert, session_config)
+        if not dry_run:
+            # Obtain the token before the run to fail early if the credentials are not valid
+            oauth2_auth.get_token()
     if workers_num > 1:
         if not schema.app:
             return ThreadPoolRunner(
@@ -318,17 +386,24 @@ def from_schema(
                 hypothesis_settings=hypothesis_settings,
                 auth=auth,
                 auth_type=auth_type,
+                oauth2=oauth2_auth,
                 headers=headers,
                 seed=seed,
                 workers_num=workers_num,
                 request_timeout=request_timeout,
                 request_tls_verify=request_tls_verify,
+                request_cert=request_cert,
+                session_config=session_config,
                 exit_first=exit_first,
                 dry_run=dry_run,
                 store_interactions=store_interactions,
                 stateful=stateful,
                 stateful_recursion_limit=stateful_recursion_limit,
                 count_operations=count_operations,
+                max_duration=max_duration,
+                max_operation_duration=max_operation_duration,
+                shard=shard,
+                rerun_failed=rerun_failed,
             )
         if isinstance(schema.app, Starlette):
             return ThreadPoolASGIRunner(
@@ -347,6 +422,10 @@ def from_schema(
                 stateful=stateful,
                 stateful_recursion_limit=stateful_recursion_limit,
                 count_operations=count_operations,
+                max_duration=max_duration,
+                max_operation_duration=max_operation_duration,
+                shard=shard,
+                rerun_failed=rerun_failed,
             )
         return ThreadPoolWSGIRunner(
             schema=schema,
@@ -365,6 +444,10 @@ def from_schema(
             stateful=stateful,
             stateful_recursion_limit=stateful_recursion_limit,
             count_operations=count_operations,
+            max_duration=max_duration,
+            max_operation_duration=max_operation_duration,
+            shard=shard,
+            rerun_failed=rerun_failed,
         )
     if not schema.app:
         return SingleThreadRunner(
@@ -375,16 +458,23 @@ def from_schema(
             hypothesis_settings=hypothesis_settings,
             auth=auth,
             auth_type=auth_type,
+            oauth2=oauth2_auth,
             headers=headers,
             seed=seed,
             request_timeout=request_timeout,
             request_tls_verify=request_tls_verify,
+            request_cert=request_cert,
+            session_config=session_config,
             exit_first=exit_first,
             dry_run=dry_run,
             store_interactions=store_interactions,
             stateful=stateful,
             stateful_recursion_limit=stateful_recursion_limit,
             count_operations=count_operations,
+            max_duration=max_duration,
+            max_operation_duration=max_operation_duration,
+            shard=shard,
+            rerun_failed=rerun_failed,
         )
     if isinstance(schema.app, Starlette):
         return SingleThreadASGIRunner(
@@ -403,6 +493,10 @@ def from_schema(
             stateful=stateful,
             stateful_recursion_limit=stateful_recursion_limit,
             count_operations=count_operations,
+            max_duration=max_duration,
+            max_operation_duration=max_operation_duration,
+            shard=shard,
+            rerun_failed=rerun_failed,
         )
     return SingleThreadWSGIRunner(
         schema=schema,
@@ -420,6 +514,10 @@ def from_schema(
         stateful=stateful,
         stateful_recursion_limit=stateful_recursion_limit,
         count_operations=count_operations,
+        max_duration=max_duration,
+        max_operation_duration=max_operation_duration,
+        shard=shard,
+        rerun_failed=rerun_failed,
     )
 
 
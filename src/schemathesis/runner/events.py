@@ -1,14 +1,14 @@
 import threading
 import time
-from typing import Dict, List, Optional, Union
+from typing import Dict, Iterable, List, Optional, Union
 
 import attr
 from requests import exceptions
 
-from ..exceptions import HTTPError
+from ..exceptions import HTTPError, InvalidSchema
 from ..models import APIOperation, Status, TestResult, TestResultSet
-from ..schemas import BaseSchema
-from ..utils import format_exception
+from ..schemas import BaseSchema, get_operations_count
+from ..utils import Result, format_exception
 from .serialization import SerializedError, SerializedTestResult
 
 
@@ -31,18 +31,34 @@ class Initialized(ExecutionEvent):
     base_url: str = attr.ib()  # pragma: no mutate
     # API schema specification name
     specification_name: str = attr.ib()  # pragma: no mutate
+    # The part of API operations that is tested in this run, e.g. "2/4"
+    shard: Optional[str] = attr.ib(default=None)  # pragma: no mutate
     # Timestamp of test run start
     start_time: float = attr.ib(factory=time.monotonic)  # pragma: no mutate
     thread_id: int = attr.ib(factory=threading.get_ident)  # pragma: no mutate
 
     @classmethod
-    def from_schema(cls, *, schema: BaseSchema, count_operations: bool = True) -> "Initialized":
-        """Computes all needed data from a schema instance."""
+    def from_schema(
+        cls,
+        *,
+        schema: BaseSchema,
+        count_operations: bool = True,
+        operations: Optional[Iterable[Result[APIOperation, InvalidSchema]]] = None,
+        shard: Optional[str] = None,
+    ) -> "Initialized":
+        """Computes all needed data from a schema instance.
+
+        If ``operations`` are passed, then only they are counted instead of all operations in the schema.
+        """
+        operations_count = None
+        if count_operations:
+            operations_count = schema.operations_count if operations is None else get_operations_count(operations)
         return cls(
-            operations_count=schema.operations_count if count_operations else None,
+            operations_count=operations_count,
             location=schema.location,
             base_url=schema.get_base_url(),
             specification_name=schema.verbose_name,
+            shard=shard,
         )
 
 
@@ -133,6 +149,15 @@ class Interrupted(ExecutionEvent):
     thread_id: int = attr.ib(factory=threading.get_ident)  # pragma: no mutate
 
 
+@attr.s(slots=True)  # pragma: no mutate
+class MaxDurationReached(ExecutionEvent):
+    """The time budget for the whole run ran out and the remaining API operations were not tested."""
+
+    # The time budget in seconds
+    max_duration: float = attr.ib()  # pragma: no mutate
+    thread_id: int = attr.ib(factory=threading.get_ident)  # pragma: no mutate
+
+
 @attr.s(slots=True)  # pragma: no mutate
 class InternalError(ExecutionEvent):
     """An error that happened inside the runner."""
@@ -4,7 +4,7 @@ import time
 import uuid
 from contextlib import contextmanager
 from types import TracebackType
-from typing import Any, Callable, Dict, Generator, Iterable, List, Optional, Type, Union, cast
+from typing import Any, Callable, Dict, Generator, Iterable, List, Optional, Tuple, Type, Union, cast
 from warnings import WarningMessage, catch_warnings
 
 import attr
@@ -13,8 +13,9 @@ import requests
 from _pytest.logging import LogCaptureHandler, catching_logs
 from hypothesis.errors import HypothesisException, InvalidArgument
 from hypothesis_jsonschema._canonicalise import HypothesisRefResolutionError
-from requests.auth import HTTPDigestAuth, _basic_auth_str
+from requests.auth import AuthBase, _basic_auth_str
 
+from ...auth import OAuth2Auth
 from ...constants import (
     DEFAULT_STATEFUL_RECURSION_LIMIT,
     RECURSIVE_REFERENCE_ERROR_MESSAGE,
@@ -29,14 +30,19 @@ from ...exceptions import (
     NonCheckError,
     get_grouped_exception,
 )
+from ..._hypothesis import create_test
 from ...hooks import HookContext, get_all_by_name
-from ...models import APIOperation, Case, Check, CheckFunction, Status, TestResult, TestResultSet
+from ...models import APIOperation, Case, CaseSource, Check, CheckFunction, Status, TestResult, TestResultSet
 from ...runner import events
+from ...runstate import RerunFailed
 from ...schemas import BaseSchema
-from ...stateful import Feedback, Stateful
+from ...sessions import SessionConfig, create_session
+from ...sharding import Shard
+from ...stateful import DeletedResources, Feedback, Stateful
 from ...targets import Target, TargetContext
-from ...types import RawAuth
-from ...utils import GenericResponse, Ok, WSGIResponse, capture_hypothesis_output, format_exception
+from ...throttling import RETRY_STATUS_CODES
+from ...types import RawAuth, RequestCert
+from ...utils import GenericResponse, Ok, Result, WSGIResponse, capture_hypothesis_output, format_exception
 from ..serialization import SerializedTestResult
 
 
@@ -49,6 +55,7 @@ class BaseRunner:
     hypothesis_settings: hypothesis.settings = attr.ib()  # pragma: no mutate
     auth: Optional[RawAuth] = attr.ib(default=None)  # pragma: no mutate
     auth_type: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+    oauth2: Optional[OAuth2Auth] = attr.ib(default=None)  # pragma: no mutate
     headers: Optional[Dict[str, Any]] = attr.ib(default=None)  # pragma: no mutate
     request_timeout: Optional[int] = attr.ib(default=None)  # pragma: no mutate
     store_interactions: bool = attr.ib(default=False)  # pragma: no mutate
@@ -58,15 +65,31 @@ class BaseRunner:
     stateful: Optional[Stateful] = attr.ib(default=None)  # pragma: no mutate
     stateful_recursion_limit: int = attr.ib(default=DEFAULT_STATEFUL_RECURSION_LIMIT)  # pragma: no mutate
     count_operations: bool = attr.ib(default=True)  # pragma: no mutate
+    max_duration: Optional[float] = attr.ib(default=None)  # pragma: no mutate
+    max_operation_duration: Optional[float] = attr.ib(default=None)  # pragma: no mutate
+    shard: Optional[Shard] = attr.ib(default=None)  # pragma: no mutate
+    rerun_failed: Optional[RerunFailed] = attr.ib(default=None)  # pragma: no mutate
 
     def execute(self) -> Generator[events.ExecutionEvent, None, None]:
         """Common logic for all runners."""
         results = TestResultSet()
-
-        initialized = events.Initialized.from_schema(schema=self.schema, count_operations=self.count_operations)
+        # Operations are collected only once, since they are both counted and tested
+        operations = list(self._get_operations())
+
+        initialized = events.Initialized.from_schema(
+            schema=self.schema,
+            count_operations=self.count_operations,
+            operations=operations,
+            shard=str(self.shard) if self.shard is not None else None,
+        )
         yield initialized
 
-        for event in self._execute(results):
+        time_budget = TimeBudget(
+            max_duration=self.max_duration,
+            max_operation_duration=self.max_operation_duration,
+            started_at=initialized.start_time,
+        )
+        for event in self._execute(operations, results, time_budget):
             yield event
             if (
                 self.exit_first
@@ -75,11 +98,39 @@ class BaseRunner:
             ):
                 break
 
+        if time_budget.is_exhausted:
+            yield events.MaxDurationReached(max_duration=cast(float, self.max_duration))
         yield events.Finished.from_results(results=results, running_time=time.monotonic() - initialized.start_time)
 
-    def _execute(self, results: TestResultSet) -> Generator[events.ExecutionEvent, None, None]:
+    def _execute(
+        self, operations: List[Result[APIOperation, InvalidSchema]], results: TestResultSet, time_budget: "TimeBudget"
+    ) -> Generator[events.ExecutionEvent, None, None]:
         raise NotImplementedError
 
+    def _get_operations(self) -> Iterable[Result[APIOperation, InvalidSchema]]:
+        """API operations to test in this run."""
+        operations = self.schema.get_all_operations()
+        if self.shard is not None:
+            operations = self.shard.select(operations)
+        if self.rerun_failed is not None:
+            operations = self.rerun_failed.select(operations)
+        return operations
+
+    def _get_all_tests(
+        self,
+        operations: List[Result[APIOperation, InvalidSchema]],
+        func: Callable,
+        settings: hypothesis.settings,
+        seed: Optional[int],
+    ) -> Generator[Tuple[Result[Tuple[APIOperation, Callable], InvalidSchema], DataGenerationMethod], None, None]:
+        """Tests for API operations selected for this run, that also replay their failures from a previous run."""
+        for result, data_generation_method in self.schema.get_all_tests(func, settings, seed, operations=operations):
+            if isinstance(result, Ok) and self.rerun_failed is not None:
+                operation, test = result.ok()
+                test = self.rerun_failed.apply(test, operation, data_generation_method, seed)
+                result = Ok((operation, test))
+            yield result, data_generation_method
+
     def _run_tests(
         self,
         maker: Callable,
@@ -88,15 +139,19 @@ class BaseRunner:
         seed: Optional[int],
         results: TestResultSet,
         recursion_level: int = 0,
+        deleted_resources: Optional[DeletedResources] = None,
+        time_budget: Optional["TimeBudget"] = None,
         **kwargs: Any,
     ) -> Generator[events.ExecutionEvent, None, None]:
         """Run tests and recursively run additional tests."""
         if recursion_level > self.stateful_recursion_limit:
             return
         for result, data_generation_method in maker(template, settings, seed):
+            if time_budget is not None and time_budget.is_over():
+                return
             if isinstance(result, Ok):
                 operation, test = result.ok()
-                feedback = Feedback(self.stateful, operation)
+                feedback = Feedback(self.stateful, operation, deleted_resources=deleted_resources)
                 for event in run_test(
                     operation,
                     test,
@@ -104,6 +159,7 @@ class BaseRunner:
                     feedback=feedback,
                     recursion_level=recursion_level,
                     data_generation_method=data_generation_method,
+                    time_budget=time_budget,
                     **kwargs,
                 ):
                     yield event
@@ -116,7 +172,9 @@ class BaseRunner:
                     settings,
                     seed,
                     recursion_level=recursion_level + 1,
+                    deleted_resources=get_deleted_resources(feedback),
                     results=results,
+                    time_budget=time_budget,
                     **kwargs,
                 )
             else:
@@ -124,6 +182,77 @@ class BaseRunner:
                 yield from handle_schema_error(result.err(), results, data_generation_method, recursion_level)
 
 
+def create_operation_test(
+    *,
+    operation: APIOperation,
+    test: Callable,
+    settings: hypothesis.settings,
+    seed: Optional[int],
+    data_generation_method: DataGenerationMethod,
+    rerun_failed: Optional[RerunFailed] = None,
+) -> Callable:
+    """Create a Hypothesis test for an API operation, that also replays its failures from a previous run."""
+    test_function = create_test(
+        operation=operation,
+        test=test,
+        settings=settings,
+        seed=seed,
+        data_generation_method=data_generation_method,
+    )
+    if rerun_failed is not None:
+        test_function = rerun_failed.apply(test_function, operation, data_generation_method, seed)
+    return test_function
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class TimeBudget:
+    """Time limits for the whole run and for testing a single API operation, in seconds.
+
+    When a limit is reached, no new test cases are generated, but the ones that are already running are finished.
+    The same instance is shared between threads.
+    """
+
+    max_duration: Optional[float] = attr.ib(default=None)  # pragma: no mutate
+    max_operation_duration: Optional[float] = attr.ib(default=None)  # pragma: no mutate
+    started_at: float = attr.ib(factory=time.monotonic)  # pragma: no mutate
+    # Whether the run was stopped because `max_duration` is reached
+    is_exhausted: bool = attr.ib(default=False)  # pragma: no mutate
+
+    def is_over(self) -> bool:
+        """Whether there is no time left for the whole run."""
+        if self.max_duration is not None and time.monotonic() - self.started_at >= self.max_duration:
+            self.is_exhausted = True
+        return self.is_exhausted
+
+    def get_stop_time(self) -> Optional[float]:
+        """The moment when testing of an API operation that starts now should be stopped."""
+        stop_times = []
+        if self.max_duration is not None:
+            stop_times.append(self.started_at + self.max_duration)
+        if self.max_operation_duration is not None:
+            stop_times.append(time.monotonic() + self.max_operation_duration)
+        return min(stop_times, default=None)
+
+
+class TimeBudgetExceeded(BaseException):
+    """Stops generating test cases for an API operation when its time is up.
+
+    It is not a subclass of `Exception`, so it is not caught by Hypothesis or by the error handling in test functions.
+    """
+
+
+def check_time_budget(stop_at: Optional[float]) -> None:
+    if stop_at is not None and time.monotonic() >= stop_at:
+        raise TimeBudgetExceeded
+
+
+def get_deleted_resources(feedback: Feedback) -> DeletedResources:
+    """Resources deleted in additional tests are tracked separately for each chain of links."""
+    if feedback.deleted_resources is None:
+        return DeletedResources()
+    return feedback.deleted_resources
+
+
 def handle_schema_error(
     error: InvalidSchema, results: TestResultSet, data_generation_method: DataGenerationMethod, recursion_level: int
 ) -> Generator[events.ExecutionEvent, None, None]:
@@ -171,6 +300,7 @@ def run_test(  # pylint: disable=too-many-locals
     results: TestResultSet,
     headers: Optional[Dict[str, Any]],
     recursion_level: int,
+    time_budget: Optional[TimeBudget] = None,
     **kwargs: Any,
 ) -> Generator[events.ExecutionEvent, None, None]:
     """A single test run with all error handling needed."""
@@ -188,11 +318,26 @@ def run_test(  # pylint: disable=too-many-locals
     hypothesis_output: List[str] = []
     errors: List[Exception] = []
     test_start_time = time.monotonic()
+    stop_at = time_budget.get_stop_time() if time_budget is not None else None
     setup_hypothesis_database_key(test, operation)
     try:
         with catch_warnings(record=True) as warnings, capture_hypothesis_output() as hypothesis_output:
-            test(checks, targets, result, errors=errors, headers=headers, **kwargs)
+            test(checks, targets, result, errors=errors, headers=headers, stop_at=stop_at, **kwargs)
         status = Status.success
+    except TimeBudgetExceeded:
+        # Test cases that were executed before the time ran out still determine the outcome
+        if time_budget is not None:
+            # Remember if the whole run is out of time, even if it is the last API operation
+            time_budget.is_over()
+        if errors:
+            status = Status.error
+            result.mark_errored()
+            for error in deduplicate_errors(errors):
+                result.add_error(error)
+        elif result.has_failures:
+            status = Status.failure
+        else:
+            status = Status.success
     except CheckFailed:
         status = Status.failure
     except NonCheckError:
@@ -360,12 +505,27 @@ def run_targets(targets: Iterable[Callable], context: TargetContext) -> None:
         hypothesis.target(value, label=target.__name__)
 
 
+def run_checks_before_call(
+    case: Case,
+    checks: Iterable[CheckFunction],
+    headers: Optional[Dict[str, Any]],
+    send: Callable[[Dict[str, Any]], GenericResponse],
+) -> None:
+    """Some checks send requests before the tested one, e.g. because the resource doesn't exist after the call."""
+    for check in checks:
+        before_call = getattr(check, "before_call", None)
+        if before_call is not None:
+            before_call(case, headers or {}, send)
+
+
 def add_cases(case: Case, response: GenericResponse, test: Callable, *args: Any) -> None:
     context = HookContext(case.operation)
     for case_hook in get_all_by_name("add_case"):
         _case = case_hook(context, case.partial_deepcopy(), response)
         # run additional test if _case is not an empty value
         if _case:
+            if _case.source is None:
+                _case.source = CaseSource(case=case, response=response)
             test(_case, *args)
 
 
@@ -415,14 +575,17 @@ def network_test(
     session: requests.Session,
     request_timeout: Optional[int],
     request_tls_verify: bool,
+    request_cert: Optional[RequestCert],
     store_interactions: bool,
     headers: Optional[Dict[str, Any]],
     feedback: Feedback,
     max_response_time: Optional[int],
     dry_run: bool,
     errors: List[Exception],
+    stop_at: Optional[float] = None,
 ) -> None:
     """A single test body will be executed against the target."""
+    check_time_budget(stop_at)
     with ErrorCollector(errors):
         headers = headers or {}
         if "user-agent" not in {header.lower() for header in headers}:
@@ -440,6 +603,7 @@ def network_test(
                 headers,
                 feedback,
                 request_tls_verify,
+                request_cert,
                 max_response_time,
             )
             add_cases(
@@ -455,6 +619,7 @@ def network_test(
                 headers,
                 feedback,
                 request_tls_verify,
+                request_cert,
                 max_response_time,
             )
 
@@ -470,11 +635,20 @@ def _network_test(
     headers: Optional[Dict[str, Any]],
     feedback: Feedback,
     request_tls_verify: bool,
+    request_cert: Optional[RequestCert],
     max_response_time: Optional[int],
 ) -> requests.Response:
-    response = case.call(session=session, headers=headers, timeout=timeout, verify=request_tls_verify)
+    def send(request_headers: Optional[Dict[str, Any]]) -> requests.Response:
+        return case.call(
+            session=session, headers=request_headers, timeout=timeout, verify=request_tls_verify, cert=request_cert
+        )
+
+    feedback.set_source(case)
+    run_checks_before_call(case, checks, headers, send)
+    response = send(headers)
     context = TargetContext(case=case, response=response, response_time=response.elapsed.total_seconds())
     run_targets(targets, context)
+    feedback.set_deleted_by(case)
     status = Status.success
     check_results: List[Check] = []
     try:
@@ -484,16 +658,26 @@ def _network_test(
         raise
     finally:
         if store_interactions:
+            for previous in response.history:
+                if previous.status_code in RETRY_STATUS_CODES:
+                    # Checks are not run for retried responses, but they are stored to show what happened
+                    result.store_requests_response(previous, Status.success, [])
             result.store_requests_response(response, status, check_results)
     feedback.add_test_case(case, response)
     return response
 
 
 @contextmanager
-def get_session(auth: Optional[Union[HTTPDigestAuth, RawAuth]] = None) -> Generator[requests.Session, None, None]:
-    with requests.Session() as session:
+def get_session(
+    auth: Optional[Union[AuthBase, RawAuth]] = None,
+    cert: Optional[RequestCert] = None,
+    config: Optional[SessionConfig] = None,
+) -> Generator[requests.Session, None, None]:
+    with create_session(config) as session:
         if auth is not None:
             session.auth = auth
+        if cert is not None:
+            session.cert = cert
         yield session
 
 
@@ -518,7 +702,9 @@ def wsgi_test(
     max_response_time: Optional[int],
     dry_run: bool,
     errors: List[Exception],
+    stop_at: Optional[float] = None,
 ) -> None:
+    check_time_budget(stop_at)
     with ErrorCollector(errors):
         headers = _prepare_wsgi_headers(headers, auth, auth_type)
         if not dry_run:
@@ -549,6 +735,8 @@ def _wsgi_test(
     feedback: Feedback,
     max_response_time: Optional[int],
 ) -> WSGIResponse:
+    feedback.set_source(case)
+    run_checks_before_call(case, checks, headers, lambda request_headers: case.call_wsgi(headers=request_headers))
     with catching_logs(LogCaptureHandler(), level=logging.DEBUG) as recorded:
         start = time.monotonic()
         response = case.call_wsgi(headers=headers)
@@ -556,6 +744,7 @@ def _wsgi_test(
     context = TargetContext(case=case, response=response, response_time=elapsed)
     run_targets(targets, context)
     result.logs.extend(recorded.records)
+    feedback.set_deleted_by(case)
     status = Status.success
     check_results: List[Check] = []
     try:
@@ -601,8 +790,10 @@ def asgi_test(
     max_response_time: Optional[int],
     dry_run: bool,
     errors: List[Exception],
+    stop_at: Optional[float] = None,
 ) -> None:
     """A single test body will be executed against the target."""
+    check_time_budget(stop_at)
     with ErrorCollector(errors):
         headers = headers or {}
 
@@ -634,9 +825,12 @@ def _asgi_test(
     feedback: Feedback,
     max_response_time: Optional[int],
 ) -> requests.Response:
+    feedback.set_source(case)
+    run_checks_before_call(case, checks, headers, lambda request_headers: case.call_asgi(headers=request_headers))
     response = case.call_asgi(headers=headers)
     context = TargetContext(case=case, response=response, response_time=response.elapsed.total_seconds())
     run_targets(targets, context)
+    feedback.set_deleted_by(case)
     status = Status.success
     check_results: List[Check] = []
     try:
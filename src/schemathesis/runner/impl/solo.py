@@ -1,12 +1,16 @@
 # weird mypy bug with imports
-from typing import Any, Dict, Generator, Union  # pylint: disable=unused-import
+from functools import partial
+from typing import Any, Dict, Generator, List, Optional, Union  # pylint: disable=unused-import
 
 import attr
 
-from ...models import TestResultSet
-from ...utils import get_requests_auth
+from ...exceptions import InvalidSchema
+from ...models import APIOperation, TestResultSet
+from ...sessions import SessionConfig
+from ...types import RequestCert
+from ...utils import Result, get_requests_auth
 from .. import events
-from .core import BaseRunner, asgi_test, get_session, network_test, wsgi_test
+from .core import BaseRunner, TimeBudget, asgi_test, get_session, network_test, wsgi_test
 
 
 @attr.s(slots=True)  # pragma: no mutate
@@ -14,12 +18,16 @@ class SingleThreadRunner(BaseRunner):
     """Fast runner that runs tests sequentially in the main thread."""
 
     request_tls_verify: Union[bool, str] = attr.ib(default=True)  # pragma: no mutate
+    request_cert: Optional[RequestCert] = attr.ib(default=None)  # pragma: no mutate
+    session_config: Optional[SessionConfig] = attr.ib(default=None)  # pragma: no mutate
 
-    def _execute(self, results: TestResultSet) -> Generator[events.ExecutionEvent, None, None]:
-        auth = get_requests_auth(self.auth, self.auth_type)
-        with get_session(auth) as session:
+    def _execute(
+        self, operations: List[Result[APIOperation, InvalidSchema]], results: TestResultSet, time_budget: TimeBudget
+    ) -> Generator[events.ExecutionEvent, None, None]:
+        auth = self.oauth2 or get_requests_auth(self.auth, self.auth_type)
+        with get_session(auth, self.request_cert, self.session_config) as session:
             yield from self._run_tests(
-                self.schema.get_all_tests,
+                partial(self._get_all_tests, operations),
                 network_test,
                 self.hypothesis_settings,
                 self.seed,
@@ -27,10 +35,12 @@ class SingleThreadRunner(BaseRunner):
                 max_response_time=self.max_response_time,
                 targets=self.targets,
                 results=results,
+                time_budget=time_budget,
                 session=session,
                 headers=self.headers,
                 request_timeout=self.request_timeout,
                 request_tls_verify=self.request_tls_verify,
+                request_cert=self.request_cert,
                 store_interactions=self.store_interactions,
                 dry_run=self.dry_run,
             )
@@ -38,9 +48,11 @@ class SingleThreadRunner(BaseRunner):
 
 @attr.s(slots=True)  # pragma: no mutate
 class SingleThreadWSGIRunner(SingleThreadRunner):
-    def _execute(self, results: TestResultSet) -> Generator[events.ExecutionEvent, None, None]:
+    def _execute(
+        self, operations: List[Result[APIOperation, InvalidSchema]], results: TestResultSet, time_budget: TimeBudget
+    ) -> Generator[events.ExecutionEvent, None, None]:
         yield from self._run_tests(
-            self.schema.get_all_tests,
+            partial(self._get_all_tests, operations),
             wsgi_test,
             self.hypothesis_settings,
             self.seed,
@@ -48,6 +60,7 @@ class SingleThreadWSGIRunner(SingleThreadRunner):
             max_response_time=self.max_response_time,
             targets=self.targets,
             results=results,
+            time_budget=time_budget,
             auth=self.auth,
             auth_type=self.auth_type,
             headers=self.headers,
@@ -58,9 +71,11 @@ class SingleThreadWSGIRunner(SingleThreadRunner):
 
 @attr.s(slots=True)  # pragma: no mutate
 class SingleThreadASGIRunner(SingleThreadRunner):
-    def _execute(self, results: TestResultSet) -> Generator[events.ExecutionEvent, None, None]:
+    def _execute(
+        self, operations: List[Result[APIOperation, InvalidSchema]], results: TestResultSet, time_budget: TimeBudget
+    ) -> Generator[events.ExecutionEvent, None, None]:
         yield from self._run_tests(
-            self.schema.get_all_tests,
+            partial(self._get_all_tests, operations),
             asgi_test,
             self.hypothesis_settings,
             self.seed,
@@ -68,6 +83,7 @@ class SingleThreadASGIRunner(SingleThreadRunner):
             max_response_time=self.max_response_time,
             targets=self.targets,
             results=results,
+            time_budget=time_budget,
             headers=self.headers,
             store_interactions=self.store_interactions,
             dry_run=self.dry_run,
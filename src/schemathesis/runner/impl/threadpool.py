@@ -1,20 +1,34 @@
 import ctypes
 import threading
 import time
-from queue import Queue
+from queue import Empty, Queue
 from typing import Any, Callable, Dict, Generator, Iterable, List, Optional, Union, cast
 
 import attr
 import hypothesis
 
-from ..._hypothesis import create_test
-from ...models import CheckFunction, TestResultSet
-from ...stateful import Feedback, Stateful
+from ...auth import OAuth2Auth
+from ...exceptions import InvalidSchema
+from ...models import APIOperation, CheckFunction, TestResultSet
+from ...runstate import RerunFailed
+from ...sessions import SessionConfig
+from ...stateful import DeletedResources, Feedback, Stateful
 from ...targets import Target
-from ...types import RawAuth
-from ...utils import Ok, capture_hypothesis_output, get_requests_auth
+from ...types import RawAuth, RequestCert
+from ...utils import Ok, Result, capture_hypothesis_output, get_requests_auth
 from .. import events
-from .core import BaseRunner, asgi_test, get_session, handle_schema_error, network_test, run_test, wsgi_test
+from .core import (
+    BaseRunner,
+    TimeBudget,
+    asgi_test,
+    create_operation_test,
+    get_deleted_resources,
+    get_session,
+    handle_schema_error,
+    network_test,
+    run_test,
+    wsgi_test,
+)
 
 
 def _run_task(
@@ -28,15 +42,21 @@ def _run_task(
     results: TestResultSet,
     stateful: Optional[Stateful],
     stateful_recursion_limit: int,
+    time_budget: Optional[TimeBudget] = None,
+    rerun_failed: Optional[RerunFailed] = None,
     **kwargs: Any,
 ) -> None:
-    def _run_tests(maker: Callable, recursion_level: int = 0) -> None:
+    def _run_tests(
+        maker: Callable, recursion_level: int = 0, deleted_resources: Optional[DeletedResources] = None
+    ) -> None:
         if recursion_level > stateful_recursion_limit:
             return
         for _result, _data_generation_method in maker(test_template, settings, seed):
+            if time_budget is not None and time_budget.is_over():
+                return
             # `result` is always `Ok` here
             _operation, test = _result.ok()
-            feedback = Feedback(stateful, _operation)
+            feedback = Feedback(stateful, _operation, deleted_resources=deleted_resources)
             for _event in run_test(
                 _operation,
                 test,
@@ -46,22 +66,32 @@ def _run_task(
                 results,
                 recursion_level=recursion_level,
                 feedback=feedback,
+                time_budget=time_budget,
                 **kwargs,
             ):
                 events_queue.put(_event)
-            _run_tests(feedback.get_stateful_tests, recursion_level + 1)
+            _run_tests(feedback.get_stateful_tests, recursion_level + 1, get_deleted_resources(feedback))
 
     with capture_hypothesis_output():
-        while not tasks_queue.empty():
-            result, data_generation_method = tasks_queue.get()
+        while True:
+            if time_budget is not None and time_budget.is_over():
+                # In-flight tests in other workers are finished, but no new API operations are taken
+                break
+            try:
+                result, data_generation_method = tasks_queue.get_nowait()
+            except Empty:
+                # Another worker could take the last task after the queue was checked, therefore it is not checked
+                # before getting a task
+                break
             if isinstance(result, Ok):
                 operation = result.ok()
-                test_function = create_test(
+                test_function = create_operation_test(
                     operation=operation,
                     test=test_template,
                     settings=settings,
                     seed=seed,
                     data_generation_method=data_generation_method,
+                    rerun_failed=rerun_failed,
                 )
                 items = (
                     Ok((operation, test_function)),
@@ -83,6 +113,9 @@ def thread_task(
     settings: hypothesis.settings,
     auth: Optional[RawAuth],
     auth_type: Optional[str],
+    oauth2: Optional[OAuth2Auth],
+    request_cert: Optional[RequestCert],
+    session_config: Optional[SessionConfig],
     headers: Optional[Dict[str, Any]],
     seed: Optional[int],
     results: TestResultSet,
@@ -94,8 +127,8 @@ def thread_task(
 
     Pretty similar to the default one-thread flow, but includes communication with the main thread via the events queue.
     """
-    prepared_auth = get_requests_auth(auth, auth_type)
-    with get_session(prepared_auth) as session:
+    prepared_auth = oauth2 or get_requests_auth(auth, auth_type)
+    with get_session(prepared_auth, request_cert, session_config) as session:
         _run_task(
             network_test,
             tasks_queue,
@@ -109,6 +142,7 @@ def thread_task(
             stateful_recursion_limit=stateful_recursion_limit,
             session=session,
             headers=headers,
+            request_cert=request_cert,
             **kwargs,
         )
 
@@ -180,13 +214,17 @@ class ThreadPoolRunner(BaseRunner):
 
     workers_num: int = attr.ib(default=2)  # pragma: no mutate
     request_tls_verify: Union[bool, str] = attr.ib(default=True)  # pragma: no mutate
+    request_cert: Optional[RequestCert] = attr.ib(default=None)  # pragma: no mutate
+    session_config: Optional[SessionConfig] = attr.ib(default=None)  # pragma: no mutate
 
-    def _execute(self, results: TestResultSet) -> Generator[events.ExecutionEvent, None, None]:
+    def _execute(
+        self, operations: List[Result[APIOperation, InvalidSchema]], results: TestResultSet, time_budget: TimeBudget
+    ) -> Generator[events.ExecutionEvent, None, None]:
         """All events come from a queue where different workers push their events."""
-        tasks_queue = self._get_tasks_queue()
+        tasks_queue = self._get_tasks_queue(operations)
         # Events are pushed by workers via a separate queue
         events_queue: Queue = Queue()
-        workers = self._init_workers(tasks_queue, events_queue, results)
+        workers = self._init_workers(tasks_queue, events_queue, results, time_budget)
 
         def stop_workers() -> None:
             for worker in workers:
@@ -217,24 +255,26 @@ class ThreadPoolRunner(BaseRunner):
             stop_workers()
             yield events.Interrupted()
 
-    def _get_tasks_queue(self) -> Queue:
+    def _get_tasks_queue(self, operations: List[Result[APIOperation, InvalidSchema]]) -> Queue:
         """All API operations are distributed among all workers via a queue."""
         tasks_queue: Queue = Queue()
         tasks_queue.queue.extend(
             [
                 (operation, data_generation_method)
-                for operation in self.schema.get_all_operations()
+                for operation in operations
                 for data_generation_method in self.schema.data_generation_methods
             ]
         )
         return tasks_queue
 
-    def _init_workers(self, tasks_queue: Queue, events_queue: Queue, results: TestResultSet) -> List[threading.Thread]:
+    def _init_workers(
+        self, tasks_queue: Queue, events_queue: Queue, results: TestResultSet, time_budget: TimeBudget
+    ) -> List[threading.Thread]:
         """Initialize & start workers that will execute tests."""
         workers = [
             threading.Thread(
                 target=self._get_task(),
-                kwargs=self._get_worker_kwargs(tasks_queue, events_queue, results),
+                kwargs=self._get_worker_kwargs(tasks_queue, events_queue, results, time_budget),
                 name=f"schemathesis_{num}",
             )
             for num in range(self.workers_num)
@@ -246,7 +286,9 @@ class ThreadPoolRunner(BaseRunner):
     def _get_task(self) -> Callable:
         return thread_task
 
-    def _get_worker_kwargs(self, tasks_queue: Queue, events_queue: Queue, results: TestResultSet) -> Dict[str, Any]:
+    def _get_worker_kwargs(
+        self, tasks_queue: Queue, events_queue: Queue, results: TestResultSet, time_budget: TimeBudget
+    ) -> Dict[str, Any]:
         return {
             "tasks_queue": tasks_queue,
             "events_queue": events_queue,
@@ -255,6 +297,9 @@ class ThreadPoolRunner(BaseRunner):
             "settings": self.hypothesis_settings,
             "auth": self.auth,
             "auth_type": self.auth_type,
+            "oauth2": self.oauth2,
+            "request_cert": self.request_cert,
+            "session_config": self.session_config,
             "headers": self.headers,
             "seed": self.seed,
             "results": results,
@@ -266,6 +311,8 @@ class ThreadPoolRunner(BaseRunner):
                 "store_interactions": self.store_interactions,
                 "max_response_time": self.max_response_time,
                 "dry_run": self.dry_run,
+                "time_budget": time_budget,
+                "rerun_failed": self.rerun_failed,
             },
         }
 
@@ -274,7 +321,9 @@ class ThreadPoolWSGIRunner(ThreadPoolRunner):
     def _get_task(self) -> Callable:
         return wsgi_thread_task
 
-    def _get_worker_kwargs(self, tasks_queue: Queue, events_queue: Queue, results: TestResultSet) -> Dict[str, Any]:
+    def _get_worker_kwargs(
+        self, tasks_queue: Queue, events_queue: Queue, results: TestResultSet, time_budget: TimeBudget
+    ) -> Dict[str, Any]:
         return {
             "tasks_queue": tasks_queue,
             "events_queue": events_queue,
@@ -292,6 +341,8 @@ class ThreadPoolWSGIRunner(ThreadPoolRunner):
                 "store_interactions": self.store_interactions,
                 "max_response_time": self.max_response_time,
                 "dry_run": self.dry_run,
+                "time_budget": time_budget,
+                "rerun_failed": self.rerun_failed,
             },
         }
 
@@ -300,7 +351,9 @@ class ThreadPoolASGIRunner(ThreadPoolRunner):
     def _get_task(self) -> Callable:
         return asgi_thread_task
 
-    def _get_worker_kwargs(self, tasks_queue: Queue, events_queue: Queue, results: TestResultSet) -> Dict[str, Any]:
+    def _get_worker_kwargs(
+        self, tasks_queue: Queue, events_queue: Queue, results: TestResultSet, time_budget: TimeBudget
+    ) -> Dict[str, Any]:
         return {
             "tasks_queue": tasks_queue,
             "events_queue": events_queue,
@@ -316,6 +369,8 @@ class ThreadPoolASGIRunner(ThreadPoolRunner):
                 "store_interactions": self.store_interactions,
                 "max_response_time": self.max_response_time,
                 "dry_run": self.dry_run,
+                "time_budget": time_budget,
+                "rerun_failed": self.rerun_failed,
             },
         }
 
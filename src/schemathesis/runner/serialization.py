@@ -2,6 +2,7 @@
 
 They all consist of primitive types and don't have references to schemas, app, etc.
 """
+import json
 import logging
 from typing import Any, Dict, List, Optional
 
@@ -9,6 +10,7 @@ import attr
 import requests
 
 from ..models import Case, Check, Interaction, Request, Response, Status, TestResult
+from ..types import NotSet
 from ..utils import format_exception
 
 
@@ -20,9 +22,12 @@ class SerializedCase:
     path_template: str = attr.ib()
     path_parameters: Optional[Dict[str, Any]] = attr.ib()
     query: Optional[Dict[str, Any]] = attr.ib()
+    # Generated data to create the same case again. `None` if it can't be represented as JSON, e.g. binary payloads,
+    # or if the check passed
+    data: Optional[Dict[str, Any]] = attr.ib(default=None)
 
     @classmethod
-    def from_case(cls, case: Case, headers: Optional[Dict[str, Any]]) -> "SerializedCase":
+    def from_case(cls, case: Case, headers: Optional[Dict[str, Any]], with_data: bool = True) -> "SerializedCase":
         return cls(
             text_lines=case.as_text_lines(headers),
             requests_code=case.get_code_to_reproduce(headers),
@@ -30,9 +35,28 @@ class SerializedCase:
             path_template=case.full_path,
             path_parameters=case.path_parameters,
             query=case.query,
+            data=serialize_case_data(case) if with_data else None,
         )
 
 
+def serialize_case_data(case: Case) -> Optional[Dict[str, Any]]:
+    """Generated data of a case that could be passed to `APIOperation.make_case` later."""
+    data: Dict[str, Any] = {
+        "path_parameters": case.path_parameters,
+        "headers": case.headers,
+        "cookies": case.cookies,
+        "query": case.query,
+    }
+    if not isinstance(case.body, NotSet):
+        data["body"] = case.body
+        data["media_type"] = case.media_type
+    try:
+        json.dumps(data)
+    except (TypeError, ValueError):
+        return None
+    return data
+
+
 @attr.s(slots=True)  # pragma: no mutate
 class SerializedCheck:
     # Check name
@@ -56,7 +80,8 @@ class SerializedCheck:
         return SerializedCheck(
             name=check.name,
             value=check.value,
-            example=SerializedCase.from_case(check.example, headers),
+            # Only failing test cases are replayed later, there is no need to serialize data of the passed ones
+            example=SerializedCase.from_case(check.example, headers, with_data=check.value == Status.failure),
             message=check.message,
             request=request,
             response=response,
@@ -0,0 +1,135 @@
+"""State of previous test runs that is used to test the failed API operations again."""
+import json
+import os
+import tempfile
+from typing import Any, Callable, Dict, Iterable, List, Optional, Set, Tuple
+
+import attr
+import hypothesis
+
+from .constants import DataGenerationMethod
+from .exceptions import InvalidSchema
+from .models import APIOperation
+from .sharding import get_operation_reference
+from .utils import Result
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class FailedTest:
+    """Testing of an API operation with some data generation method, that failed or errored."""
+
+    # API operation reference, e.g. "GET /api/users"
+    reference: str = attr.ib()  # pragma: no mutate
+    data_generation_method: DataGenerationMethod = attr.ib()  # pragma: no mutate
+    # Hypothesis seed that was used for the test
+    seed: Optional[int] = attr.ib(default=None)  # pragma: no mutate
+    # Generated data of failing test cases. See `serialize_case_data`
+    examples: List[Dict[str, Any]] = attr.ib(factory=list)  # pragma: no mutate
+
+    @property
+    def key(self) -> Tuple[str, DataGenerationMethod]:
+        return self.reference, self.data_generation_method
+
+    def asdict(self) -> Dict[str, Any]:
+        return {
+            "reference": self.reference,
+            "data_generation_method": self.data_generation_method.value,
+            "seed": self.seed,
+            "examples": self.examples,
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "FailedTest":
+        return cls(
+            reference=data["reference"],
+            data_generation_method=DataGenerationMethod(data["data_generation_method"]),
+            seed=data.get("seed"),
+            examples=list(data.get("examples", [])),
+        )
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class RunState:
+    """Tests that failed in previous runs."""
+
+    failed: List[FailedTest] = attr.ib(factory=list)  # pragma: no mutate
+
+    @classmethod
+    def load(cls, path: str) -> "RunState":
+        """Load the state from a file. If there is no such file, then nothing failed before.
+
+        :raises ValueError: If the file content is not a valid state.
+        """
+        try:
+            with open(path, encoding="utf-8") as fd:
+                data = json.load(fd)
+        except FileNotFoundError:
+            return cls()
+        try:
+            return cls(failed=[FailedTest.from_dict(item) for item in data["failed"]])
+        except (KeyError, TypeError) as exc:
+            raise ValueError(f"Unexpected structure: {exc}") from exc
+
+    def save(self, path: str) -> None:
+        directory = os.path.dirname(path) or "."
+        os.makedirs(directory, exist_ok=True)
+        # Write to a temporary file first, so concurrent runs never see a partially written state
+        with tempfile.NamedTemporaryFile("w", encoding="utf-8", dir=directory, delete=False) as fd:
+            json.dump({"failed": [test.asdict() for test in self.failed]}, fd, indent=2)
+        os.replace(fd.name, path)
+
+    def update(self, tested: Set[Tuple[str, DataGenerationMethod]], failed: Iterable[FailedTest]) -> "RunState":
+        """The state after a run that tested some API operations and found new failures.
+
+        Failures of API operations that were not tested in that run, e.g. because of ``--shard``, are kept.
+        """
+        kept = [test for test in self.failed if test.key not in tested]
+        return self.__class__(failed=[*kept, *failed])
+
+    def get(self, reference: str, data_generation_method: DataGenerationMethod) -> Optional[FailedTest]:
+        for test in self.failed:
+            if test.key == (reference, data_generation_method):
+                return test
+        return None
+
+    @property
+    def references(self) -> Set[str]:
+        return {test.reference for test in self.failed}
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class RerunFailed:
+    """Test API operations that failed in a previous run before the other ones, or test only them.
+
+    Failing test cases from the previous run are replayed before generating new ones. The previous seed is used
+    unless a seed is passed explicitly.
+    """
+
+    state: RunState = attr.ib()  # pragma: no mutate
+    only_failed: bool = attr.ib(default=False)  # pragma: no mutate
+
+    def select(
+        self, operations: Iterable[Result[APIOperation, InvalidSchema]]
+    ) -> List[Result[APIOperation, InvalidSchema]]:
+        operations = list(operations)
+        references = self.state.references
+        failed = [result for result in operations if get_operation_reference(result) in references]
+        if not failed:
+            # Nothing from the previous failures is found, therefore everything is tested, the same way as in pytest
+            return operations
+        if self.only_failed:
+            return failed
+        return [*failed, *(result for result in operations if get_operation_reference(result) not in references)]
+
+    def apply(
+        self, test: Callable, operation: APIOperation, data_generation_method: DataGenerationMethod, seed: Optional[int]
+    ) -> Callable:
+        """Use the previous seed and replay failing test cases from the previous run in the explicit phase."""
+        failed = self.state.get(operation.verbose_name, data_generation_method)
+        if failed is None:
+            return test
+        if seed is None and failed.seed is not None:
+            test = hypothesis.seed(failed.seed)(test)
+        for data in failed.examples:
+            test = hypothesis.example(case=operation.make_case(**data))(test)
+        return test
@@ -17,7 +17,7 @@ from hypothesis.strategies import SearchStrategy
 from requests.structures import CaseInsensitiveDict
 
 from ._hypothesis import create_test
-from .constants import DEFAULT_DATA_GENERATION_METHODS, CodeSampleStyle, DataGenerationMethod
+from .constants import DEFAULT_DATA_GENERATION_METHODS, DEFAULT_RECURSION_DEPTH, CodeSampleStyle, DataGenerationMethod
 from .exceptions import InvalidSchema
 from .hooks import HookContext, HookDispatcher, HookScope, dispatch
 from .models import APIOperation, Case
@@ -59,6 +59,7 @@ class BaseSchema(Mapping):
         default=DEFAULT_DATA_GENERATION_METHODS
     )  # pragma: no mutate
     code_sample_style: CodeSampleStyle = attr.ib(default=CodeSampleStyle.default())  # pragma: no mutate
+    recursion_depth: int = attr.ib(default=DEFAULT_RECURSION_DEPTH)  # pragma: no mutate
 
     def __iter__(self) -> Iterator[str]:
         return iter(self.operations)
@@ -121,14 +122,8 @@ class BaseSchema(Mapping):
 
     @property
     def operations_count(self) -> int:
-        total = 0
         # Avoid creating a list of all operation - for large schemas it consumes too much memory
-        for result in self.get_all_operations():
-            if isinstance(result, Ok) or (isinstance(result, Err) and result.err().method is not None):
-                total += 1
-            # In the `Err` case without `method` we don't know how many operations are there.
-            # it happens when all operations are behind an unresolvable reference
-        return total
+        return get_operations_count(self.get_all_operations())
 
     def get_all_operations(self) -> Generator[Result[APIOperation, InvalidSchema], None, None]:
         raise NotImplementedError
@@ -137,6 +132,10 @@ class BaseSchema(Mapping):
         """Get examples from the API operation."""
         raise NotImplementedError
 
+    def get_strategies_from_boundaries(self, operation: APIOperation) -> List[SearchStrategy[Case]]:
+        """Get cases with values at the edges of the API operation's constraints."""
+        raise NotImplementedError
+
     def get_stateful_tests(
         self, response: GenericResponse, operation: APIOperation, stateful: Optional[Stateful]
     ) -> Sequence[StatefulTest]:
@@ -152,10 +151,16 @@ class BaseSchema(Mapping):
         func: Callable,
         settings: Optional[hypothesis.settings] = None,
         seed: Optional[int] = None,
+        operations: Optional[Iterable[Result[APIOperation, InvalidSchema]]] = None,
         _given_kwargs: Optional[Dict[str, GivenInput]] = None,
     ) -> Generator[Tuple[Result[Tuple[APIOperation, Callable], InvalidSchema], DataGenerationMethod], None, None]:
-        """Generate all operations and Hypothesis tests for them."""
-        for result in self.get_all_operations():
+        """Generate all operations and Hypothesis tests for them.
+
+        :param operations: API operations to create tests for. All operations from the schema are used by default.
+        """
+        if operations is None:
+            operations = self.get_all_operations()
+        for result in operations:
             for data_generation_method in self.data_generation_methods:
                 if isinstance(result, Ok):
                     test = create_test(
@@ -261,6 +266,7 @@ class BaseSchema(Mapping):
             skip_deprecated_operations=skip_deprecated_operations,  # type: ignore
             data_generation_methods=data_generation_methods,  # type: ignore
             code_sample_style=code_sample_style,  # type: ignore
+            recursion_depth=self.recursion_depth,
         )
 
     def get_local_hook_dispatcher(self) -> Optional[HookDispatcher]:
@@ -288,6 +294,10 @@ class BaseSchema(Mapping):
         """
         raise NotImplementedError
 
+    def prepare_urlencoded(self, form_data: FormData, operation: APIOperation) -> FormData:
+        """Serialize individual fields of `application/x-www-form-urlencoded` payloads."""
+        raise NotImplementedError
+
     def get_request_payload_content_types(self, operation: APIOperation) -> List[str]:
         raise NotImplementedError
 
@@ -299,6 +309,10 @@ class BaseSchema(Mapping):
     ) -> SearchStrategy:
         raise NotImplementedError
 
+    def get_oauth2_token_url(self, grant_type: str) -> Optional[str]:
+        """URL to obtain OAuth 2 access tokens with the given grant type, if it is defined in the API schema."""
+        return None
+
     def as_state_machine(self) -> Type[APIStateMachine]:
         """Create a state machine class.
 
@@ -316,6 +330,16 @@ class BaseSchema(Mapping):
         raise NotImplementedError
 
 
+def get_operations_count(operations: Iterable[Result[APIOperation, InvalidSchema]]) -> int:
+    total = 0
+    for result in operations:
+        if isinstance(result, Ok) or (isinstance(result, Err) and result.err().method is not None):
+            total += 1
+        # In the `Err` case without `method` we don't know how many operations are there.
+        # it happens when all operations are behind an unresolvable reference
+    return total
+
+
 def operations_to_dict(
     operations: Generator[Result[APIOperation, InvalidSchema], None, None]
 ) -> Dict[str, MethodsDict]:
@@ -1,9 +1,12 @@
-from typing import TYPE_CHECKING, Any, Callable, Collection, Dict, Optional, Type
+from typing import TYPE_CHECKING, Any, Callable, Collection, Dict, List, Optional, Tuple, Type, Union
 
 import attr
 import yaml
 from typing_extensions import Protocol, runtime_checkable
+from urllib3.fields import RequestField
+from urllib3.filepost import encode_multipart_formdata
 
+from .exceptions import SerializationNotPossible
 from .utils import is_json_media_type, is_plain_text_media_type
 
 if TYPE_CHECKING:
@@ -30,6 +33,21 @@ class SerializerContext:
     case: "Case" = attr.ib()  # pragma: no mutate
 
 
+@attr.s(slots=True)  # pragma: no mutate
+class ParameterSerializerContext:
+    """The context for serialization of parameters with the ``content`` keyword.
+
+    Such parameters are serialized during data generation, therefore there is no test case yet.
+
+    :ivar str name: Name of the parameter that is being processed.
+    """
+
+    name: str = attr.ib()  # pragma: no mutate
+
+
+AnySerializerContext = Union[SerializerContext, ParameterSerializerContext]
+
+
 @runtime_checkable
 class Serializer(Protocol):
     """Transform generated data to a form supported by the transport layer.
@@ -38,7 +56,7 @@ class Serializer(Protocol):
     `requests` and `werkzeug` transports.
     """
 
-    def as_requests(self, context: SerializerContext, payload: Any) -> Dict[str, Any]:
+    def as_requests(self, context: AnySerializerContext, payload: Any) -> Dict[str, Any]:
         raise NotImplementedError
 
     def as_werkzeug(self, context: SerializerContext, payload: Any) -> Dict[str, Any]:
@@ -101,7 +119,7 @@ def _to_json(value: Any) -> Dict[str, Any]:
 
 @register("application/json")
 class JSONSerializer:
-    def as_requests(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
+    def as_requests(self, context: AnySerializerContext, value: Any) -> Dict[str, Any]:
         return _to_json(value)
 
     def as_werkzeug(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
@@ -116,7 +134,7 @@ def _to_yaml(value: Any) -> Dict[str, Any]:
 
 @register("text/yaml", aliases=("text/x-yaml", "application/x-yaml", "text/vnd.yaml"))
 class YAMLSerializer:
-    def as_requests(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
+    def as_requests(self, context: AnySerializerContext, value: Any) -> Dict[str, Any]:
         return _to_yaml(value)
 
     def as_werkzeug(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
@@ -154,29 +172,61 @@ def _to_bytes(value: Any) -> bytes:
 
 @register("multipart/form-data")
 class MultipartSerializer:
-    def as_requests(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
+    def as_requests(self, context: AnySerializerContext, value: Any) -> Dict[str, Any]:
         if isinstance(value, bytes):
             return {"data": value}
+        if not isinstance(context, SerializerContext):
+            raise SerializationNotPossible("`multipart/form-data` can't be used for parameters")
         multipart = _prepare_form_data(value)
         files, data = context.case.operation.prepare_multipart(multipart)
         return {"files": files, "data": data}
 
     def as_werkzeug(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
+        if isinstance(value, dict):
+            files, _ = context.case.operation.prepare_multipart(_prepare_form_data(dict(value)))
+            if files and any(_has_content_type(part) for _, part in files):
+                # `werkzeug` can't set content types & headers for non-file parts, therefore the payload is
+                # encoded explicitly
+                body, content_type = _encode_multipart(files)
+                return {"data": body, "content_type": content_type}
         return {"data": value}
 
 
+def _has_content_type(part: Any) -> bool:
+    return isinstance(part, tuple) and len(part) > 2
+
+
+def _encode_multipart(files: List[Tuple[str, Any]]) -> Tuple[bytes, str]:
+    """Encode multipart parts in the same way as `requests` does."""
+    fields = []
+    for name, part in files:
+        if not isinstance(part, tuple):
+            part = (name, part)
+        filename, data, content_type, headers = (part + (None, None))[:4]
+        field = RequestField(name=name, data=data, filename=filename, headers=headers)
+        field.make_multipart(content_type=content_type)
+        fields.append(field)
+    return encode_multipart_formdata(fields)
+
+
+def _to_urlencoded(context: AnySerializerContext, value: Any) -> Dict[str, Any]:
+    if isinstance(value, dict) and isinstance(context, SerializerContext):
+        value = context.case.operation.prepare_urlencoded(value)
+    return {"data": value}
+
+
 @register("application/x-www-form-urlencoded")
 class URLEncodedFormSerializer:
-    def as_requests(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
-        return {"data": value}
+    def as_requests(self, context: AnySerializerContext, value: Any) -> Dict[str, Any]:
+        return _to_urlencoded(context, value)
 
     def as_werkzeug(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
-        return {"data": value}
+        return _to_urlencoded(context, value)
 
 
 @register("text/plain")
 class TextSerializer:
-    def as_requests(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
+    def as_requests(self, context: AnySerializerContext, value: Any) -> Dict[str, Any]:
         if isinstance(value, bytes):
             return {"data": value}
         return {"data": str(value).encode("utf8")}
@@ -189,7 +239,7 @@ class TextSerializer:
 
 @register("application/octet-stream")
 class OctetStreamSerializer:
-    def as_requests(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
+    def as_requests(self, context: AnySerializerContext, value: Any) -> Dict[str, Any]:
         return {"data": _to_bytes(value)}
 
     def as_werkzeug(self, context: SerializerContext, value: Any) -> Dict[str, Any]:
@@ -0,0 +1,142 @@
+"""Network settings for sessions that are used to load API schemas and to send test requests."""
+import time
+from typing import Any, Dict, List, Optional
+
+import attr
+import requests
+from requests.adapters import DEFAULT_POOLSIZE, HTTPAdapter
+from requests.utils import should_bypass_proxies
+from urllib3 import PoolManager
+from urllib3.connection import HTTPConnection, HTTPSConnection
+
+from .throttling import RETRY_STATUS_CODES, RateLimiter, get_retry_delay
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class SessionConfig:
+    """Settings for network connections.
+
+    :ivar proxy: Proxy URL for all requests. Hosts listed in the ``NO_PROXY`` environment variable are not proxied.
+    :ivar pool_size: Maximum number of connections to keep in the pool for each host.
+    :ivar resolve: Addresses to connect to instead of resolving the given host names via DNS.
+    :ivar rate_limiter: Limits the rate of all requests made with this config, even from different threads.
+    :ivar retries: How many times to retry requests that receive responses with 429 or 503 status codes.
+    """
+
+    proxy: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+    pool_size: Optional[int] = attr.ib(default=None)  # pragma: no mutate
+    resolve: Dict[str, str] = attr.ib(factory=dict)  # pragma: no mutate
+    rate_limiter: Optional[RateLimiter] = attr.ib(default=None)  # pragma: no mutate
+    retries: int = attr.ib(default=0)  # pragma: no mutate
+
+    @property
+    def has_waits(self) -> bool:
+        """Whether requests could wait for the rate limiter or before retries."""
+        return self.rate_limiter is not None or self.retries > 0
+
+
+def create_session(config: Optional[SessionConfig] = None) -> requests.Session:
+    if config is None:
+        return requests.Session()
+    return ConfiguredSession(config)
+
+
+class ConfiguredSession(requests.Session):
+    """Session that applies `SessionConfig` to all requests."""
+
+    def __init__(self, config: SessionConfig) -> None:
+        super().__init__()
+        self.session_config = config
+        adapter = ConfiguredAdapter(config)
+        self.mount("http://", adapter)
+        self.mount("https://", adapter)
+
+    def send(self, request: requests.PreparedRequest, **kwargs: Any) -> requests.Response:  # type: ignore
+        retried: List[requests.Response] = []
+        attempt = 0
+        while True:
+            if self.session_config.rate_limiter is not None:
+                # Waiting here is not included in `response.elapsed`, unlike waiting in the transport adapter.
+                # Redirects are sent via this method as well, therefore they are limited too
+                self.session_config.rate_limiter.acquire()
+            response = super().send(request, **kwargs)
+            if response.status_code not in RETRY_STATUS_CODES or attempt >= self.session_config.retries:
+                break
+            # Read the whole response, so it is available for reports, and release the connection
+            response.content  # pylint: disable=pointless-statement
+            response.close()
+            retried.append(response)
+            time.sleep(get_retry_delay(response, attempt))
+            attempt += 1
+        # Retried responses come first, as the history is sorted from the oldest to the most recent response
+        response.history = [*retried, *response.history]
+        return response
+
+
+class ConfiguredAdapter(HTTPAdapter):
+    """Transport adapter that applies `SessionConfig` to outgoing requests."""
+
+    def __init__(self, config: SessionConfig) -> None:
+        self.session_config = config
+        pool_size = config.pool_size or DEFAULT_POOLSIZE
+        super().__init__(pool_connections=pool_size, pool_maxsize=pool_size)
+
+    def init_poolmanager(self, connections: int, maxsize: int, block: bool = False, **pool_kwargs: Any) -> None:
+        super().init_poolmanager(connections, maxsize, block, **pool_kwargs)
+        if self.session_config.resolve:
+            self.poolmanager = ResolvingPoolManager(
+                self.session_config.resolve, num_pools=connections, maxsize=maxsize, block=block, **pool_kwargs
+            )
+
+    def send(self, request: requests.PreparedRequest, *args: Any, **kwargs: Any) -> requests.Response:
+        proxy = self.session_config.proxy
+        if proxy is not None and not should_bypass_proxies(request.url, no_proxy=None):
+            kwargs["proxies"] = {"http": proxy, "https": proxy}
+        return super().send(request, *args, **kwargs)
+
+
+class ResolvingPoolManager(PoolManager):
+    """Connect to pre-defined addresses instead of resolving host names via DNS."""
+
+    def __init__(self, resolve: Dict[str, str], **kwargs: Any) -> None:
+        super().__init__(**kwargs)
+        self.resolve = resolve
+
+    def _new_pool(self, scheme: str, host: str, port: int, request_context: Optional[Dict[str, Any]] = None) -> Any:
+        pool = super()._new_pool(scheme, host, port, request_context)
+        address = self.resolve.get(host)
+        if address is not None:
+            pool.ConnectionCls = RESOLVED_CONNECTION_CLASSES[scheme]
+            pool.conn_kw = {**pool.conn_kw, "resolved_address": address}
+        return pool
+
+
+class ResolvedConnectionMixin:
+    """Open sockets to the given address.
+
+    The original host name is still used in the ``Host`` header and for TLS verification.
+    """
+
+    def __init__(self, *args: Any, resolved_address: str, **kwargs: Any) -> None:
+        super().__init__(*args, **kwargs)  # type: ignore
+        self.resolved_address = resolved_address
+
+    def _new_conn(self) -> Any:
+        # `_dns_host` is the address `urllib3` opens sockets to, but it is also used as the host name afterwards
+        dns_host = self._dns_host  # type: ignore
+        self._dns_host = self.resolved_address
+        try:
+            return super()._new_conn()  # type: ignore
+        finally:
+            self._dns_host = dns_host
+
+
+class ResolvedHTTPConnection(ResolvedConnectionMixin, HTTPConnection):
+    pass
+
+
+class ResolvedHTTPSConnection(ResolvedConnectionMixin, HTTPSConnection):
+    pass
+
+
+RESOLVED_CONNECTION_CLASSES = {"http": ResolvedHTTPConnection, "https": ResolvedHTTPSConnection}
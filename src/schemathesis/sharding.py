@@ -0,0 +1,103 @@
+"""Splitting API operations between multiple test runs, e.g. parallel CI jobs."""
+import re
+from typing import Dict, Iterable, List, Optional
+from xml.etree import ElementTree
+
+import attr
+
+from .exceptions import InvalidSchema
+from .models import APIOperation
+from .utils import Ok, Result
+
+SHARD_RE = re.compile(r"^\s*(\d+)\s*/\s*(\d+)\s*$")
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class Shard:
+    """A part of API operations to test, ``index`` out of ``total`` parts. The ``index`` starts from 1.
+
+    Operations are distributed by their references, e.g. ``GET /api/users``, and do not depend on their order in
+    the schema. All parts together contain every operation exactly once, if they are selected from the same schema
+    with the same ``durations``.
+
+    :ivar durations: Durations of testing operations in seconds, keyed by operation references. If passed, parts are
+        balanced by the total duration instead of the number of operations.
+    """
+
+    index: int = attr.ib()  # pragma: no mutate
+    total: int = attr.ib()  # pragma: no mutate
+    durations: Dict[str, float] = attr.ib(factory=dict, repr=False)  # pragma: no mutate
+
+    def __attrs_post_init__(self) -> None:
+        if not 1 <= self.index <= self.total:
+            raise ValueError(f"Shard index should be between 1 and {self.total}. Got: {self.index}")
+
+    @classmethod
+    def from_string(cls, value: str) -> "Shard":
+        """Create a shard from a string like ``2/4``."""
+        match = SHARD_RE.match(value)
+        if match is None:
+            raise ValueError(f"Should be in N/M format, where N and M are positive integers. Got: {value}")
+        return cls(index=int(match.group(1)), total=int(match.group(2)))
+
+    def __str__(self) -> str:
+        return f"{self.index}/{self.total}"
+
+    def select(
+        self, operations: Iterable[Result[APIOperation, InvalidSchema]]
+    ) -> List[Result[APIOperation, InvalidSchema]]:
+        """Operations that belong to this shard, in their original order."""
+        operations = list(operations)
+        references = [get_operation_reference(result) for result in operations]
+        assignment = distribute(
+            [reference for reference in references if reference is not None], self.total, self.durations
+        )
+        selected = []
+        for result, reference in zip(operations, references):
+            # Schema errors that are not related to a specific operation are reported only by the first shard
+            shard = assignment[reference] if reference is not None else 0
+            if shard == self.index - 1:
+                selected.append(result)
+        return selected
+
+
+def get_operation_reference(result: Result[APIOperation, InvalidSchema]) -> Optional[str]:
+    if isinstance(result, Ok):
+        return result.ok().verbose_name
+    error = result.err()
+    if error.method is not None:
+        return f"{error.method.upper()} {error.full_path}"
+    return None
+
+
+def distribute(references: Iterable[str], total: int, durations: Dict[str, float]) -> Dict[str, int]:
+    """Assign references to ``total`` shards, so that all shards take roughly the same time.
+
+    Operations without known durations take the average known duration. If there are no known durations at all,
+    every operation has the same weight, and shards contain the same number of operations.
+    """
+    unique = sorted(set(references))
+    known = [durations[reference] for reference in unique if reference in durations]
+    default = sum(known) / len(known) if known else 1.0
+    weights = {reference: durations.get(reference, default) for reference in unique}
+    loads = [0.0] * total
+    assignment = {}
+    # The longest operations are placed first, so the shorter ones could fill the gaps afterwards
+    for reference in sorted(unique, key=lambda item: (-weights[item], item)):
+        shard = min(range(total), key=lambda idx: (loads[idx], idx))
+        assignment[reference] = shard
+        loads[shard] += weights[reference]
+    return assignment
+
+
+def load_junit_durations(path: str) -> Dict[str, float]:
+    """Load testing durations of operations from a JUnit XML report.
+
+    Each operation may have multiple test cases, e.g. for different data generation methods. Their durations are summed.
+    """
+    durations: Dict[str, float] = {}
+    for test_case in ElementTree.parse(path).iter("testcase"):
+        name = test_case.get("name")
+        if name is not None:
+            durations[name] = durations.get(name, 0.0) + float(test_case.get("time") or 0)
+    return durations
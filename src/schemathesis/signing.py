@@ -0,0 +1,153 @@
+"""Request signers to use with the ``sign_request`` hook.
+
+.. code:: python
+
+    import schemathesis
+    from schemathesis.signing import HMACSigner
+
+    schemathesis.hooks.register("sign_request")(HMACSigner(key=b"secret"))
+"""
+import datetime
+import hashlib
+import hmac
+import time
+from typing import TYPE_CHECKING, Callable, Dict, List, Optional, Tuple, Union
+from urllib.parse import parse_qsl, quote, unquote, urlsplit
+
+import attr
+import requests
+
+if TYPE_CHECKING:
+    from .hooks import HookContext
+
+SIGV4_ALGORITHM = "AWS4-HMAC-SHA256"
+
+
+def get_body(request: requests.PreparedRequest) -> bytes:
+    body = request.body
+    if body is None:
+        return b""
+    if isinstance(body, str):
+        return body.encode("utf-8")
+    return body
+
+
+def _sha256(data: bytes) -> str:
+    return hashlib.sha256(data).hexdigest()
+
+
+def _hmac(key: bytes, message: str) -> bytes:
+    return hmac.new(key, message.encode("utf-8"), hashlib.sha256).digest()
+
+
+def _utcnow() -> datetime.datetime:
+    return datetime.datetime.now(datetime.timezone.utc)
+
+
+def _to_bytes(value: Union[str, bytes]) -> bytes:
+    if isinstance(value, str):
+        return value.encode("utf-8")
+    return value
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class HMACSigner:
+    """Sign requests with HMAC-SHA256.
+
+    The signature covers the method, the path with the query string, the timestamp and the SHA-256 digest of the body.
+    These values are joined with newlines, and the signature is sent as a hex string in the ``signature_header`` header.
+    """
+
+    key: Union[str, bytes] = attr.ib(converter=_to_bytes)  # pragma: no mutate
+    signature_header: str = attr.ib(default="X-Signature")  # pragma: no mutate
+    timestamp_header: str = attr.ib(default="X-Timestamp")  # pragma: no mutate
+    digest_header: str = attr.ib(default="X-Content-SHA256")  # pragma: no mutate
+    clock: Callable[[], float] = attr.ib(default=time.time)  # pragma: no mutate
+
+    def __call__(self, context: "HookContext", request: requests.PreparedRequest) -> None:
+        timestamp = str(int(self.clock()))
+        digest = _sha256(get_body(request))
+        string_to_sign = "\n".join((request.method or "", request.path_url, timestamp, digest))
+        signature = hmac.new(self.key, string_to_sign.encode("utf-8"), hashlib.sha256).hexdigest()
+        request.headers[self.timestamp_header] = timestamp
+        request.headers[self.digest_header] = digest
+        request.headers[self.signature_header] = signature
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class AWSSigV4Signer:
+    """Sign requests with AWS Signature Version 4.
+
+    Besides AWS itself, it works with services that implement the same signing process, e.g. MinIO.
+    """
+
+    access_key: str = attr.ib()  # pragma: no mutate
+    secret_key: str = attr.ib()  # pragma: no mutate
+    region: str = attr.ib()  # pragma: no mutate
+    service: str = attr.ib()  # pragma: no mutate
+    session_token: Optional[str] = attr.ib(default=None)  # pragma: no mutate
+    clock: Callable[[], datetime.datetime] = attr.ib(default=_utcnow)  # pragma: no mutate
+
+    def __call__(self, context: "HookContext", request: requests.PreparedRequest) -> None:
+        now = self.clock()
+        amz_date = now.strftime("%Y%m%dT%H%M%SZ")
+        date = now.strftime("%Y%m%d")
+        payload_hash = _sha256(get_body(request))
+        request.headers.pop("Authorization", None)
+        request.headers["X-Amz-Date"] = amz_date
+        if self.session_token is not None:
+            request.headers["X-Amz-Security-Token"] = self.session_token
+        if self.service == "s3":
+            request.headers["X-Amz-Content-SHA256"] = payload_hash
+        canonical_headers, signed_headers = self._get_canonical_headers(request)
+        canonical_request = "\n".join(
+            (
+                request.method or "",
+                self._get_canonical_uri(request),
+                _get_canonical_query(request),
+                canonical_headers,
+                signed_headers,
+                payload_hash,
+            )
+        )
+        scope = f"{date}/{self.region}/{self.service}/aws4_request"
+        string_to_sign = "\n".join((SIGV4_ALGORITHM, amz_date, scope, _sha256(canonical_request.encode("utf-8"))))
+        signature = hmac.new(self.get_signing_key(date), string_to_sign.encode("utf-8"), hashlib.sha256).hexdigest()
+        request.headers["Authorization"] = (
+            f"{SIGV4_ALGORITHM} Credential={self.access_key}/{scope}, "
+            f"SignedHeaders={signed_headers}, Signature={signature}"
+        )
+
+    def get_signing_key(self, date: str) -> bytes:
+        key = _hmac(f"AWS4{self.secret_key}".encode("utf-8"), date)
+        key = _hmac(key, self.region)
+        key = _hmac(key, self.service)
+        return _hmac(key, "aws4_request")
+
+    def _get_canonical_uri(self, request: requests.PreparedRequest) -> str:
+        # The path in the prepared URL is already percent-encoded
+        path = unquote(urlsplit(request.url).path) or "/"
+        if self.service == "s3":
+            # S3 is the only service that expects the path to be encoded once
+            return quote(path, safe="/~")
+        return quote(quote(path, safe="/~"), safe="/~")
+
+    @staticmethod
+    def _get_canonical_headers(request: requests.PreparedRequest) -> Tuple[str, str]:
+        headers: Dict[str, str] = {"host": urlsplit(request.url).netloc}
+        for name, value in request.headers.items():
+            lowered = name.lower()
+            if lowered == "content-type" or lowered.startswith("x-amz-"):
+                headers[lowered] = " ".join(str(value).split())
+        names = sorted(headers)
+        canonical_headers = "".join(f"{name}:{headers[name]}\n" for name in names)
+        return canonical_headers, ";".join(names)
+
+
+def _get_canonical_query(request: requests.PreparedRequest) -> str:
+    query = urlsplit(request.url).query
+    pairs: List[Tuple[str, str]] = [
+        (quote(name, safe="-_.~"), quote(value, safe="-_.~"))
+        for name, value in parse_qsl(query, keep_blank_values=True)
+    ]
+    return "&".join(f"{name}={value}" for name, value in sorted(pairs))
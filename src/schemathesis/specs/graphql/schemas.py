@@ -112,6 +112,9 @@ class GraphQLSchema(BaseSchema):
     def get_strategies_from_examples(self, operation: APIOperation) -> List[SearchStrategy[Case]]:
         return []
 
+    def get_strategies_from_boundaries(self, operation: APIOperation) -> List[SearchStrategy[Case]]:
+        return []
+
     def get_stateful_tests(
         self, response: GenericResponse, operation: APIOperation, stateful: Optional[Stateful]
     ) -> Sequence[StatefulTest]:
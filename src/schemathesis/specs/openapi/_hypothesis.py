@@ -3,10 +3,12 @@ import re
 from base64 import b64encode
 from contextlib import contextmanager, suppress
 from copy import deepcopy
-from typing import Any, Callable, Dict, Generator, Iterable, Optional, Tuple, Union
+from typing import Any, Callable, Dict, Generator, Iterable, List, Optional, Tuple, Union
 from urllib.parse import quote_plus
 from weakref import WeakKeyDictionary
 
+import jsonschema
+from hypothesis import event, note, reject
 from hypothesis import strategies as st
 from hypothesis_jsonschema import from_schema
 from requests.auth import _basic_auth_str
@@ -16,10 +18,12 @@ from ...constants import DataGenerationMethod
 from ...exceptions import InvalidSchema
 from ...hooks import GLOBAL_HOOK_DISPATCHER, HookContext, HookDispatcher
 from ...models import APIOperation, Case
+from ...parameters import ParameterSet
 from ...schemas import BaseSchema
 from ...types import NotSet
 from ...utils import NOT_SET
 from .constants import LOCATION_TO_CONTAINER
+from .negative import Mutation, iter_mutations
 from .parameters import OpenAPIParameter, parameters_to_json_schema
 
 PARAMETERS = frozenset(("path_parameters", "headers", "cookies", "query", "body"))
@@ -97,6 +101,7 @@ def get_case_strategy(  # pylint: disable=too-many-locals
     cookies: Union[NotSet, Dict[str, Any]] = NOT_SET,
     query: Union[NotSet, Dict[str, Any]] = NOT_SET,
     body: Any = NOT_SET,
+    security_alternative: Optional[int] = None,
 ) -> Any:
     """A strategy that creates `Case` instances.
 
@@ -109,22 +114,58 @@ def get_case_strategy(  # pylint: disable=too-many-locals
 
     The primary purpose of this behavior is to prevent sending incomplete explicit examples by generating missing parts
     as it works with `body`.
-    """
-    to_strategy = {DataGenerationMethod.positive: make_positive_strategy}[data_generation_method]
 
+    With the negative data generation method, exactly one component is made invalid, and the description of what
+    makes it invalid is stored in `Case.mutation`. Explicitly passed components are never made invalid.
+
+    If the API operation has alternative security requirements, then `security_alternative` is the index of the one
+    to use. By default, it is chosen randomly.
+    """
     context = HookContext(operation)
+    mutation = None
 
     with detect_invalid_schema(operation):
-        path_parameters = get_parameters_value(path_parameters, "path", draw, operation, context, hooks, to_strategy)
-        headers = get_parameters_value(headers, "header", draw, operation, context, hooks, to_strategy)
-        cookies = get_parameters_value(cookies, "cookie", draw, operation, context, hooks, to_strategy)
-        query = get_parameters_value(query, "query", draw, operation, context, hooks, to_strategy)
+        if data_generation_method == DataGenerationMethod.negative:
+            explicit = {"path": path_parameters, "header": headers, "cookie": cookies, "query": query, "body": body}
+            mutations = [item for item in get_mutations(operation) if explicit[item.location] is NOT_SET]
+            if not mutations:
+                cant_negate(operation)
+            mutation = draw(st.sampled_from(mutations))
+
+        def get_to_strategy(location: str) -> Callable[[Dict[str, Any]], st.SearchStrategy]:
+            if mutation is not None and mutation.location == location:
+                return mutation
+            return make_positive_strategy
+
+        parameters_source = operation
+        if operation.security:
+            # Security requirements are alternatives, therefore only one of them is used in each case
+            if security_alternative is None:
+                security_alternative = draw(st.sampled_from(range(len(operation.security))))
+            parameters_source = get_security_alternative(operation, security_alternative)
+        path_parameters = get_parameters_value(
+            path_parameters, "path", draw, parameters_source, context, hooks, get_to_strategy("path")
+        )
+        headers = get_parameters_value(
+            headers, "header", draw, parameters_source, context, hooks, get_to_strategy("header")
+        )
+        cookies = get_parameters_value(
+            cookies, "cookie", draw, parameters_source, context, hooks, get_to_strategy("cookie")
+        )
+        query = get_parameters_value(query, "query", draw, parameters_source, context, hooks, get_to_strategy("query"))
 
         media_type = None
         if body is NOT_SET:
-            if operation.body:
+            if mutation is not None and mutation.location == "body":
+                # Without a payload alternative, the mutation is a missing required body
+                if mutation.parameter is not None:
+                    strategy = _get_body_strategy(mutation.parameter, mutation, operation.schema)
+                    strategy = apply_hooks(operation, context, hooks, strategy, "body")
+                    media_type = mutation.parameter.media_type
+                    body = draw(strategy)
+            elif operation.body:
                 parameter = draw(st.sampled_from(operation.body.items))
-                strategy = _get_body_strategy(parameter, to_strategy, operation.schema)
+                strategy = _get_body_strategy(parameter, make_positive_strategy, operation.schema)
                 strategy = apply_hooks(operation, context, hooks, strategy, "body")
                 media_type = parameter.media_type
                 body = draw(strategy)
@@ -147,9 +188,190 @@ def get_case_strategy(  # pylint: disable=too-many-locals
         cookies=cookies,
         query=query,
         body=body,
+        mutation=mutation.description if mutation is not None else None,
     )
 
 
+def cant_negate(operation: APIOperation) -> None:
+    """Reject the current example if there is nothing to make invalid in this API operation."""
+    event_text = f"Can't generate negative test cases for `{operation.verbose_name}`."
+    note(f"{event_text} It doesn't define any parameters with constraints that could be violated.")
+    event(event_text)
+    reject()
+
+
+_MUTATIONS_CACHE: WeakKeyDictionary = WeakKeyDictionary()
+
+
+def get_mutations(operation: APIOperation) -> List[Mutation]:
+    """All possible ways to make a test case for the given API operation invalid."""
+    # The cache key relies on object ids, which means that the operation should not be mutated
+    if operation in _MUTATIONS_CACHE:
+        return _MUTATIONS_CACHE[operation]
+    mutations = []
+    for location in ("path", "header", "cookie", "query"):
+        if getattr(operation, LOCATION_TO_CONTAINER[location]):
+            schema = get_parameters_schema(operation, location)
+            for schema_mutation in iter_mutations(schema, location):
+                mutations.append(
+                    Mutation(
+                        location=location,
+                        description=schema_mutation.description,
+                        custom_formats=STRING_FORMATS,
+                        schema_mutation=schema_mutation,
+                    )
+                )
+    if operation.body:
+        for parameter in operation.body.items:
+            schema = operation.schema.prepare_schema(parameter.as_json_schema())
+            for schema_mutation in iter_mutations(schema, "body"):
+                description = schema_mutation.description
+                if len(operation.body) > 1:
+                    description += f" ({parameter.media_type})"
+                mutations.append(
+                    Mutation(
+                        location="body",
+                        description=description,
+                        custom_formats=STRING_FORMATS,
+                        schema_mutation=schema_mutation,
+                        parameter=parameter,
+                    )
+                )
+        if all(parameter.is_required for parameter in operation.body.items):
+            mutations.append(
+                Mutation(
+                    location="body", description="required request body was missing", custom_formats=STRING_FORMATS
+                )
+            )
+    _MUTATIONS_CACHE[operation] = mutations
+    return mutations
+
+
+# Collections & strings that are longer than this are too expensive to send as boundary values
+MAX_BOUNDARY_SIZE = 100  # pragma: no mutate
+
+
+def get_boundary_strategies(operation: APIOperation) -> List[st.SearchStrategy[Case]]:
+    """Strategies for cases where a single value is at the edge of its constraints, and the rest is generated.
+
+    Values are taken for each parameter and for each top-level property of object payloads.
+    """
+    strategies = []
+    for location in ("path", "header", "cookie", "query"):
+        container = LOCATION_TO_CONTAINER[location]
+        for parameter in getattr(operation, container):
+            schema = operation.schema.prepare_schema(parameter.as_json_schema())
+            for value in get_boundary_values(schema):
+                if isinstance(value, (list, dict)):
+                    # Explicit values are not serialized according to the parameter's style
+                    continue
+                if location in ("header", "cookie"):
+                    value = str(value)
+                strategies.append(get_case_strategy(operation=operation, **{container: {parameter.name: value}}))
+    if operation.body:
+        for parameter in operation.body.items:
+            schema = operation.schema.prepare_schema(parameter.as_json_schema())
+            for value in get_boundary_values(schema):
+                strategies.append(_get_boundary_body_case(operation, parameter, value))
+            for name, subschema in schema.get("properties", {}).items():
+                for value in get_boundary_values(subschema):
+                    strategies.append(_get_boundary_body_case(operation, parameter, value, property_name=name))
+    return strategies
+
+
+@st.composite  # type: ignore
+def _get_boundary_body_case(
+    draw: Callable,
+    operation: APIOperation,
+    parameter: OpenAPIParameter,
+    value: Any,
+    property_name: Optional[str] = None,
+) -> Case:
+    if property_name is not None:
+        strategy = _get_body_strategy(parameter, make_positive_strategy, operation.schema)
+        body = draw(strategy.filter(lambda item: isinstance(item, dict)))
+        value = {**body, property_name: value}
+    case = draw(get_case_strategy(operation=operation, body=value))
+    case.media_type = parameter.media_type
+    return case
+
+
+def get_boundary_values(schema: Dict[str, Any]) -> List[Any]:
+    """Values at the edges of the given schema's constraints and right inside them.
+
+    Every `enum` member is a boundary value too. Only values that are valid against the schema are returned.
+    """
+    if not isinstance(schema, dict) or "$ref" in schema:
+        return []
+    if isinstance(schema.get("enum"), list):
+        candidates = list(schema["enum"])
+    else:
+        candidates = list(_iter_boundary_candidates(schema))
+    validator = jsonschema.Draft4Validator(schema)
+    values: List[Any] = []
+    seen = set()
+    for candidate in candidates:
+        key = json.dumps(candidate, sort_keys=True)
+        if key in seen:
+            continue
+        seen.add(key)
+        try:
+            if validator.is_valid(candidate):
+                values.append(candidate)
+        except jsonschema.RefResolutionError:
+            continue
+    return values
+
+
+def _iter_boundary_candidates(schema: Dict[str, Any]) -> Generator[Any, None, None]:
+    type_ = schema.get("type")
+    types = [type_] if isinstance(type_, str) else type_ or []
+    if "integer" in types or "number" in types:
+        minimum = schema.get("minimum")
+        maximum = schema.get("maximum")
+        step: Union[int, float] = 1
+        if "integer" not in types and _is_number(minimum) and _is_number(maximum):
+            # Narrow ranges of floats
+            step = min(step, (maximum - minimum) / 4)
+        for limit, is_exclusive, direction in (
+            (minimum, schema.get("exclusiveMinimum") is True, 1),
+            (maximum, schema.get("exclusiveMaximum") is True, -1),
+        ):
+            if _is_number(limit):
+                if is_exclusive:
+                    limit += step * direction
+                yield limit
+                yield limit + step * direction
+    if "string" in types and "pattern" not in schema and "format" not in schema:
+        # Arbitrary strings can't match patterns and formats
+        for length in _iter_boundary_sizes(schema, "minLength", "maxLength"):
+            yield "a" * length
+    if "array" in types and isinstance(schema.get("items"), dict):
+        items = get_boundary_values(schema["items"])
+        if items:
+            unique = schema.get("uniqueItems") is True
+            for size in _iter_boundary_sizes(schema, "minItems", "maxItems"):
+                if unique and size > 1:
+                    continue
+                yield [items[0]] * size
+
+
+def _iter_boundary_sizes(schema: Dict[str, Any], min_keyword: str, max_keyword: str) -> Generator[int, None, None]:
+    minimum = schema.get(min_keyword)
+    maximum = schema.get(max_keyword)
+    if isinstance(minimum, int):
+        yield minimum
+        yield minimum + 1
+    if isinstance(maximum, int) and maximum <= MAX_BOUNDARY_SIZE:
+        yield maximum
+        if maximum > 0:
+            yield maximum - 1
+
+
+def _is_number(value: Any) -> bool:
+    return isinstance(value, (int, float)) and not isinstance(value, bool)
+
+
 YAML_PARSING_ISSUE_MESSAGE = (
     "The API schema contains non-string keys. "
     "If you store your schema in YAML, it is likely caused by unquoted keys parsed as "
@@ -195,7 +417,8 @@ def _get_body_strategy(
     schema = parameter.as_json_schema()
     schema = parent_schema.prepare_schema(schema)
     strategy = to_strategy(schema)
-    if not parameter.is_required:
+    # A missing optional payload is not invalid, therefore it is not generated for negative test cases
+    if not parameter.is_required and not isinstance(to_strategy, Mutation):
         strategy |= st.just(NOT_SET)
     _BODY_STRATEGIES_CACHE.setdefault(parameter, {})[to_strategy] = strategy
     return strategy
@@ -242,13 +465,7 @@ def get_parameters_strategy(
         nested_cache_key = (to_strategy, location, tuple(sorted(exclude)))
         if operation in _PARAMETER_STRATEGIES_CACHE and nested_cache_key in _PARAMETER_STRATEGIES_CACHE[operation]:
             return _PARAMETER_STRATEGIES_CACHE[operation][nested_cache_key]
-        schema = parameters_to_json_schema(parameters)
-        if not operation.schema.validate_schema and location == "path":
-            # If schema validation is disabled, we try to generate data even if the parameter definition
-            # contains errors.
-            # In this case, we know that the `required` keyword should always be `True`.
-            schema["required"] = list(schema["properties"])
-        schema = operation.schema.prepare_schema(schema)
+        schema = get_parameters_schema(operation, location)
         for name in exclude:
             # Values from `exclude` are not necessarily valid for the schema - they come from user-defined examples
             # that may be invalid
@@ -275,6 +492,40 @@ def get_parameters_strategy(
     return st.none()
 
 
+_SECURITY_ALTERNATIVES_CACHE: WeakKeyDictionary = WeakKeyDictionary()
+
+
+def get_security_alternative(operation: APIOperation, index: int) -> APIOperation:
+    """A version of the API operation with parameters of the given security alternative added to the regular ones.
+
+    Therefore, they are generated, serialized, and passed to `before_generate_*` hooks the same way as other parameters.
+    """
+    # The cache key relies on object ids, which means that the operation should not be mutated
+    cache = _SECURITY_ALTERNATIVES_CACHE.setdefault(operation, {})
+    if index not in cache:
+        components: Dict[str, ParameterSet] = {
+            container: ParameterSet(list(getattr(operation, container)))
+            for location, container in LOCATION_TO_CONTAINER.items()
+            if location != "body"
+        }
+        for parameter in operation.security[index]:
+            components[LOCATION_TO_CONTAINER[parameter.location]].add(parameter)
+        cache[index] = operation.clone(body=operation.body, **components)
+    return cache[index]
+
+
+def get_parameters_schema(operation: APIOperation, location: str) -> Dict[str, Any]:
+    """Create a JSON schema for all parameters in the given location."""
+    parameters = getattr(operation, LOCATION_TO_CONTAINER[location])
+    schema = parameters_to_json_schema(parameters)
+    if not operation.schema.validate_schema and location == "path":
+        # If schema validation is disabled, we try to generate data even if the parameter definition
+        # contains errors.
+        # In this case, we know that the `required` keyword should always be `True`.
+        schema["required"] = list(schema["properties"])
+    return operation.schema.prepare_schema(schema)
+
+
 def make_positive_strategy(schema: Dict[str, Any]) -> st.SearchStrategy:
     return from_schema(schema, custom_formats=STRING_FORMATS)
 
@@ -1,7 +1,12 @@
-from typing import TYPE_CHECKING, Any, Dict, Generator, Optional, Union
+from http.cookies import SimpleCookie
+from typing import TYPE_CHECKING, Any, Callable, Dict, Generator, List, Optional, Tuple, Union, cast
+from urllib.parse import parse_qsl, urlencode, urlsplit, urlunsplit
+
+import requests
 
 from ...exceptions import (
     get_headers_error,
+    get_ignored_auth_error,
     get_malformed_media_type_error,
     get_missing_content_type_error,
     get_response_type_error,
@@ -71,15 +76,145 @@ def response_headers_conformance(response: GenericResponse, case: "Case") -> Opt
     if not defined_headers:
         return None
 
-    missing_headers = [header for header in defined_headers if header not in response.headers]
-    if not missing_headers:
-        return None
-    message = ",".join(missing_headers)
-    exc_class = get_headers_error(message)
-    raise exc_class(f"Received a response with missing headers: {message}")
+    schema = case.operation.schema
+    missing_headers = [
+        name
+        for name, definition in defined_headers.items()
+        if name not in response.headers and schema.is_header_required(definition)
+    ]
+    if missing_headers:
+        message = ",".join(missing_headers)
+        exc_class = get_headers_error(message)
+        raise exc_class(f"Received a response with missing headers: {message}")
+    for name, definition in defined_headers.items():
+        if name in response.headers:
+            schema.validate_header(case.operation, name, definition, response.headers[name])
+    return None
 
 
 def response_schema_conformance(response: GenericResponse, case: "Case") -> None:
     if not isinstance(case.operation.schema, BaseOpenAPISchema):
         raise TypeError("This check can be used only with Open API schemas")
     return case.operation.validate_response(response)
+
+
+# Credentials that are used in modified requests. Servers should not accept them
+MALFORMED_CREDENTIALS = "schemathesis-malformed-credentials"
+# `user:password`
+FOREIGN_BASIC_CREDENTIALS = "Basic dXNlcjpwYXNzd29yZA=="
+FOREIGN_BEARER_CREDENTIALS = "Bearer schemathesis-foreign-token"
+# Pairs of credentials locations and names, e.g. `("header", "Authorization")`
+CredentialsLocations = List[Tuple[str, str]]
+
+
+def ignored_auth(response: GenericResponse, case: "Case") -> Optional[bool]:
+    """Verify that the API operation requires the declared authentication.
+
+    A successful request is sent again without credentials, with malformed credentials, and with credentials of a
+    different authentication scheme. All of them should be rejected.
+    """
+    if not isinstance(case.operation.schema, BaseOpenAPISchema):
+        raise TypeError("This check can be used only with Open API schemas")
+    if not 200 <= response.status_code < 300:
+        return None
+    locations = case.operation.schema.get_credentials_locations(case.operation)
+    if not locations:
+        return None
+    original = response.request
+    accepted = []
+    for description, modify in CREDENTIALS_MODIFIERS:
+        request = modify(original, locations)
+        # Request signers could put valid credentials back
+        replayed = case.replay(request, sign=False)
+        if 200 <= replayed.status_code < 300:
+            accepted.append((description, request, replayed.status_code))
+    if accepted:
+        summary = "\n".join(f"    - {description} -> {status_code}" for description, _, status_code in accepted)
+        details = "\n\n".join(
+            f"Request {description}:\n\n{_format_request(request, locations)}" for description, request, _ in accepted
+        )
+        message = (
+            f"Authentication is not enforced for `{case.operation.verbose_name}`\n\n"
+            f"The API accepted requests that should be rejected:\n\n{summary}\n\n"
+            f"Original request:\n\n{_format_request(original, locations)}\n\n{details}"
+        )
+        exc_class = get_ignored_auth_error(case.operation.verbose_name)
+        raise exc_class(message)
+    return None
+
+
+def _without_credentials(
+    request: requests.PreparedRequest, locations: CredentialsLocations
+) -> requests.PreparedRequest:
+    request = request.copy()
+    # Authentication hooks, e.g. Digest or OAuth 2, would send valid credentials on a 401 response
+    request.hooks = requests.hooks.default_hooks()
+    for location, name in locations:
+        _set_credentials(request, location, name, None)
+    return request
+
+
+def _with_malformed_credentials(
+    request: requests.PreparedRequest, locations: CredentialsLocations
+) -> requests.PreparedRequest:
+    modified = _without_credentials(request, locations)
+    for location, name in locations:
+        value = MALFORMED_CREDENTIALS
+        if location == "header" and name.lower() == "authorization":
+            # Keep the original authentication scheme, so only the credentials themselves are malformed
+            scheme = request.headers.get(name, "Bearer").split(" ", 1)[0]
+            value = f"{scheme} {value}"
+        _set_credentials(modified, location, name, value)
+    return modified
+
+
+def _with_foreign_credentials(
+    request: requests.PreparedRequest, locations: CredentialsLocations
+) -> requests.PreparedRequest:
+    modified = _without_credentials(request, locations)
+    if request.headers.get("Authorization", "").lower().startswith("basic "):
+        value = FOREIGN_BEARER_CREDENTIALS
+    else:
+        value = FOREIGN_BASIC_CREDENTIALS
+    _set_credentials(modified, "header", "Authorization", value)
+    return modified
+
+
+CredentialsModifier = Callable[[requests.PreparedRequest, CredentialsLocations], requests.PreparedRequest]
+CREDENTIALS_MODIFIERS: Tuple[Tuple[str, CredentialsModifier], ...] = (
+    ("without credentials", _without_credentials),
+    ("with malformed credentials", _with_malformed_credentials),
+    ("with credentials of a different scheme", _with_foreign_credentials),
+)
+
+
+def _set_credentials(request: requests.PreparedRequest, location: str, name: str, value: Optional[str]) -> None:
+    """Replace credentials in the given location. If the value is `None`, then the credentials are removed."""
+    if location == "header":
+        request.headers.pop(name, None)
+        if value is not None:
+            request.headers[name] = value
+    elif location == "query":
+        parts = urlsplit(cast(str, request.url))
+        query = [(key, item) for key, item in parse_qsl(parts.query, keep_blank_values=True) if key != name]
+        if value is not None:
+            query.append((name, value))
+        request.url = urlunsplit(parts._replace(query=urlencode(query)))
+    elif location == "cookie":
+        cookies = SimpleCookie()
+        cookies.load(request.headers.pop("Cookie", ""))
+        items = [f"{key}={morsel.value}" for key, morsel in cookies.items() if key != name]
+        if value is not None:
+            items.append(f"{name}={value}")
+        if items:
+            request.headers["Cookie"] = "; ".join(items)
+
+
+def _format_request(request: requests.PreparedRequest, locations: CredentialsLocations) -> str:
+    """Show only the parts of the request that are relevant to authentication."""
+    lines = [f"{request.method} {request.url}"]
+    names = {"authorization"} | {name.lower() for location, name in locations if location == "header"}
+    if any(location == "cookie" for location, _ in locations):
+        names.add("cookie")
+    lines.extend(f"{key}: {value}" for key, value in request.headers.items() if key.lower() in names)
+    return "\n".join(f"    {line}" for line in lines)
@@ -1,17 +1,45 @@
 from copy import deepcopy
-from typing import Any, Dict
+from typing import Any, Dict, List, Optional, Set, Tuple
 
+import jsonschema
+
+from ...exceptions import SchemaConversionError
 from ...utils import traverse_schema
 
+# Applicators whose subschemas evaluate properties only if they are valid against the instance
+CONDITIONAL_APPLICATORS = ("anyOf", "oneOf", "if", "then", "else")
+# Keywords that don't constrain instances. In Open API 3.1 Reference Objects, `summary` and `description` are the only
+# allowed siblings of `$ref`, therefore such objects are not changed
+NON_CONSTRAINING_KEYWORDS = (
+    "$id",
+    "$schema",
+    "$anchor",
+    "$dynamicAnchor",
+    "$comment",
+    "$defs",
+    "definitions",
+    "title",
+    "summary",
+    "description",
+)
+
 
-def to_json_schema(schema: Dict[str, Any], nullable_name: str) -> Dict[str, Any]:
+def to_json_schema(
+    schema: Dict[str, Any], nullable_name: Optional[str], is_response_schema: bool = False
+) -> Dict[str, Any]:
     """Convert Open API parameters to JSON Schema.
 
     NOTE. This function is applied to all keywords (including nested) during a schema resolving, thus it is not recursive.
     See a recursive version below.
     """
     schema = deepcopy(schema)
-    if schema.get(nullable_name) is True:
+    if is_response_schema:
+        # Write-only properties are sent only in requests
+        remove_properties(schema, "writeOnly")
+    else:
+        # Read-only properties are sent only in responses
+        remove_properties(schema, "readOnly")
+    if nullable_name is not None and schema.get(nullable_name) is True:
         del schema[nullable_name]
         schema = {"anyOf": [schema, {"type": "null"}]}
     if schema.get("type") == "file":
@@ -20,5 +48,169 @@ def to_json_schema(schema: Dict[str, Any], nullable_name: str) -> Dict[str, Any]
     return schema
 
 
-def to_json_schema_recursive(schema: Dict[str, Any], nullable_name: str) -> Dict[str, Any]:
-    return traverse_schema(schema, to_json_schema, nullable_name)
+def to_json_schema_recursive(
+    schema: Dict[str, Any], nullable_name: Optional[str], is_response_schema: bool = False
+) -> Dict[str, Any]:
+    return traverse_schema(schema, to_json_schema, nullable_name, is_response_schema)
+
+
+def remove_properties(schema: Dict[str, Any], keyword: str) -> None:
+    """Remove properties that are marked with the given keyword, e.g. `readOnly`."""
+    properties = schema.get("properties")
+    if not isinstance(properties, dict):
+        return
+    for name, subschema in list(properties.items()):
+        if isinstance(subschema, dict) and subschema.get(keyword) is True:
+            del properties[name]
+            if isinstance(schema.get("required"), list):
+                schema["required"] = [item for item in schema["required"] if item != name]
+                if not schema["required"]:
+                    # Empty `required` is not valid in Draft 4
+                    del schema["required"]
+
+
+def to_draft_7(schema: Dict[str, Any], root: Optional[Dict[str, Any]] = None, strict: bool = True) -> Dict[str, Any]:
+    """Express JSON Schema 2020-12 keywords via their Draft 7 equivalents.
+
+    `hypothesis-jsonschema` doesn't know about the newer keywords and ignores them during data generation. Only exact
+    equivalents are converted, other keywords are kept as is. If `unevaluatedProperties` can't be converted,
+    `SchemaConversionError` is raised, as ignoring it would accept any properties.
+
+    :param root: The document to resolve local references against. They are resolved to find properties that are
+        evaluated by referenced subschemas.
+    :param bool strict: If ``False``, properties that are evaluated only by conditional subschemas are allowed for
+        all instances, instead of raising an error.
+    """
+    schema = deepcopy(schema)
+    if "unevaluatedProperties" in schema:
+        convert_unevaluated_properties(schema, root, strict)
+    if isinstance(schema.get("prefixItems"), list):
+        if "items" in schema:
+            schema["additionalItems"] = schema["items"]
+        schema["items"] = schema.pop("prefixItems")
+    if isinstance(schema.get("dependentRequired"), dict):
+        schema.setdefault("dependencies", {}).update(schema.pop("dependentRequired"))
+    if isinstance(schema.get("dependentSchemas"), dict):
+        # The original keyword is kept, as references may point to its subschemas. Draft 7 ignores it
+        schema.setdefault("dependencies", {}).update(deepcopy(schema["dependentSchemas"]))
+    return wrap_reference_siblings(schema)
+
+
+def to_draft_7_recursive(
+    schema: Dict[str, Any], root: Optional[Dict[str, Any]] = None, strict: bool = True
+) -> Dict[str, Any]:
+    return traverse_schema(schema, to_draft_7, root=schema if root is None else root, strict=strict)
+
+
+def wrap_reference_siblings(schema: Dict[str, Any]) -> Dict[str, Any]:
+    """Move `$ref` to `allOf` if there are other keywords next to it.
+
+    In JSON Schema 2020-12 they are applied together with `$ref`, while Draft 7 ignores them. Other keywords stay
+    in place, so references to them are still valid.
+    """
+    if not isinstance(schema.get("$ref"), str) or all(
+        key == "$ref" or key in NON_CONSTRAINING_KEYWORDS for key in schema
+    ):
+        return schema
+    wrapped = {key: value for key, value in schema.items() if key != "$ref"}
+    wrapped["allOf"] = [*wrapped.get("allOf", []), {"$ref": schema["$ref"]}]
+    return wrapped
+
+
+def convert_unevaluated_properties(schema: Dict[str, Any], root: Optional[Dict[str, Any]], strict: bool = True) -> None:
+    """Express `unevaluatedProperties` via `additionalProperties`.
+
+    All properties that are evaluated by the schema itself and by subschemas that are always applied to the same
+    instance (`allOf` & `$ref`) are added to its `properties` & `patternProperties`. Subschemas of conditional
+    applicators are valid only for some instances, therefore they may evaluate only the same properties.
+    """
+    unevaluated = schema.pop("unevaluatedProperties")
+    if unevaluated is True or unevaluated == {}:
+        # Any property is allowed
+        return
+    evaluated = EvaluatedProperties(root)
+    evaluated.collect(schema)
+    if evaluated.is_all:
+        # `additionalProperties` evaluates all properties that are not evaluated by other keywords
+        return
+    conditional_names = [name for name in evaluated.conditional_names if name not in evaluated.names]
+    conditional_patterns = [pattern for pattern in evaluated.conditional_patterns if pattern not in evaluated.patterns]
+    if evaluated.is_conditional_all or conditional_names or conditional_patterns:
+        if strict:
+            raise SchemaConversionError(
+                "`unevaluatedProperties` can't be converted to Draft 7, because some properties are evaluated only by "
+                "conditional subschemas"
+            )
+        if evaluated.is_conditional_all:
+            return
+        evaluated.names.extend(conditional_names)
+        evaluated.patterns.extend(conditional_patterns)
+    properties = schema.get("properties", {})
+    names = [name for name in evaluated.names if name not in properties]
+    if names:
+        schema["properties"] = {**properties, **{name: {} for name in names}}
+    pattern_properties = schema.get("patternProperties", {})
+    patterns = [pattern for pattern in evaluated.patterns if pattern not in pattern_properties]
+    if patterns:
+        schema["patternProperties"] = {**pattern_properties, **{pattern: {} for pattern in patterns}}
+    schema["additionalProperties"] = unevaluated
+
+
+class EvaluatedProperties:
+    """Names and patterns of properties that are evaluated by a schema and its subschemas."""
+
+    def __init__(self, root: Optional[Dict[str, Any]]) -> None:
+        self.root = root
+        self.names: List[str] = []
+        self.patterns: List[str] = []
+        # Evaluated only by subschemas that are valid for some instances
+        self.conditional_names: List[str] = []
+        self.conditional_patterns: List[str] = []
+        # Whether all properties are evaluated
+        self.is_all = False
+        self.is_conditional_all = False
+        self._seen_references: Set[Tuple[str, bool]] = set()
+
+    def collect(self, schema: Any, is_conditional: bool = False) -> None:
+        if not isinstance(schema, dict):
+            # Boolean schemas don't evaluate properties
+            return
+        if "$dynamicRef" in schema or "$recursiveRef" in schema:
+            raise SchemaConversionError("`unevaluatedProperties` can't be converted to Draft 7 next to `$dynamicRef`")
+        if "additionalProperties" in schema or "unevaluatedProperties" in schema:
+            if is_conditional:
+                self.is_conditional_all = True
+            else:
+                self.is_all = True
+        names, patterns = (
+            (self.conditional_names, self.conditional_patterns) if is_conditional else (self.names, self.patterns)
+        )
+        names.extend(name for name in schema.get("properties", {}) if name not in names)
+        patterns.extend(pattern for pattern in schema.get("patternProperties", {}) if pattern not in patterns)
+        for subschema in schema.get("allOf", []):
+            self.collect(subschema, is_conditional)
+        if isinstance(schema.get("$ref"), str):
+            self.collect(self._resolve(schema["$ref"], is_conditional), is_conditional)
+        # `not` is valid only if its subschema is not, and annotations of invalid subschemas are dropped
+        for keyword in CONDITIONAL_APPLICATORS:
+            subschemas = schema.get(keyword, [])
+            for subschema in subschemas if isinstance(subschemas, list) else [subschemas]:
+                self.collect(subschema, is_conditional=True)
+        for subschema in schema.get("dependentSchemas", {}).values():
+            self.collect(subschema, is_conditional=True)
+
+    def _resolve(self, reference: str, is_conditional: bool) -> Any:
+        if (reference, is_conditional) in self._seen_references:
+            # Properties of this subschema are already collected, e.g. it is recursive
+            return {}
+        self._seen_references.add((reference, is_conditional))
+        if self.root is None or not reference.startswith("#"):
+            raise SchemaConversionError(
+                f"`unevaluatedProperties` can't be converted to Draft 7 next to a non-local reference: {reference}"
+            )
+        try:
+            return jsonschema.RefResolver("", self.root).resolve_fragment(self.root, reference[1:])
+        except jsonschema.RefResolutionError as exc:
+            raise SchemaConversionError(
+                f"`unevaluatedProperties` can't be converted to Draft 7 next to an unresolvable reference: {reference}"
+            ) from exc
@@ -0,0 +1,117 @@
+"""Support for the `discriminator` keyword in `oneOf` / `anyOf` schemas.
+
+https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.3.md#discriminator-object
+"""
+from typing import Any, Dict, List, Optional
+
+import jsonschema
+
+COMBINATORS = ("oneOf", "anyOf")
+
+
+def get_property_name(schema: Dict[str, Any]) -> Optional[str]:
+    discriminator = schema.get("discriminator")
+    # In Open API 2.0, `discriminator` is a string, and it is used only with `allOf` inheritance
+    if isinstance(discriminator, dict) and isinstance(discriminator.get("propertyName"), str):
+        return discriminator["propertyName"]
+    return None
+
+
+def is_match(reference: str, target: str) -> bool:
+    """Whether a mapping value points to the given reference.
+
+    Mapping values are either references or schema names.
+    """
+    if "/" in target:
+        return reference == target
+    return reference.rsplit("/", 1)[-1] == target
+
+
+def get_values(schema: Dict[str, Any], reference: str) -> List[str]:
+    """Discriminator values that select the schema behind the given reference."""
+    mapping = schema["discriminator"].get("mapping", {})
+    values = [value for value, target in mapping.items() if isinstance(target, str) and is_match(reference, target)]
+    if not values:
+        # Implicit mapping - the schema name is used as a value
+        values.append(reference.rsplit("/", 1)[-1])
+    return values
+
+
+def add_constraints(schema: Dict[str, Any]) -> Dict[str, Any]:
+    """Require the discriminator value that matches each referenced branch.
+
+    Without it, generated objects often match one branch but carry a discriminator value of another one.
+    Must be applied before branches are inlined, as mapping uses references to identify them.
+    """
+    property_name = get_property_name(schema)
+    if property_name is None:
+        return schema
+    for keyword in COMBINATORS:
+        branches = schema.get(keyword)
+        if not isinstance(branches, list):
+            continue
+        for idx, branch in enumerate(branches):
+            if isinstance(branch, dict) and isinstance(branch.get("$ref"), str):
+                constraint = {
+                    "properties": {property_name: {"enum": get_values(schema, branch["$ref"])}},
+                    "required": [property_name],
+                }
+                branches[idx] = {"allOf": [branch, constraint]}
+    return schema
+
+
+def select_branch(schema: Dict[str, Any], keyword: str, instance: Any) -> Optional[int]:
+    """Index of the branch selected by the discriminator value of the given instance."""
+    property_name = get_property_name(schema)
+    if property_name is None or not isinstance(instance, dict):
+        return None
+    value = instance.get(property_name)
+    if not isinstance(value, str):
+        return None
+    target = schema["discriminator"].get("mapping", {}).get(value, value)
+    for idx, branch in enumerate(schema[keyword]):
+        if not isinstance(branch, dict):
+            continue
+        reference = branch.get("$ref")
+        if isinstance(reference, str):
+            if is_match(reference, target):
+                return idx
+        elif value in _get_allowed_values(branch, property_name):
+            # The branch is inlined, or its constraints were added by `add_constraints`
+            return idx
+    return None
+
+
+def _get_allowed_values(branch: Dict[str, Any], property_name: str) -> List[Any]:
+    subschema = branch.get("properties", {}).get(property_name)
+    if isinstance(subschema, dict):
+        if "const" in subschema:
+            return [subschema["const"]]
+        if isinstance(subschema.get("enum"), list):
+            return subschema["enum"]
+    values = []
+    for item in branch.get("allOf", []):
+        if isinstance(item, dict):
+            values.extend(_get_allowed_values(item, property_name))
+    return values
+
+
+def narrow_error(error: jsonschema.ValidationError) -> jsonschema.ValidationError:
+    """Replace the error with the most relevant one from the branch selected by the discriminator.
+
+    `jsonschema` picks an error from any `oneOf` / `anyOf` branch, which is confusing if the discriminator value clearly
+    points to a specific one.
+    """
+    child, parent = error, error.parent
+    while parent is not None:
+        if parent.validator in COMBINATORS and isinstance(parent.schema, dict):
+            idx = select_branch(parent.schema, parent.validator, parent.instance)
+            if idx is not None:
+                if child.relative_schema_path and child.relative_schema_path[0] == idx:
+                    return error
+                errors = [item for item in parent.context if item.relative_schema_path[0] == idx]
+                if errors:
+                    return jsonschema.exceptions.best_match(errors)
+                return error
+        child, parent = parent, parent.parent
+    return error
@@ -134,6 +134,10 @@ def get_strategies_from_examples(
         get_static_parameters_from_example(operation), get_static_parameters_from_properties(operation)
     ):
         strategies.append(get_case_strategy(operation=operation, **static_parameters))
+    # Each alternative security requirement is tested at least once
+    strategies.extend(
+        get_case_strategy(operation=operation, security_alternative=index) for index in range(len(operation.security))
+    )
     return strategies
 
 
@@ -118,6 +118,25 @@ class Link(StatefulTest):
                     components[LOCATION_TO_CONTAINER[location]].add(parameter)
         return self.operation.clone(**components)
 
+    def is_used_in(self, parsed: ParsedData, case: Case) -> bool:
+        """Whether all values from the parsed data are used in the given case."""
+        for full_name, value in parsed.parameters.items():
+            try:
+                location, name = full_name.split(".")
+                locations = [location]
+            except ValueError:
+                name = full_name
+                locations = [location for location in LOCATION_TO_CONTAINER if location != "body"]
+            containers = [getattr(case, LOCATION_TO_CONTAINER[location]) or {} for location in locations]
+            if not any(name in container and container[name] == value for container in containers):
+                return False
+        if parsed.body is not NOT_SET and case.body != parsed.body:
+            return False
+        return bool(parsed.parameters) or parsed.body is not NOT_SET
+
+    def reads_created_resource(self, response: GenericResponse) -> bool:
+        return is_read_after_creation(response, self.operation)
+
     def _get_container_by_parameter_name(self, full_name: str, templates: Dict[str, Dict[str, Dict[str, Any]]]) -> List:
         """Detect in what request part the parameters is defined."""
         location: Optional[str]
@@ -210,6 +229,17 @@ class OpenAPILink(Direction):
             return self.operation.schema.get_operation_by_id(self.definition["operationId"])  # type: ignore
         return self.operation.schema.get_operation_by_reference(self.definition["operationRef"])  # type: ignore
 
+    def reads_created_resource(self, response: GenericResponse) -> bool:
+        return is_read_after_creation(response, self.get_target_operation())
+
+
+def is_read_after_creation(response: GenericResponse, target: APIOperation) -> bool:
+    """Whether a link from the given response leads to reading the resource that was created by the call.
+
+    The "201 Created" status code means that the call created a resource, e.g. via `POST` or `PUT`, and `GET` reads it.
+    """
+    return response.status_code == 201 and target.method.upper() == "GET"
+
 
 def get_container(case: Case, location: Optional[str], name: str) -> Optional[Dict[str, Any]]:
     """Get a container that suppose to store the given parameter."""
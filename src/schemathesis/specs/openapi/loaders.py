@@ -3,7 +3,6 @@ from typing import IO, Any, Callable, Dict, Iterable, Optional, Union
 from urllib.parse import urljoin
 
 import jsonschema
-import requests
 import yaml
 from jsonschema import ValidationError
 from starlette.applications import Starlette
@@ -11,14 +10,20 @@ from starlette.testclient import TestClient as ASGIClient
 from werkzeug.test import Client
 from yarl import URL
 
-from ...constants import DEFAULT_DATA_GENERATION_METHODS, CodeSampleStyle, DataGenerationMethod
+from ...constants import (
+    DEFAULT_DATA_GENERATION_METHODS,
+    DEFAULT_RECURSION_DEPTH,
+    CodeSampleStyle,
+    DataGenerationMethod,
+)
 from ...exceptions import HTTPError
 from ...hooks import HookContext, dispatch
 from ...lazy import LazySchema
+from ...sessions import SessionConfig, create_session
 from ...types import Filter, NotSet, PathLike
 from ...utils import NOT_SET, StringDatesYAMLLoader, WSGIResponse, require_relative_url, setup_headers
 from . import definitions
-from .schemas import BaseOpenAPISchema, OpenApi30, SwaggerV20
+from .schemas import BaseOpenAPISchema, OpenApi30, OpenApi31, SwaggerV20
 
 
 def from_path(
@@ -35,6 +40,7 @@ def from_path(
     force_schema_version: Optional[str] = None,
     data_generation_methods: Iterable[DataGenerationMethod] = DEFAULT_DATA_GENERATION_METHODS,
     code_sample_style: str = CodeSampleStyle.default().name,
+    recursion_depth: int = DEFAULT_RECURSION_DEPTH,
     encoding: str = "utf8",
 ) -> BaseOpenAPISchema:
     """Load Open API schema via a file from an OS path.
@@ -56,6 +62,7 @@ def from_path(
             force_schema_version=force_schema_version,
             data_generation_methods=data_generation_methods,
             code_sample_style=code_sample_style,
+            recursion_depth=recursion_depth,
             location=pathlib.Path(path).absolute().as_uri(),
         )
 
@@ -75,16 +82,20 @@ def from_uri(
     force_schema_version: Optional[str] = None,
     data_generation_methods: Iterable[DataGenerationMethod] = DEFAULT_DATA_GENERATION_METHODS,
     code_sample_style: str = CodeSampleStyle.default().name,
+    recursion_depth: int = DEFAULT_RECURSION_DEPTH,
+    session_config: Optional[SessionConfig] = None,
     **kwargs: Any,
 ) -> BaseOpenAPISchema:
     """Load Open API schema from the network.
 
     :param str uri: Schema URL.
+    :param session_config: Proxy, connection pool and address resolution settings for loading the schema.
     """
     setup_headers(kwargs)
     if not base_url and port:
         base_url = str(URL(uri).with_port(port))
-    response = requests.get(uri, **kwargs)
+    with create_session(session_config) as session:
+        response = session.get(uri, **kwargs)
     HTTPError.raise_for_status(response)
     return from_file(
         response.text,
@@ -99,6 +110,7 @@ def from_uri(
         force_schema_version=force_schema_version,
         data_generation_methods=data_generation_methods,
         code_sample_style=code_sample_style,
+        recursion_depth=recursion_depth,
         location=uri,
     )
 
@@ -117,6 +129,7 @@ def from_file(
     force_schema_version: Optional[str] = None,
     data_generation_methods: Iterable[DataGenerationMethod] = DEFAULT_DATA_GENERATION_METHODS,
     code_sample_style: str = CodeSampleStyle.default().name,
+    recursion_depth: int = DEFAULT_RECURSION_DEPTH,
     location: Optional[str] = None,
     **kwargs: Any,  # needed in the runner to have compatible API across all loaders
 ) -> BaseOpenAPISchema:
@@ -138,6 +151,7 @@ def from_file(
         force_schema_version=force_schema_version,
         data_generation_methods=data_generation_methods,
         code_sample_style=code_sample_style,
+        recursion_depth=recursion_depth,
         location=location,
     )
 
@@ -156,11 +170,14 @@ def from_dict(
     force_schema_version: Optional[str] = None,
     data_generation_methods: Iterable[DataGenerationMethod] = DEFAULT_DATA_GENERATION_METHODS,
     code_sample_style: str = CodeSampleStyle.default().name,
+    recursion_depth: int = DEFAULT_RECURSION_DEPTH,
     location: Optional[str] = None,
 ) -> BaseOpenAPISchema:
     """Load Open API schema from a Python dictionary.
 
     :param dict raw_schema: A schema to load.
+    :param int recursion_depth: How many times recursive references are inlined before their optional parts
+        are cut off during data generation.
     """
     _code_sample_style = CodeSampleStyle.from_str(code_sample_style)
     dispatch("before_load_schema", HookContext(), raw_schema)
@@ -179,6 +196,7 @@ def from_dict(
             validate_schema=validate_schema,
             data_generation_methods=data_generation_methods,
             code_sample_style=_code_sample_style,
+            recursion_depth=recursion_depth,
             location=location,
         )
 
@@ -196,6 +214,25 @@ def from_dict(
             validate_schema=validate_schema,
             data_generation_methods=data_generation_methods,
             code_sample_style=_code_sample_style,
+            recursion_depth=recursion_depth,
+            location=location,
+        )
+
+    def init_openapi_31() -> OpenApi31:
+        _maybe_validate_schema(raw_schema, definitions.OPENAPI_31_VALIDATOR, validate_schema)
+        return OpenApi31(
+            raw_schema,
+            app=app,
+            base_url=base_url,
+            method=method,
+            endpoint=endpoint,
+            tag=tag,
+            operation_id=operation_id,
+            skip_deprecated_operations=skip_deprecated_operations,
+            validate_schema=validate_schema,
+            data_generation_methods=data_generation_methods,
+            code_sample_style=_code_sample_style,
+            recursion_depth=recursion_depth,
             location=location,
         )
 
@@ -203,9 +240,13 @@ def from_dict(
         return init_openapi_2()
     if force_schema_version == "30":
         return init_openapi_3()
+    if force_schema_version == "31":
+        return init_openapi_31()
     if "swagger" in raw_schema:
         return init_openapi_2()
     if "openapi" in raw_schema:
+        if str(raw_schema["openapi"]).startswith("3.1"):
+            return init_openapi_31()
         return init_openapi_3()
     raise ValueError("Unsupported schema type")
 
@@ -273,6 +314,7 @@ def from_wsgi(
     force_schema_version: Optional[str] = None,
     data_generation_methods: Iterable[DataGenerationMethod] = DEFAULT_DATA_GENERATION_METHODS,
     code_sample_style: str = CodeSampleStyle.default().name,
+    recursion_depth: int = DEFAULT_RECURSION_DEPTH,
     **kwargs: Any,
 ) -> BaseOpenAPISchema:
     """Load Open API schema from a WSGI app.
@@ -298,6 +340,7 @@ def from_wsgi(
         force_schema_version=force_schema_version,
         data_generation_methods=data_generation_methods,
         code_sample_style=code_sample_style,
+        recursion_depth=recursion_depth,
         location=schema_path,
     )
 
@@ -324,6 +367,7 @@ def from_aiohttp(
     force_schema_version: Optional[str] = None,
     data_generation_methods: Iterable[DataGenerationMethod] = DEFAULT_DATA_GENERATION_METHODS,
     code_sample_style: str = CodeSampleStyle.default().name,
+    recursion_depth: int = DEFAULT_RECURSION_DEPTH,
     **kwargs: Any,
 ) -> BaseOpenAPISchema:
     """Load Open API schema from an AioHTTP app.
@@ -348,6 +392,7 @@ def from_aiohttp(
         force_schema_version=force_schema_version,
         data_generation_methods=data_generation_methods,
         code_sample_style=code_sample_style,
+        recursion_depth=recursion_depth,
         **kwargs,
     )
 
@@ -366,6 +411,7 @@ def from_asgi(
     force_schema_version: Optional[str] = None,
     data_generation_methods: Iterable[DataGenerationMethod] = DEFAULT_DATA_GENERATION_METHODS,
     code_sample_style: str = CodeSampleStyle.default().name,
+    recursion_depth: int = DEFAULT_RECURSION_DEPTH,
     **kwargs: Any,
 ) -> BaseOpenAPISchema:
     """Load Open API schema from an ASGI app.
@@ -391,5 +437,6 @@ def from_asgi(
         force_schema_version=force_schema_version,
         data_generation_methods=data_generation_methods,
         code_sample_style=code_sample_style,
+        recursion_depth=recursion_depth,
         location=schema_path,
     )
@@ -0,0 +1,46 @@
+"""Generation of data that doesn't match the API schema."""
+from typing import Any, Dict, Optional
+
+import attr
+import jsonschema
+from hypothesis import strategies as st
+from hypothesis_jsonschema import from_schema
+
+from ..parameters import OpenAPIParameter
+from .mutations import SchemaMutation, iter_mutations
+
+__all__ = ("Mutation", "SchemaMutation", "iter_mutations", "negative_schema")
+
+
+@attr.s(slots=True, eq=False)  # pragma: no mutate
+class Mutation:
+    """A way to make a single component of a test case invalid.
+
+    Instances are used as cache keys for strategies, therefore they are compared by identity.
+    """
+
+    location: str = attr.ib()  # pragma: no mutate
+    description: str = attr.ib()  # pragma: no mutate
+    custom_formats: Dict[str, st.SearchStrategy] = attr.ib()  # pragma: no mutate
+    # `None` means that the whole component is missing. Possible only for required bodies
+    schema_mutation: Optional[SchemaMutation] = attr.ib(default=None)  # pragma: no mutate
+    # The mutated payload alternative, for mutations in the `body` location
+    parameter: Optional[OpenAPIParameter] = attr.ib(default=None)  # pragma: no mutate
+
+    def __call__(self, schema: Dict[str, Any]) -> st.SearchStrategy:
+        assert self.schema_mutation is not None
+        return negative_schema(schema, self.schema_mutation, self.custom_formats)
+
+
+def negative_schema(
+    schema: Dict[str, Any], mutation: SchemaMutation, custom_formats: Dict[str, st.SearchStrategy]
+) -> st.SearchStrategy:
+    """A strategy for values that don't match the given schema in the way described by the mutation."""
+    try:
+        mutated = mutation.apply(schema)
+    except (KeyError, ValueError):
+        # The mutated part is not in this schema. E.g. the property was excluded because it has an explicit value
+        return st.nothing()
+    validator = jsonschema.Draft4Validator(schema)
+    # The mutated schema may still accept some valid values. E.g. an integer is a valid number
+    return from_schema(mutated, custom_formats=custom_formats).filter(lambda value: not validator.is_valid(value))
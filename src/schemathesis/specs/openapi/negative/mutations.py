@@ -0,0 +1,321 @@
+"""Schema mutations that describe how to make generated data invalid.
+
+Each mutation points to a sub-schema within a JSON Schema and knows how to replace it with another one that matches
+only values that are invalid according to the original sub-schema.
+"""
+from copy import deepcopy
+from typing import Any, Callable, Dict, Generator, List, Tuple
+
+import attr
+
+Schema = Dict[str, Any]
+Transform = Callable[[Schema], Schema]
+
+# Used in descriptions to name top-level schema properties of non-body locations
+LOCATION_NAMES = {
+    "path": "path parameter",
+    "query": "query parameter",
+    "header": "header",
+    "cookie": "cookie",
+}
+ALL_TYPES = ("null", "boolean", "integer", "number", "string", "array", "object")
+NUMERIC_TYPES = ("integer", "number")
+# Non-body parameters are always sent as strings, therefore the only way to violate a non-string type is to send
+# a string that can't be interpreted as a value of that type
+NON_SCALAR_STRING = {"type": "string", "pattern": r"^\D+$", "not": {"enum": ["true", "false"]}}
+# A property name that is added to objects that don't allow additional properties
+UNKNOWN_PROPERTY = "x-schemathesis-unknown-property"
+# Limits the traversal of deeply nested and recursive schemas
+MAX_DEPTH = 5  # pragma: no mutate
+# Data that exceeds this size is too expensive to generate
+MAX_SIZE = 256  # pragma: no mutate
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class SchemaMutation:
+    """A single way to make data invalid against a schema."""
+
+    # Keys leading to the mutated sub-schema from the schema root
+    path: Tuple[str, ...] = attr.ib()  # pragma: no mutate
+    transform: Transform = attr.ib()  # pragma: no mutate
+    # Human-readable explanation of what is wrong with the generated data
+    description: str = attr.ib()  # pragma: no mutate
+
+    def apply(self, schema: Schema) -> Schema:
+        """Create a mutated copy of the given schema.
+
+        Everything on the way to the mutated sub-schema becomes required. Otherwise, the mutated part might not be
+        generated at all.
+        """
+        root = deepcopy(schema)
+        inline_root(root)
+        if not self.path:
+            return self.transform(root)
+        parent = root
+        for idx, key in enumerate(self.path):
+            if key == "properties" and idx + 1 < len(self.path):
+                required = parent.setdefault("required", [])
+                if self.path[idx + 1] not in required:
+                    required.append(self.path[idx + 1])
+            elif key == "items":
+                parent["minItems"] = max(parent.get("minItems", 0), 1)
+            child = resolve(root, parent[key])
+            if child is None:
+                raise ValueError(f"Can not resolve a reference: {parent[key]['$ref']}")
+            if idx == len(self.path) - 1:
+                parent[key] = self.transform(child)
+            else:
+                parent[key] = child
+                parent = child
+        return root
+
+
+def inline_root(root: Schema) -> None:
+    """Replace the root-level reference with the keywords of its target.
+
+    Other root-level keywords (e.g. `definitions`) are kept in place, so nested references are still resolvable.
+    """
+    if "$ref" in root:
+        target = resolve(root, {"$ref": root.pop("$ref")})
+        if target is not None:
+            root.update(target)
+
+
+def resolve(root: Schema, node: Any) -> Any:
+    """Follow local references until a non-reference node is found.
+
+    Returns `None` if the reference can't be resolved within the root schema.
+    """
+    for _ in range(MAX_DEPTH):
+        if not isinstance(node, dict) or "$ref" not in node:
+            return node
+        reference = node["$ref"]
+        if not isinstance(reference, str) or not reference.startswith("#/"):
+            return None
+        node = root
+        for part in reference[2:].split("/"):
+            part = part.replace("~1", "/").replace("~0", "~")
+            if not isinstance(node, dict) or part not in node:
+                return None
+            node = node[part]
+        node = deepcopy(node)
+    return None
+
+
+def iter_mutations(schema: Schema, location: str) -> Generator[SchemaMutation, None, None]:
+    """All mutations applicable to a schema of the given location.
+
+    For non-body locations, the schema is an object where each property corresponds to a single parameter.
+    """
+    if location == "body":
+        root = deepcopy(schema)
+        inline_root(root)
+        yield from _iter_body_mutations(root, root, (), [], 0)
+    else:
+        kind = LOCATION_NAMES[location]
+        for name, subschema in schema.get("properties", {}).items():
+            node = resolve(schema, subschema)
+            if isinstance(node, dict):
+                yield from _iter_value_mutations(node, ("properties", name), f"{kind} `{name}`", is_body=False)
+        if location != "path":
+            # Path parameters can't be missing - the URL is not valid without them
+            for name in schema.get("required", []):
+                yield SchemaMutation(
+                    path=(), transform=remove_property(name), description=f"required {kind} `{name}` was missing"
+                )
+
+
+def _iter_body_mutations(
+    root: Schema, node: Schema, path: Tuple[str, ...], pointer: List[str], depth: int
+) -> Generator[SchemaMutation, None, None]:
+    subject = describe_pointer(pointer)
+    yield from _iter_value_mutations(node, path, subject, is_body=True)
+    types = get_types(node)
+    if "object" in types or (not types and "properties" in node):
+        for name in node.get("required", []):
+            if not isinstance(name, str):
+                continue
+            yield SchemaMutation(
+                path=path,
+                transform=remove_property(name),
+                description=f"required {describe_pointer(pointer + [name])} was missing",
+            )
+        if node.get("additionalProperties") is False and "patternProperties" not in node:
+            yield SchemaMutation(
+                path=path,
+                transform=add_unknown_property,
+                description=f"{subject} had an unexpected property `{UNKNOWN_PROPERTY}`",
+            )
+    if depth + 1 >= MAX_DEPTH:
+        return
+    for name, subschema in node.get("properties", {}).items():
+        child = resolve(root, subschema)
+        if isinstance(child, dict):
+            yield from _iter_body_mutations(root, child, path + ("properties", name), pointer + [name], depth + 1)
+    items = resolve(root, node.get("items"))
+    if isinstance(items, dict):
+        yield from _iter_body_mutations(root, items, path + ("items",), pointer + ["[]"], depth + 1)
+
+
+def _iter_value_mutations(
+    node: Schema, path: Tuple[str, ...], subject: str, is_body: bool
+) -> Generator[SchemaMutation, None, None]:
+    # pylint: disable=too-many-branches
+    types = get_types(node)
+    if types:
+        expected = " or ".join(types)
+        if is_body:
+            for target in ALL_TYPES:
+                if target in types or (target in NUMERIC_TYPES and set(types) & set(NUMERIC_TYPES)):
+                    # Integers are valid numbers, and numbers are often generated as integral ones
+                    continue
+                yield SchemaMutation(
+                    path=path,
+                    transform=replace_with({"type": target}),
+                    description=f"{subject} had type {target} instead of {expected}",
+                )
+        elif {"array", "object"} & set(types):
+            # Arrays & objects in non-body locations are serialized according to their declared type, therefore
+            # values of other types would be mangled during serialization
+            return
+        elif "string" not in types:
+            yield SchemaMutation(
+                path=path,
+                transform=replace_with(NON_SCALAR_STRING),
+                description=f"{subject} had type string instead of {expected}",
+            )
+    numeric_type = "integer" if types == ["integer"] else "number"
+    if not types or set(types) & set(NUMERIC_TYPES):
+        minimum = node.get("minimum")
+        if is_number(minimum):
+            if node.get("exclusiveMinimum") is True:
+                new = {"type": numeric_type, "maximum": minimum}
+                description = f"{subject} was not greater than {minimum}"
+            else:
+                new = {"type": numeric_type, "maximum": minimum, "exclusiveMaximum": True}
+                description = f"{subject} was less than the minimum of {minimum}"
+            yield SchemaMutation(path=path, transform=replace_with(new), description=description)
+        maximum = node.get("maximum")
+        if is_number(maximum):
+            if node.get("exclusiveMaximum") is True:
+                new = {"type": numeric_type, "minimum": maximum}
+                description = f"{subject} was not less than {maximum}"
+            else:
+                new = {"type": numeric_type, "minimum": maximum, "exclusiveMinimum": True}
+                description = f"{subject} was greater than the maximum of {maximum}"
+            yield SchemaMutation(path=path, transform=replace_with(new), description=description)
+    if not types or "string" in types:
+        min_length = node.get("minLength")
+        if is_integer(min_length) and min_length > 0:
+            yield SchemaMutation(
+                path=path,
+                transform=replace_with({"type": "string", "maxLength": min_length - 1}),
+                description=f"{subject} was shorter than {min_length} characters",
+            )
+        max_length = node.get("maxLength")
+        if is_integer(max_length) and max_length < MAX_SIZE:
+            yield SchemaMutation(
+                path=path,
+                transform=replace_with({"type": "string", "minLength": max_length + 1}),
+                description=f"{subject} was longer than {max_length} characters",
+            )
+        pattern = node.get("pattern")
+        if isinstance(pattern, str):
+            yield SchemaMutation(
+                path=path,
+                transform=replace_with({"type": "string", "not": {"pattern": pattern}}),
+                description=f"{subject} did not match the pattern `{pattern}`",
+            )
+    if is_body and (not types or "array" in types):
+        items = node.get("items", {})
+        min_items = node.get("minItems")
+        if is_integer(min_items) and min_items > 0:
+            yield SchemaMutation(
+                path=path,
+                transform=replace_with({"type": "array", "items": items, "maxItems": min_items - 1}),
+                description=f"{subject} had fewer than {min_items} items",
+            )
+        max_items = node.get("maxItems")
+        if is_integer(max_items) and max_items < MAX_SIZE:
+            yield SchemaMutation(
+                path=path,
+                transform=replace_with({"type": "array", "items": items, "minItems": max_items + 1}),
+                description=f"{subject} had more than {max_items} items",
+            )
+    enum = node.get("enum")
+    if isinstance(enum, list) and types != ["boolean"]:
+        negated: Schema = {"not": {"enum": enum}}
+        if types:
+            negated["type"] = types if len(types) > 1 else types[0]
+        yield SchemaMutation(
+            path=path, transform=replace_with(negated), description=f"{subject} was not one of the allowed values"
+        )
+
+
+def describe_pointer(pointer: List[str]) -> str:
+    """Human-readable name of a location within the request body.
+
+    [] => "request body"
+    ["user", "age"] => "property `user.age`"
+    ["tags", "[]"] => "item of property `tags`"
+    """
+    if not pointer:
+        return "request body"
+    if pointer[-1] == "[]":
+        return f"item of {describe_pointer(pointer[:-1])}"
+    path = ""
+    for part in pointer:
+        if part == "[]":
+            path += "[]"
+        elif path:
+            path += f".{part}"
+        else:
+            path = part
+    return f"property `{path}`"
+
+
+def get_types(node: Schema) -> List[str]:
+    type_ = node.get("type")
+    if isinstance(type_, str):
+        return [type_]
+    if isinstance(type_, list):
+        return [item for item in type_ if isinstance(item, str)]
+    return []
+
+
+def is_number(value: Any) -> bool:
+    return isinstance(value, (int, float)) and not isinstance(value, bool)
+
+
+def is_integer(value: Any) -> bool:
+    return isinstance(value, int) and not isinstance(value, bool)
+
+
+def replace_with(new: Schema) -> Transform:
+    def transform(_: Schema) -> Schema:
+        return deepcopy(new)
+
+    return transform
+
+
+def remove_property(name: str) -> Transform:
+    def transform(node: Schema) -> Schema:
+        node = deepcopy(node)
+        node.get("properties", {}).pop(name, None)
+        node["required"] = [item for item in node["required"] if item != name]
+        if not node["required"]:
+            del node["required"]
+        if node.get("additionalProperties") is not False and "not" not in node:
+            # Otherwise the property may still be generated as an additional one
+            node["not"] = {"required": [name]}
+        return node
+
+    return transform
+
+
+def add_unknown_property(node: Schema) -> Schema:
+    node = deepcopy(node)
+    node.setdefault("properties", {})[UNKNOWN_PROPERTY] = {}
+    node["required"] = node.get("required", []) + [UNKNOWN_PROPERTY]
+    return node
+
@@ -1,5 +1,5 @@
 import json
-from typing import Any, ClassVar, Dict, Iterable, List, Tuple
+from typing import Any, ClassVar, Dict, Iterable, List, Optional, Tuple
 
 import attr
 
@@ -13,7 +13,8 @@ class OpenAPIParameter(Parameter):
 
     example_field: ClassVar[str]
     examples_field: ClassVar[str]
-    nullable_field: ClassVar[str]
+    # `None` if the spec version has no special keyword for nullable values
+    nullable_field: ClassVar[Optional[str]]
     supported_jsonschema_keywords: ClassVar[Tuple[str, ...]]
 
     @property
@@ -203,11 +204,47 @@ class OpenAPI30Parameter(OpenAPIParameter):
     def is_header(self) -> bool:
         return self.location in ("header", "cookie")
 
+    @property
+    def allow_reserved(self) -> bool:
+        # Applies only to query parameters
+        return self.location == "query" and self.definition.get("allowReserved", False)
+
     def from_open_api_to_json_schema(self, open_api_schema: Dict[str, Any]) -> Dict[str, Any]:
         open_api_schema = get_parameter_schema(open_api_schema)
         return super().from_open_api_to_json_schema(open_api_schema)
 
 
+@attr.s(slots=True, eq=False)
+class OpenAPI31Parameter(OpenAPI30Parameter):
+    """Open API 3.1 parameter.
+
+    Schema objects are JSON Schema 2020-12 documents, therefore there is no `nullable` keyword - `type` arrays are used
+    instead.
+
+    https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.1.0.md#parameter-object
+    """
+
+    nullable_field = None
+    supported_jsonschema_keywords = OpenAPI30Parameter.supported_jsonschema_keywords + (
+        "$ref",
+        "$defs",
+        "const",
+        "prefixItems",
+        "contains",
+        "minContains",
+        "maxContains",
+        "unevaluatedItems",
+        "patternProperties",
+        "propertyNames",
+        "dependentRequired",
+        "dependentSchemas",
+        "unevaluatedProperties",
+        "if",
+        "then",
+        "else",
+    )
+
+
 @attr.s(slots=True, eq=False)
 class OpenAPIBody(OpenAPIParameter):
     media_type: str = attr.ib()
@@ -306,6 +343,14 @@ class OpenAPI30Body(OpenAPIBody, OpenAPI30Parameter):
         return self.required
 
 
+@attr.s(slots=True, eq=False)
+class OpenAPI31Body(OpenAPI30Body):
+    """Open API 3.1 body variant."""
+
+    nullable_field = None
+    supported_jsonschema_keywords = OpenAPI31Parameter.supported_jsonschema_keywords
+
+
 @attr.s(slots=True, eq=False)
 class OpenAPI20CompositeBody(OpenAPIBody, OpenAPI20Parameter):
     """A special container to abstract over multiple `formData` parameters."""
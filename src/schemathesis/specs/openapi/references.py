@@ -1,6 +1,7 @@
 from copy import deepcopy
 from functools import lru_cache
-from typing import Any, Callable, Dict, List, Tuple, Union, overload
+from typing import Any, Callable, Dict, List, Set, Tuple, Union, overload
+from urllib.parse import urldefrag
 from urllib.request import urlopen
 
 import jsonschema
@@ -8,7 +9,8 @@ import requests
 import yaml
 
 from ...utils import StringDatesYAMLLoader
-from .converter import to_json_schema_recursive
+from . import discriminator
+from .converter import to_draft_7_recursive, to_json_schema_recursive, wrap_reference_siblings
 
 # Reference resolving will stop after this depth
 RECURSION_DEPTH_LIMIT = 100
@@ -65,15 +67,19 @@ class InliningResolver(jsonschema.RefResolver):
         if isinstance(item, dict):
             ref = item.get("$ref")
             if ref is not None and isinstance(ref, str):
-                with self.resolving(ref) as resolved:
-                    return self.resolve_all(resolved, recursion_level + 1)
-            item = deepcopy(item)
+                return self.inline_reference(item, ref, recursion_level)
+            item = discriminator.add_constraints(deepcopy(item))
             for key, sub_item in item.items():
                 item[key] = self.resolve_all(sub_item, recursion_level)
         elif isinstance(item, list):
             item = [self.resolve_all(sub_item, recursion_level) for sub_item in deepcopy(item)]
         return item
 
+    def inline_reference(self, item: Dict[str, Any], ref: str, recursion_level: int) -> Any:
+        """Replace the given object with the resolved reference target. Keywords next to `$ref` are ignored."""
+        with self.resolving(ref) as resolved:
+            return self.resolve_all(resolved, recursion_level + 1)
+
     def resolve_in_scope(self, definition: Dict[str, Any], scope: str) -> Tuple[List[str], Dict[str, Any]]:
         scopes = [scope]
         # if there is `$ref` then we have a scope change that should be used during validation later to
@@ -85,11 +91,26 @@ class InliningResolver(jsonschema.RefResolver):
         return scopes, definition
 
 
+class Draft202012InliningResolver(InliningResolver):
+    """Inline references in JSON Schema 2020-12 documents, which Open API 3.1 Schema objects are.
+
+    Keywords next to `$ref` are applied together with it, therefore they are moved to `allOf` instead of being dropped.
+    """
+
+    def inline_reference(self, item: Dict[str, Any], ref: str, recursion_level: int) -> Any:
+        wrapped = wrap_reference_siblings(item)
+        if "$ref" in wrapped:
+            # Nothing to keep
+            return super().inline_reference(item, ref, recursion_level)
+        return self.resolve_all(wrapped, recursion_level)
+
+
 class ConvertingResolver(InliningResolver):
     """Convert resolved OpenAPI schemas to JSON Schema.
 
     When recursive schemas are validated we need to have resolved documents properly converted.
     This approach is the simplest one, since this logic isolated in a single place.
+    The resolved documents are used only for response validation.
     """
 
     def __init__(self, *args: Any, nullable_name: Any, **kwargs: Any) -> None:
@@ -98,5 +119,71 @@ class ConvertingResolver(InliningResolver):
 
     def resolve(self, ref: str) -> Tuple[str, Any]:
         url, document = super().resolve(ref)
-        document = to_json_schema_recursive(document, nullable_name=self.nullable_name)
+        document = to_json_schema_recursive(document, nullable_name=self.nullable_name, is_response_schema=True)
         return url, document
+
+
+class Draft7ConvertingResolver(ConvertingResolver):
+    """Additionally express JSON Schema 2020-12 keywords of Open API 3.1 schemas via Draft 7 ones.
+
+    `jsonschema` 3 doesn't support 2020-12, therefore these keywords would be silently ignored during validation.
+    """
+
+    def resolve(self, ref: str) -> Tuple[str, Any]:
+        url, document = super().resolve(ref)
+        # References in the resolved part are local to the whole document
+        root = self.resolve_from_url(urldefrag(url)[0])
+        return url, to_draft_7_recursive(document, root=root)
+
+
+def remove_optional_references(schema: Dict[str, Any]) -> None:
+    """Remove optional parts of the schema that are behind non-inlined references.
+
+    Recursive references are inlined only up to a certain depth, and the remaining ones lead to infinite recursion
+    during data generation. Cutting them off at positions where no data is required makes the generated data finite.
+    References in required positions are kept as is.
+    """
+    # Each definition comes with property names that are required by its `allOf` siblings
+    stack: List[Tuple[Any, Set[str]]] = [(schema, set())]
+    while stack:
+        definition, inherited_required = stack.pop()
+        if isinstance(definition, list):
+            stack.extend((item, set()) for item in definition)
+            continue
+        if not isinstance(definition, dict):
+            continue
+        required = get_required(definition) | inherited_required
+        all_of = definition.get("allOf")
+        if isinstance(all_of, list):
+            for branch in all_of:
+                if isinstance(branch, dict):
+                    required |= get_required(branch)
+        properties = definition.get("properties")
+        if isinstance(properties, dict):
+            for name, value in list(properties.items()):
+                if name not in required and is_reference(value):
+                    # The property will not be generated
+                    del properties[name]
+        items = definition.get("items")
+        if is_reference(items) and not definition.get("minItems"):
+            # Empty arrays are still valid
+            definition["maxItems"] = 0
+        additional_properties = definition.get("additionalProperties")
+        if is_reference(additional_properties) and not definition.get("minProperties"):
+            definition["additionalProperties"] = False
+        for key, value in definition.items():
+            if key == "allOf" and isinstance(value, list):
+                stack.extend((branch, required) for branch in value)
+            elif key not in ("enum", "const", "example", "examples", "default"):
+                stack.append((value, set()))
+
+
+def get_required(definition: Dict[str, Any]) -> Set[str]:
+    required = definition.get("required", [])
+    if isinstance(required, list):
+        return {name for name in required if isinstance(name, str)}
+    return set()
+
+
+def is_reference(item: Any) -> bool:
+    return isinstance(item, dict) and isinstance(item.get("$ref"), str)
@@ -1,12 +1,13 @@
 # pylint: disable=too-many-ancestors
 import itertools
+import json
 from collections import defaultdict
 from contextlib import ExitStack, contextmanager
 from copy import deepcopy
 from difflib import get_close_matches
 from json import JSONDecodeError
 from typing import Any, Callable, ClassVar, Dict, Generator, Iterable, List, Optional, Sequence, Tuple, Type, Union
-from urllib.parse import urlsplit
+from urllib.parse import urljoin, urlsplit
 
 import jsonschema
 import requests
@@ -15,6 +16,7 @@ from hypothesis.strategies import SearchStrategy
 from ...constants import DataGenerationMethod
 from ...exceptions import (
     InvalidSchema,
+    get_header_validation_error,
     get_missing_content_type_error,
     get_response_parsing_error,
     get_schema_validation_error,
@@ -25,9 +27,9 @@ from ...schemas import BaseSchema
 from ...stateful import APIStateMachine, Stateful, StatefulTest
 from ...types import FormData
 from ...utils import Err, GenericResponse, Ok, Result, get_response_payload, is_json_media_type
-from . import links, serialization
-from ._hypothesis import get_case_strategy
-from .converter import to_json_schema_recursive
+from . import discriminator, links, serialization
+from ._hypothesis import get_boundary_strategies, get_case_strategy
+from .converter import to_draft_7_recursive, to_json_schema_recursive
 from .examples import get_strategies_from_examples
 from .filters import (
     should_skip_by_operation_id,
@@ -42,9 +44,20 @@ from .parameters import (
     OpenAPI20Parameter,
     OpenAPI30Body,
     OpenAPI30Parameter,
+    OpenAPI31Body,
+    OpenAPI31Parameter,
+    OpenAPIBody,
     OpenAPIParameter,
+    get_parameter_schema,
+)
+from .references import (
+    RECURSION_DEPTH_LIMIT,
+    ConvertingResolver,
+    Draft7ConvertingResolver,
+    Draft202012InliningResolver,
+    InliningResolver,
+    remove_optional_references,
 )
-from .references import RECURSION_DEPTH_LIMIT, ConvertingResolver, InliningResolver
 from .security import BaseSecurityProcessor, OpenAPISecurityProcessor, SwaggerSecurityProcessor
 from .stateful import create_state_machine
 
@@ -53,12 +66,18 @@ SCHEMA_PARSING_ERRORS = (KeyError, AttributeError, jsonschema.exceptions.RefReso
 
 
 class BaseOpenAPISchema(BaseSchema):
-    nullable_name: str
+    nullable_name: Optional[str]
     links_field: str
     allowed_http_methods: Tuple[str, ...]
     security: BaseSecurityProcessor
     parameter_cls: Type[OpenAPIParameter]
     component_locations: ClassVar[Tuple[str, ...]] = ()
+    # Inlines references in API operation definitions
+    resolver_cls: ClassVar[Type[InliningResolver]] = InliningResolver
+    # Validates API responses against schemas defined in the API schema
+    response_validator_cls: ClassVar[Type] = jsonschema.Draft4Validator
+    # Resolves references during response validation and converts the resolved schemas to JSON Schema
+    response_resolver_cls: ClassVar[Type[ConvertingResolver]] = ConvertingResolver
     _operations_by_id: Dict[str, APIOperation]
 
     @property  # pragma: no mutate
@@ -99,6 +118,8 @@ class BaseOpenAPISchema(BaseSchema):
             raise InvalidSchema(SCHEMA_ERROR_MESSAGE) from exc
 
         context = HookContext()
+        # Recursive references are inlined `recursion_depth` times
+        recursion_level = RECURSION_DEPTH_LIMIT - self.recursion_depth
         for path, methods in paths.items():
             method = None
             try:
@@ -107,13 +128,13 @@ class BaseOpenAPISchema(BaseSchema):
                     continue
                 self.dispatch_hook("before_process_path", context, path, methods)
                 scope, raw_methods = self._resolve_methods(methods)
-                common_parameters = self.resolver.resolve_all(methods.get("parameters", []), RECURSION_DEPTH_LIMIT - 5)
+                common_parameters = self.resolver.resolve_all(methods.get("parameters", []), recursion_level)
                 for method, definition in raw_methods.items():
                     try:
                         # Setting a low recursion limit doesn't solve the problem with recursive references & inlining
                         # too much but decreases the number of cases when Schemathesis stuck on this step.
                         with self.resolver.in_scope(scope):
-                            resolved_definition = self.resolver.resolve_all(definition, RECURSION_DEPTH_LIMIT - 5)
+                            resolved_definition = self.resolver.resolve_all(definition, recursion_level)
                         # Only method definitions are parsed
                         if (
                             method not in self.allowed_http_methods
@@ -191,7 +212,7 @@ class BaseOpenAPISchema(BaseSchema):
     def resolver(self) -> InliningResolver:
         if not hasattr(self, "_resolver"):
             # pylint: disable=attribute-defined-outside-init
-            self._resolver = InliningResolver(self.location or "", self.raw_schema)
+            self._resolver = self.resolver_cls(self.location or "", self.raw_schema)
         return self._resolver
 
     def get_content_types(self, operation: APIOperation, response: GenericResponse) -> List[str]:
@@ -202,6 +223,9 @@ class BaseOpenAPISchema(BaseSchema):
         """Get examples from the API operation."""
         raise NotImplementedError
 
+    def get_strategies_from_boundaries(self, operation: APIOperation) -> List[SearchStrategy[Case]]:
+        return get_boundary_strategies(operation)
+
     def get_response_schema(self, definition: Dict[str, Any], scope: str) -> Tuple[List[str], Optional[Dict[str, Any]]]:
         """Extract response schema from `responses`."""
         raise NotImplementedError
@@ -213,14 +237,15 @@ class BaseOpenAPISchema(BaseSchema):
         return self._operations_by_id[operation_id]
 
     def _group_operations_by_id(self) -> Generator[Tuple[str, APIOperation], None, None]:
+        recursion_level = RECURSION_DEPTH_LIMIT - self.recursion_depth
         for path, methods in self.raw_schema["paths"].items():
             scope, raw_methods = self._resolve_methods(methods)
-            common_parameters = self.resolver.resolve_all(methods.get("parameters", []), RECURSION_DEPTH_LIMIT - 5)
+            common_parameters = self.resolver.resolve_all(methods.get("parameters", []), recursion_level)
             for method, definition in methods.items():
                 if method not in self.allowed_http_methods or "operationId" not in definition:
                     continue
                 with self.resolver.in_scope(scope):
-                    resolved_definition = self.resolver.resolve_all(definition, RECURSION_DEPTH_LIMIT - 5)
+                    resolved_definition = self.resolver.resolve_all(definition, recursion_level)
                 parameters = self.collect_parameters(
                     itertools.chain(resolved_definition.get("parameters", ()), common_parameters), resolved_definition
                 )
@@ -238,7 +263,8 @@ class BaseOpenAPISchema(BaseSchema):
         resolved_definition = self.resolver.resolve_all(data)
         parent_ref, _ = reference.rsplit("/", maxsplit=1)
         _, methods = self.resolver.resolve(parent_ref)
-        common_parameters = self.resolver.resolve_all(methods.get("parameters", []), RECURSION_DEPTH_LIMIT - 5)
+        recursion_level = RECURSION_DEPTH_LIMIT - self.recursion_depth
+        common_parameters = self.resolver.resolve_all(methods.get("parameters", []), recursion_level)
         parameters = self.collect_parameters(
             itertools.chain(resolved_definition.get("parameters", ()), common_parameters), resolved_definition
         )
@@ -286,6 +312,54 @@ class BaseOpenAPISchema(BaseSchema):
             return None
         return definitions.get("headers")
 
+    def get_header_schema(self, definition: Dict[str, Any]) -> Dict[str, Any]:
+        """Extract the schema of a response header."""
+        raise NotImplementedError
+
+    def deserialize_header(self, definition: Dict[str, Any], value: str) -> Any:
+        """Convert a response header value to a form suitable for schema validation."""
+        raise NotImplementedError
+
+    def is_header_required(self, definition: Dict[str, Any]) -> bool:
+        """Whether the response header must be present."""
+        raise NotImplementedError
+
+    def validate_header(self, operation: APIOperation, name: str, definition: Dict[str, Any], value: str) -> None:
+        scope = operation.definition.scope
+        # The header value is converted based on its schema, therefore references are resolved before that
+        with self.resolver.in_scope(scope):
+            definition = self.resolver.resolve_all(definition, RECURSION_DEPTH_LIMIT - self.recursion_depth)
+        schema = self.get_header_schema(definition)
+        schema = to_json_schema_recursive(schema, self.nullable_name, is_response_schema=True)
+        data = self.deserialize_header(definition, value)
+        # Recursive references are not fully inlined
+        resolver = self.response_resolver_cls(self.location or "", self.raw_schema, nullable_name=self.nullable_name)
+        with resolver.in_scope(scope):
+            try:
+                jsonschema.validate(
+                    data,
+                    schema,
+                    cls=self.response_validator_cls,
+                    resolver=resolver,
+                    format_checker=get_format_checker(),
+                )
+            except jsonschema.ValidationError as exc:
+                exc_class = get_header_validation_error(name, exc)
+                raise exc_class(
+                    f"The received `{name}` header does not conform to the defined schema!\n\nDetails: \n\n{exc}"
+                ) from exc
+
+    def get_credentials_locations(self, operation: APIOperation) -> List[Tuple[str, str]]:
+        """Locations and names of credentials, required by the given API operation."""
+        return self.security.get_credentials_locations(self.raw_schema, operation, self.resolver)
+
+    def get_oauth2_token_url(self, grant_type: str) -> Optional[str]:
+        token_url = self.security.get_oauth2_token_url(self.raw_schema, self.resolver, grant_type)
+        if token_url is not None:
+            # Relative URLs are resolved against the base URL of the API
+            return urljoin(self.get_base_url() + "/", token_url)
+        return None
+
     def as_state_machine(self) -> Type[APIStateMachine]:
         return create_state_machine(self)
 
@@ -397,14 +471,16 @@ class BaseOpenAPISchema(BaseSchema):
             raise exc_class(
                 f"The received response is not valid JSON:\n\n    {payload}\n\nException: \n\n    {exc}"
             ) from exc
-        resolver = ConvertingResolver(self.location or "", self.raw_schema, nullable_name=self.nullable_name)
+        resolver = self.response_resolver_cls(self.location or "", self.raw_schema, nullable_name=self.nullable_name)
         with in_scopes(resolver, scopes):
             try:
-                jsonschema.validate(data, schema, cls=jsonschema.Draft4Validator, resolver=resolver)
+                jsonschema.validate(data, schema, cls=self.response_validator_cls, resolver=resolver)
             except jsonschema.ValidationError as exc:
-                exc_class = get_schema_validation_error(exc)
+                # Point to the branch selected by the discriminator instead of an arbitrary `oneOf` / `anyOf` branch
+                error = discriminator.narrow_error(exc)
+                exc_class = get_schema_validation_error(error)
                 raise exc_class(
-                    f"The received response does not conform to the defined schema!\n\nDetails: \n\n{exc}"
+                    f"The received response does not conform to the defined schema!\n\nDetails: \n\n{error}"
                 ) from exc
         return None  # explicitly return None for mypy
 
@@ -418,6 +494,8 @@ class BaseOpenAPISchema(BaseSchema):
         for key in self.component_locations:
             if key in self.raw_schema:
                 schema[key] = deepcopy(self.raw_schema[key])
+        # Recursive references can't be fully inlined, their optional parts are cut off to make the data finite
+        remove_optional_references(schema)
         return schema
 
 
@@ -435,8 +513,23 @@ def in_scopes(resolver: jsonschema.RefResolver, scopes: List[str]) -> Generator[
         yield
 
 
+def get_format_checker() -> jsonschema.FormatChecker:
+    format_checker = jsonschema.FormatChecker()
+    if "uri" not in format_checker.checkers:
+        # `jsonschema` checks the `uri` format only if `rfc3987` is installed
+        format_checker.checks("uri")(is_uri)
+    return format_checker
+
+
+def is_uri(value: Any) -> bool:
+    if not isinstance(value, str):
+        return True
+    return bool(urlsplit(value).scheme) and not any(char.isspace() for char in value)
+
+
 OPENAPI_20_DEFAULT_BODY_MEDIA_TYPE = "application/json"
 OPENAPI_20_DEFAULT_FORM_MEDIA_TYPE = "multipart/form-data"
+COLLECTION_FORMAT_DELIMITERS = {"csv": ",", "ssv": " ", "tsv": "\t", "pipes": "|"}
 
 
 class SwaggerV20(BaseOpenAPISchema):
@@ -509,7 +602,7 @@ class SwaggerV20(BaseOpenAPISchema):
             return scopes, None
         # Extra conversion to JSON Schema is needed here if there was one $ref in the input
         # because it is not converted
-        return scopes, to_json_schema_recursive(schema, self.nullable_name)
+        return scopes, to_json_schema_recursive(schema, self.nullable_name, is_response_schema=True)
 
     def get_content_types(self, operation: APIOperation, response: GenericResponse) -> List[str]:
         produces = operation.definition.raw.get("produces", None)
@@ -520,6 +613,18 @@ class SwaggerV20(BaseOpenAPISchema):
     def _get_parameter_serializer(self, definitions: List[Dict[str, Any]]) -> Optional[Callable]:
         return serialization.serialize_swagger2_parameters(definitions)
 
+    def get_header_schema(self, definition: Dict[str, Any]) -> Dict[str, Any]:
+        # Header objects in Open API 2.0 are schema-like themselves
+        return {key: value for key, value in definition.items() if key not in ("description", "collectionFormat")}
+
+    def deserialize_header(self, definition: Dict[str, Any], value: str) -> Any:
+        delimiter = COLLECTION_FORMAT_DELIMITERS.get(definition.get("collectionFormat", "csv"), ",")
+        return serialization.deserialize_header(value, definition, delimiter=delimiter)
+
+    def is_header_required(self, definition: Dict[str, Any]) -> bool:
+        # There is no way to mark response headers as optional in Open API 2.0
+        return True
+
     def prepare_multipart(
         self, form_data: FormData, operation: APIOperation
     ) -> Tuple[Optional[List], Optional[Dict[str, Any]]]:
@@ -556,6 +661,9 @@ class SwaggerV20(BaseOpenAPISchema):
         # `None` is the default value for `files` and `data` arguments in `requests.request`
         return files or None, data or None
 
+    def prepare_urlencoded(self, form_data: FormData, operation: APIOperation) -> FormData:
+        return form_data
+
     def get_request_payload_content_types(self, operation: APIOperation) -> List[str]:
         return self._get_consumes_for_operation(operation.definition.resolved)
 
@@ -580,6 +688,7 @@ class OpenApi30(SwaggerV20):  # pylint: disable=too-many-ancestors
     allowed_http_methods = SwaggerV20.allowed_http_methods + ("trace",)
     security = OpenAPISecurityProcessor()
     parameter_cls = OpenAPI30Parameter
+    body_cls: ClassVar[Type[OpenAPIBody]] = OpenAPI30Body
     component_locations = ("components",)
     links_field = "links"
 
@@ -587,6 +696,13 @@ class OpenApi30(SwaggerV20):  # pylint: disable=too-many-ancestors
     def spec_version(self) -> str:
         return self.raw_schema["openapi"]
 
+    def get_headers(self, operation: APIOperation, response: GenericResponse) -> Optional[Dict[str, Dict[str, Any]]]:
+        headers = super().get_headers(operation, response)
+        if headers is None:
+            return None
+        # Response headers named `Content-Type` are ignored by the Open API 3 specification
+        return {name: definition for name, definition in headers.items() if name.lower() != "content-type"}
+
     @property
     def verbose_name(self) -> str:
         return f"Open API {self.spec_version}"
@@ -604,11 +720,11 @@ class OpenApi30(SwaggerV20):  # pylint: disable=too-many-ancestors
         self, parameters: Iterable[Dict[str, Any]], definition: Dict[str, Any]
     ) -> List[OpenAPIParameter]:
         # Open API 3.0 has the `requestBody` keyword, which may contain multiple different payload variants.
-        collected: List[OpenAPIParameter] = [OpenAPI30Parameter(definition=parameter) for parameter in parameters]
+        collected: List[OpenAPIParameter] = [self.parameter_cls(definition=parameter) for parameter in parameters]
         if "requestBody" in definition:
             required = definition["requestBody"].get("required", False)
             for media_type, content in definition["requestBody"]["content"].items():
-                collected.append(OpenAPI30Body(content, media_type=media_type, required=required))
+                collected.append(self.body_cls(content, media_type=media_type, required=required))
         return collected
 
     def get_response_schema(self, definition: Dict[str, Any], scope: str) -> Tuple[List[str], Optional[Dict[str, Any]]]:
@@ -618,7 +734,7 @@ class OpenApi30(SwaggerV20):  # pylint: disable=too-many-ancestors
         if option:
             # Extra conversion to JSON Schema is needed here if there was one $ref in the input
             # because it is not converted
-            return scopes, to_json_schema_recursive(option["schema"], self.nullable_name)
+            return scopes, to_json_schema_recursive(option["schema"], self.nullable_name, is_response_schema=True)
         return scopes, None
 
     def get_strategies_from_examples(self, operation: APIOperation) -> List[SearchStrategy[Case]]:
@@ -634,6 +750,26 @@ class OpenApi30(SwaggerV20):  # pylint: disable=too-many-ancestors
     def _get_parameter_serializer(self, definitions: List[Dict[str, Any]]) -> Optional[Callable]:
         return serialization.serialize_openapi3_parameters(definitions)
 
+    def get_header_schema(self, definition: Dict[str, Any]) -> Dict[str, Any]:
+        if "schema" not in definition and "content" not in definition:
+            return {}
+        return get_parameter_schema(definition)
+
+    def deserialize_header(self, definition: Dict[str, Any], value: str) -> Any:
+        if "content" in definition:
+            media_type = next(iter(definition["content"]), None)
+            if media_type is not None and is_json_media_type(media_type):
+                try:
+                    return json.loads(value)
+                except JSONDecodeError:
+                    pass
+            return value
+        explode = definition.get("explode", False)
+        return serialization.deserialize_header(value, definition.get("schema", {}), explode=explode)
+
+    def is_header_required(self, definition: Dict[str, Any]) -> bool:
+        return definition.get("required", False)
+
     def get_request_payload_content_types(self, operation: APIOperation) -> List[str]:
         return list(operation.definition.resolved["requestBody"]["content"].keys())
 
@@ -651,9 +787,12 @@ class OpenApi30(SwaggerV20):  # pylint: disable=too-many-ancestors
         # Open API 3.0 requires media types to be present. We can get here only if the schema defines
         # the "multipart/form-data" media type
         schema = content["multipart/form-data"]["schema"]
+        encoding = content["multipart/form-data"].get("encoding", {})
         for name, property_schema in schema.get("properties", {}).items():
             if name in form_data:
-                if isinstance(form_data[name], list):
+                if name in encoding:
+                    files.extend(_make_encoded_parts(name, form_data[name], property_schema, encoding[name]))
+                elif isinstance(form_data[name], list):
                     files.extend([(name, item) for item in form_data[name]])
                 elif property_schema.get("format") in ("binary", "base64"):
                     files.append((name, form_data[name]))
@@ -661,3 +800,58 @@ class OpenApi30(SwaggerV20):  # pylint: disable=too-many-ancestors
                     files.append((name, (None, form_data[name])))
         # `None` is the default value for `files` and `data` arguments in `requests.request`
         return files or None, None
+
+    def prepare_urlencoded(self, form_data: FormData, operation: APIOperation) -> FormData:
+        content = operation.definition.resolved["requestBody"]["content"]
+        definition = content.get("application/x-www-form-urlencoded", {})
+        encoding = definition.get("encoding")
+        if not encoding:
+            return form_data
+        return serialization.serialize_urlencoded_form(form_data, definition.get("schema", {}), encoding)
+
+
+def _make_encoded_parts(
+    name: str, value: Any, schema: Dict[str, Any], encoding: Dict[str, Any]
+) -> List[Tuple[str, Tuple[Optional[str], Any, str, Dict[str, str]]]]:
+    """Create `multipart/form-data` parts with content types & headers defined in the `encoding` object."""
+    headers = serialization.get_encoding_headers(encoding)
+    if isinstance(value, list):
+        values, schema = value, schema.get("items", {})
+    else:
+        values = [value]
+    content_type = serialization.get_encoding_content_type(encoding, schema)
+    # Files get their field name as the file name, the same way as `requests` does by default
+    filename = name if schema.get("format") in ("binary", "base64") else None
+    return [
+        (name, (filename, serialization.serialize_part(item, content_type), content_type, headers)) for item in values
+    ]
+
+
+class OpenApi31(OpenApi30):  # pylint: disable=too-many-ancestors
+    # Schema objects are JSON Schema 2020-12 documents, where `null` is a regular type
+    nullable_name = None
+    parameter_cls = OpenAPI31Parameter
+    body_cls = OpenAPI31Body
+    resolver_cls = Draft202012InliningResolver
+    # `jsonschema` 3 doesn't support 2020-12, therefore response schemas are converted to Draft 7 before validation
+    response_validator_cls = jsonschema.Draft7Validator
+    response_resolver_cls = Draft7ConvertingResolver
+
+    def get_all_operations(self) -> Generator[Result[APIOperation, InvalidSchema], None, None]:
+        if "paths" not in self.raw_schema:
+            # Open API 3.1 documents may contain only `webhooks` or `components`
+            return
+        yield from super().get_all_operations()
+
+    def prepare_schema(self, schema: Any) -> Any:
+        # `hypothesis-jsonschema` generates data according to Draft 4 - 7 semantics
+        return to_draft_7_recursive(super().prepare_schema(schema))
+
+    def get_response_schema(self, definition: Dict[str, Any], scope: str) -> Tuple[List[str], Optional[Dict[str, Any]]]:
+        scopes, schema = super().get_response_schema(definition, scope)
+        if schema is None:
+            return scopes, None
+        return scopes, to_draft_7_recursive(schema, root=self.raw_schema)
+
+    def get_header_schema(self, definition: Dict[str, Any]) -> Dict[str, Any]:
+        return to_draft_7_recursive(super().get_header_schema(definition), root=self.raw_schema)
@@ -1,10 +1,11 @@
 """Processing of ``securityDefinitions`` or ``securitySchemes`` keywords."""
-from typing import Any, ClassVar, Dict, Generator, List, Tuple, Type
+from typing import Any, Callable, ClassVar, Dict, Generator, List, Optional, Tuple, Type
 
 import attr
 from jsonschema import RefResolver
 
 from ...models import APIOperation
+from ...parameters import ParameterSet
 from .parameters import OpenAPI20Parameter, OpenAPI30Parameter, OpenAPIParameter
 
 
@@ -13,27 +14,91 @@ class BaseSecurityProcessor:
     api_key_locations: Tuple[str, ...] = ("header", "query")
     http_security_name = "basic"
     parameter_cls: ClassVar[Type[OpenAPIParameter]] = OpenAPI20Parameter
+    # OAuth 2 grant types and names of the corresponding flows
+    oauth2_flows: ClassVar[Dict[str, str]] = {"client_credentials": "application", "password": "password"}
 
     def process_definitions(self, schema: Dict[str, Any], operation: APIOperation, resolver: RefResolver) -> None:
-        """Add relevant security parameters to data generation."""
-        for definition in self._get_active_definitions(schema, operation, resolver):
-            if definition["type"] == "apiKey":
-                self.process_api_key_security_definition(definition, operation)
-            self.process_http_security_definition(definition, operation)
+        """Add relevant security parameters to data generation.
+
+        Security requirements are alternatives - if there are several of them, then each generated test case contains
+        parameters for exactly one requirement.
+        """
+        alternatives = self._get_active_alternatives(schema, operation, resolver)
+        if len(alternatives) == 1:
+            for definition in alternatives[0]:
+                self._add_parameter(definition, operation.add_parameter)
+        else:
+            for alternative in alternatives:
+                parameters: ParameterSet[OpenAPIParameter] = ParameterSet()
+                for definition in alternative:
+                    self._add_parameter(definition, parameters.add)
+                operation.security.append(parameters)
+
+    def _add_parameter(self, definition: Dict[str, Any], add: Callable[[OpenAPIParameter], None]) -> None:
+        if definition["type"] == "apiKey":
+            add(self.parameter_cls(self._make_api_key_parameter(definition)))
+        elif definition["type"] == self.http_security_name:
+            add(self.parameter_cls(self._make_http_auth_parameter(definition)))
+
+    def _get_active_alternatives(
+        self, schema: Dict[str, Any], operation: APIOperation, resolver: RefResolver
+    ) -> List[List[Dict[str, Any]]]:
+        """Get security definitions for each alternative security requirement of the given API operation."""
+        definitions = self.get_security_definitions(schema, resolver)
+        return [
+            [definition for name, definition in definitions.items() if name in requirement]
+            for requirement in get_security_requirements(schema, operation)
+        ]
 
     def _get_active_definitions(
         self, schema: Dict[str, Any], operation: APIOperation, resolver: RefResolver
     ) -> Generator[Dict[str, Any], None, None]:
-        """Get only security definitions active for the given API operation."""
+        """Get security definitions active for the given API operation in any of its security requirements."""
         definitions = self.get_security_definitions(schema, resolver)
         requirements = get_security_requirements(schema, operation)
         for name, definition in definitions.items():
-            if name in requirements:
+            if any(name in requirement for requirement in requirements):
                 yield definition
 
     def get_security_definitions(self, schema: Dict[str, Any], resolver: RefResolver) -> Dict[str, Any]:
         return schema.get("securityDefinitions", {})
 
+    def get_credentials_locations(
+        self, schema: Dict[str, Any], operation: APIOperation, resolver: RefResolver
+    ) -> List[Tuple[str, str]]:
+        """Locations and names of credentials, required by the given API operation.
+
+        For example, ``("header", "Authorization")`` for HTTP authentication. Nothing is required if any of the
+        security requirements is empty.
+        """
+        if any(not requirement for requirement in get_security_requirements(schema, operation)):
+            return []
+        locations = []
+        for definition in self._get_active_definitions(schema, operation, resolver):
+            if definition["type"] == "apiKey":
+                location = (definition["in"], definition["name"])
+            else:
+                # HTTP authentication, OAuth 2 and OpenID Connect pass credentials in the `Authorization` header
+                location = ("header", "Authorization")
+            if location not in locations:
+                locations.append(location)
+        return locations
+
+    def get_oauth2_token_url(self, schema: Dict[str, Any], resolver: RefResolver, grant_type: str) -> Optional[str]:
+        """Token URL of the first OAuth 2 security definition that supports the given grant type."""
+        flow = self.oauth2_flows[grant_type]
+        for definition in self.get_security_definitions(schema, resolver).values():
+            if definition.get("type") == "oauth2":
+                token_url = self._get_token_url(definition, flow)
+                if token_url is not None:
+                    return token_url
+        return None
+
+    def _get_token_url(self, definition: Dict[str, Any], flow: str) -> Optional[str]:
+        if definition.get("flow") == flow:
+            return definition.get("tokenUrl")
+        return None
+
     def get_security_definitions_as_parameters(
         self, schema: Dict[str, Any], operation: APIOperation, resolver: RefResolver, location: str
     ) -> List[Dict[str, Any]]:
@@ -48,15 +113,6 @@ class BaseSecurityProcessor:
             if self._is_match(definition, location)
         ]
 
-    def process_api_key_security_definition(self, definition: Dict[str, Any], operation: APIOperation) -> None:
-        parameter = self.parameter_cls(self._make_api_key_parameter(definition))
-        operation.add_parameter(parameter)
-
-    def process_http_security_definition(self, definition: Dict[str, Any], operation: APIOperation) -> None:
-        if definition["type"] == self.http_security_name:
-            parameter = self.parameter_cls(self._make_http_auth_parameter(definition))
-            operation.add_parameter(parameter)
-
     def _is_match(self, definition: Dict[str, Any], location: str) -> bool:
         return (definition["type"] == "apiKey" and location in self.api_key_locations) or (
             definition["type"] == self.http_security_name and location == "header"
@@ -98,6 +154,7 @@ class OpenAPISecurityProcessor(BaseSecurityProcessor):
     api_key_locations = ("header", "cookie", "query")
     http_security_name = "http"
     parameter_cls: ClassVar[Type[OpenAPIParameter]] = OpenAPI30Parameter
+    oauth2_flows: ClassVar[Dict[str, str]] = {"client_credentials": "clientCredentials", "password": "password"}
 
     def get_security_definitions(self, schema: Dict[str, Any], resolver: RefResolver) -> Dict[str, Any]:
         """In Open API 3 security definitions are located in ``components`` and may have references inside."""
@@ -107,6 +164,9 @@ class OpenAPISecurityProcessor(BaseSecurityProcessor):
             return resolver.resolve(security_schemes["$ref"])[1]
         return security_schemes
 
+    def _get_token_url(self, definition: Dict[str, Any], flow: str) -> Optional[str]:
+        return definition.get("flows", {}).get(flow, {}).get("tokenUrl")
+
     def _make_http_auth_parameter(self, definition: Dict[str, Any]) -> Dict[str, Any]:
         schema = make_auth_header_schema(definition)
         return make_auth_header(schema=schema)
@@ -115,8 +175,12 @@ class OpenAPISecurityProcessor(BaseSecurityProcessor):
         return make_api_key_schema(definition, schema={"type": "string"})
 
 
-def get_security_requirements(schema: Dict[str, Any], operation: APIOperation) -> List[str]:
-    """Get applied security requirements for the given API operation."""
+def get_security_requirements(schema: Dict[str, Any], operation: APIOperation) -> List[List[str]]:
+    """Get applied security requirements for the given API operation.
+
+    Each requirement is a list of security scheme names that should be satisfied together, and requirements themselves
+    are alternatives. An empty requirement means that the API operation may be called without authentication.
+    """
     # https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#operation-object
     # > This definition overrides any declared top-level security.
     # > To remove a top-level security declaration, an empty array can be used.
@@ -126,4 +190,4 @@ def get_security_requirements(schema: Dict[str, Any], operation: APIOperation) -
         requirements = local_requirements
     else:
         requirements = global_requirements
-    return [key for requirement in requirements for key in requirement]
+    return [list(requirement) for requirement in requirements]
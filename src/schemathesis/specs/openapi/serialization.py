@@ -1,11 +1,18 @@
 import functools
 import json
 from typing import Any, Callable, Dict, Generator, List, Optional
+from urllib.parse import urlencode
+
+from ... import serializers
+from ...exceptions import SerializationNotPossible
+from ...serializers import ParameterSerializerContext
 
 Generated = Dict[str, Any]
 Definition = Dict[str, Any]
 DefinitionList = List[Definition]
 MapFunction = Callable[[Generated], Generated]
+# https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.3.md#fixed-fields-10
+DEFAULT_STYLES = {"path": "simple", "query": "form", "header": "simple", "cookie": "form"}
 
 
 def compose(*functions: Callable) -> Callable:
@@ -39,12 +46,13 @@ def _serialize_openapi3(definitions: DefinitionList) -> Generator[Optional[Calla
             # https://swagger.io/docs/specification/describing-parameters/#schema-vs-content
             options = iter(definition["content"].keys())
             media_type = next(options, None)
-            if media_type == "application/json":
-                yield to_json(name)
+            if media_type is not None:
+                yield to_media_type(name, media_type=media_type)
         else:
             # Simple serialization
-            style = definition.get("style")
-            explode = definition.get("explode")
+            style = definition.get("style", DEFAULT_STYLES.get(definition["in"]))
+            # When style is "form", the default value is `true`. For all other styles, the default value is `false`
+            explode = definition.get("explode", style == "form")
             type_ = definition.get("schema", {}).get("type")
             if definition["in"] == "path":
                 yield from _serialize_path_openapi3(name, type_, style, explode)
@@ -53,7 +61,9 @@ def _serialize_openapi3(definitions: DefinitionList) -> Generator[Optional[Calla
             elif definition["in"] == "header":
                 yield from _serialize_header_openapi3(name, type_, explode)
             elif definition["in"] == "cookie":
-                yield from _serialize_cookie_openapi3(name, type_, explode)
+                # Array & object cookies are dropped with `explode=true`, therefore they are serialized as with
+                # `explode=false` by default
+                yield from _serialize_cookie_openapi3(name, type_, definition.get("explode", False))
 
 
 def _serialize_path_openapi3(
@@ -95,6 +105,11 @@ def _serialize_query_openapi3(
                 yield comma_delimited_object(name)
             if explode is True:
                 yield extracted_object(name)
+        if explode is False:
+            if style == "pipeDelimited":
+                yield flat_delimited_object(name, delimiter="|")
+            if style == "spaceDelimited":
+                yield flat_delimited_object(name, delimiter=" ")
     elif type_ == "array" and explode is False:
         if style == "pipeDelimited":
             yield delimited(name, delimiter="|")
@@ -119,9 +134,7 @@ def _serialize_header_openapi3(
             yield delimited_object(name)
 
 
-def _serialize_cookie_openapi3(
-    name: str, type_: str, explode: Optional[bool]
-) -> Generator[Optional[Callable], None, None]:
+def _serialize_cookie_openapi3(name: str, type_: str, explode: bool) -> Generator[Optional[Callable], None, None]:
     # Cookies should be coerced to a string so we can check it for validity later
     yield to_string(name)
     # Cookie parameters always use the "form" style
@@ -131,11 +144,10 @@ def _serialize_cookie_openapi3(
         # We use the same behavior as in the examples - https://swagger.io/docs/specification/serialization/
         # The item is removed
         yield nothing(name)
-    if explode is False:
-        if type_ == "array":
-            yield delimited(name, delimiter=",")
-        if type_ == "object":
-            yield comma_delimited_object(name)
+    elif type_ == "array":
+        yield delimited(name, delimiter=",")
+    elif type_ == "object":
+        yield comma_delimited_object(name)
 
 
 def _serialize_swagger2(definitions: DefinitionList) -> Generator[Optional[Callable], None, None]:
@@ -179,9 +191,25 @@ def make_delimited(data: Optional[Dict[str, Any]], delimiter: str = ",") -> str:
 
 
 @conversion
-def to_json(item: Generated, name: str) -> None:
-    """Serialize an item to JSON."""
-    item[name] = json.dumps(item[name])
+def to_media_type(item: Generated, name: str, media_type: str) -> None:
+    """Serialize an item with the serializer registered for the given media type."""
+    serializer = serializers.get(media_type)
+    if serializer is None:
+        raise SerializationNotPossible.from_media_types(media_type)
+    kwargs = serializer().as_requests(ParameterSerializerContext(name=name), item[name])
+    if "json" in kwargs:
+        item[name] = json.dumps(kwargs["json"])
+        return
+    data = kwargs.get("data")
+    if isinstance(data, bytes):
+        item[name] = data.decode("utf8", errors="replace")
+    elif isinstance(data, str):
+        item[name] = data
+    elif isinstance(data, (dict, list)):
+        # E.g. `application/x-www-form-urlencoded`
+        item[name] = urlencode(data, doseq=True)
+    else:
+        raise SerializationNotPossible(f"Serializer for `{media_type}` can't be used for parameters")
 
 
 @conversion
@@ -194,19 +222,40 @@ def deep_object(item: Generated, name: str) -> None:
     """Serialize an object with `deepObject` style.
 
     id={"role": "admin", "firstName": "Alex"} => id[role]=admin&id[firstName]=Alex
+    id={"owner": {"name": "Alex"}} => id[owner][name]=Alex
     """
     generated = item.pop(name)
     if generated:
-        item.update({f"{name}[{key}]": value for key, value in generated.items()})
+        item.update(_flatten_deep_object(name, generated))
     else:
         item[name] = ""
 
 
+def _flatten_deep_object(prefix: str, value: Dict[str, Any]) -> Generated:
+    flattened = {}
+    for key, sub_value in value.items():
+        sub_prefix = f"{prefix}[{key}]"
+        if isinstance(sub_value, dict) and sub_value:
+            flattened.update(_flatten_deep_object(sub_prefix, sub_value))
+        else:
+            flattened[sub_prefix] = sub_value
+    return flattened
+
+
 @conversion
 def comma_delimited_object(item: Generated, name: str) -> None:
     item[name] = ",".join(map(str, sum((item[name] or {}).items(), ())))
 
 
+@conversion
+def flat_delimited_object(item: Generated, name: str, delimiter: str) -> None:
+    """Serialize an object as a flat list of keys and values.
+
+    id={"r": 100, "g": 200} => "r|100|g|200"
+    """
+    item[name] = delimiter.join(map(str, sum((item[name] or {}).items(), ())))
+
+
 @conversion
 def delimited_object(item: Generated, name: str) -> None:
     item[name] = make_delimited(item[name])
@@ -349,3 +398,132 @@ def nothing(item: Generated, name: str) -> None:
 def to_string(item: Generated, name: str) -> None:
     """Convert the value to a string."""
     item[name] = str(item[name])
+
+
+# https://github.com/OAI/OpenAPI-Specification/blob/master/versions/3.0.3.md#encoding-object
+def get_encoding_content_type(encoding: Definition, schema: Definition) -> str:
+    """Get the content type of a single property in a `multipart/form-data` payload."""
+    content_type = encoding.get("contentType")
+    if content_type is not None:
+        # It could be a comma-separated list of media types, and any of them is acceptable
+        return content_type.split(",")[0].strip()
+    # The default value depends on the property type, for arrays it is based on the inner type
+    if schema.get("type") == "array":
+        schema = schema.get("items", {})
+    type_ = schema.get("type")
+    if type_ == "object":
+        return "application/json"
+    if type_ == "string" and schema.get("format") in ("binary", "base64"):
+        return "application/octet-stream"
+    if type_ in ("string", "integer", "number", "boolean"):
+        return "text/plain"
+    return "application/octet-stream"
+
+
+def get_encoding_headers(encoding: Definition) -> Dict[str, str]:
+    """Get additional headers of a single property in a `multipart/form-data` payload.
+
+    Their values are taken from examples or defaults in the corresponding header definitions.
+    """
+    headers = {}
+    for name, definition in encoding.get("headers", {}).items():
+        # `Content-Type` is described separately via `contentType` and SHALL be ignored here
+        if name.lower() == "content-type":
+            continue
+        value = _get_header_example(definition)
+        if value is not None:
+            headers[name] = str(value)
+    return headers
+
+
+def _get_header_example(definition: Definition) -> Any:
+    if "example" in definition:
+        return definition["example"]
+    for example in definition.get("examples", {}).values():
+        if "value" in example:
+            return example["value"]
+    schema = definition.get("schema", {})
+    for keyword in ("example", "default"):
+        if keyword in schema:
+            return schema[keyword]
+    if schema.get("enum"):
+        return schema["enum"][0]
+    return None
+
+
+def serialize_part(value: Any, content_type: str) -> Any:
+    """Serialize a single property of a `multipart/form-data` payload with the given content type."""
+    if isinstance(value, bytes) or serializers.get(content_type) is None:
+        # E.g. files with `image/png` content type are sent as is
+        return value
+    return to_media_type("value", media_type=content_type)({"value": value})["value"]
+
+
+def serialize_urlencoded_form(form_data: Generated, schema: Definition, encoding: Dict[str, Definition]) -> Generated:
+    """Apply the `encoding` object to an `application/x-www-form-urlencoded` payload.
+
+    Properties are serialized the same way as query parameters.
+    """
+    properties = schema.get("properties", {})
+    conversions: List[Optional[Callable]] = []
+    for name, definition in encoding.items():
+        if "contentType" in definition and not {"style", "explode", "allowReserved"} & set(definition):
+            conversions.append(to_media_type(name, media_type=get_encoding_content_type(definition, {})))
+        else:
+            style = definition.get("style", "form")
+            explode = definition.get("explode", style == "form")
+            type_ = properties.get(name, {}).get("type")
+            conversions.extend(_serialize_query_openapi3(name, type_, style, explode))
+    # Conversions modify data in-place, but the original payload should stay intact
+    return compose(*[conv for conv in conversions if conv is not None])(dict(form_data))
+
+
+def deserialize_header(value: str, schema: Definition, explode: bool = False, delimiter: str = ",") -> Any:
+    """Convert a response header value to a Python object according to its schema.
+
+    Header values use the "simple" style:
+
+        "5" => 5
+        "3,4,5" => [3, 4, 5]
+        "role,admin,firstName,Alex" => {"role": "admin", "firstName": "Alex"}
+
+    Values that can't be converted are returned as is, so they are reported during schema validation.
+    """
+    type_ = schema.get("type")
+    if type_ == "array":
+        items = schema.get("items", {})
+        return [_deserialize_primitive(item.strip(), items) for item in value.split(delimiter)] if value else []
+    if type_ == "object":
+        properties = schema.get("properties", {})
+        if not value:
+            return {}
+        parts = [part.strip() for part in value.split(delimiter)]
+        if explode:
+            if not all("=" in part for part in parts):
+                return value
+            pairs = [part.split("=", 1) for part in parts]
+        else:
+            if len(parts) % 2:
+                return value
+            pairs = list(zip(parts[::2], parts[1::2]))
+        return {key: _deserialize_primitive(item, properties.get(key, {})) for key, item in pairs}
+    return _deserialize_primitive(value, schema)
+
+
+def _deserialize_primitive(value: str, schema: Definition) -> Any:
+    type_ = schema.get("type")
+    # Open API 3.1 allows multiple types
+    types = type_ if isinstance(type_, list) else [type_]
+    if "integer" in types or "number" in types:
+        try:
+            return int(value)
+        except ValueError:
+            pass
+    if "number" in types:
+        try:
+            return float(value)
+        except ValueError:
+            pass
+    if "boolean" in types and value in ("true", "false"):
+        return value == "true"
+    return value
@@ -1,6 +1,7 @@
 import enum
 import json
 from typing import TYPE_CHECKING, Any, Callable, ClassVar, Dict, Generator, List, Optional, Tuple
+from weakref import WeakKeyDictionary
 
 import attr
 import hypothesis
@@ -10,10 +11,12 @@ from starlette.applications import Starlette
 
 from .constants import DataGenerationMethod
 from .exceptions import InvalidSchema
-from .models import APIOperation, Case, CheckFunction
+from .models import APIOperation, Case, CaseSource, CheckFunction
+from .types import AuthProfiles
 from .utils import NOT_SET, GenericResponse, Ok, Result
 
 if TYPE_CHECKING:
+    from .checks import ObjectLevelAuthorizationCheck
     from .schemas import BaseSchema
 
 
@@ -56,6 +59,18 @@ class StatefulTest:
     def make_operation(self, collected: List[ParsedData]) -> APIOperation:
         raise NotImplementedError
 
+    def is_used_in(self, parsed: ParsedData, case: Case) -> bool:
+        """Whether the given case contains the parsed data."""
+        raise NotImplementedError
+
+    def reads_created_resource(self, response: GenericResponse) -> bool:
+        """Whether this test reads a resource that was created by the call with the given response."""
+        return False
+
+
+# API operations created for stateful tests & data they were created from
+_STATEFUL_DATA: WeakKeyDictionary = WeakKeyDictionary()
+
 
 @attr.s(slots=True)  # pragma: no mutate
 class StatefulData:
@@ -63,14 +78,59 @@ class StatefulData:
 
     stateful_test: StatefulTest = attr.ib()  # pragma: no mutate
     container: List[ParsedData] = attr.ib(factory=list)  # pragma: no mutate
+    # Calls that produced the corresponding items in `container`
+    sources: List[CaseSource] = attr.ib(factory=list)  # pragma: no mutate
 
     def make_operation(self) -> APIOperation:
-        return self.stateful_test.make_operation(self.container)
+        operation = self.stateful_test.make_operation(self.container)
+        _STATEFUL_DATA[operation] = self
+        return operation
 
     def store(self, case: Case, response: GenericResponse) -> None:
         """Parse and store data for a stateful test."""
         parsed = self.stateful_test.parse(case, response)
         self.container.append(parsed)
+        self.sources.append(CaseSource(case=case, response=response))
+
+    def find_source(self, case: Case) -> Optional[CaseSource]:
+        """Find the call that produced data used in the given case."""
+        for parsed, source in zip(self.container, self.sources):
+            if self.stateful_test.is_used_in(parsed, case):
+                return source
+        return None
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class DeletedResources:
+    """Resources that were successfully deleted during a stateful test.
+
+    A successful access to any of them later on is a "use after free" bug.
+    """
+
+    # Paths of deleted resources & calls that deleted them
+    items: Dict[str, CaseSource] = attr.ib(factory=dict)  # pragma: no mutate
+
+    def store(self, case: Case, response: GenericResponse) -> None:
+        if not 200 <= response.status_code < 300:
+            return
+        path = case.formatted_path
+        if case.method == "DELETE":
+            self.items[path] = CaseSource(case=case, response=response)
+        elif case.method == "PUT":
+            # The resource is created again
+            self.items.pop(path, None)
+
+    def get(self, case: Case) -> Optional[CaseSource]:
+        """Get the call that deleted the resource accessed by the given case, if there is any."""
+        # Repeated deletion is not an access, and `PUT` may create the resource again
+        if case.method in ("DELETE", "PUT"):
+            return None
+        path = case.formatted_path
+        for deleted_path, source in self.items.items():
+            # Sub-resources are gone as well
+            if path == deleted_path or path.startswith(f"{deleted_path.rstrip('/')}/"):
+                return source
+        return None
 
 
 @attr.s(slots=True)  # pragma: no mutate
@@ -83,12 +143,29 @@ class Feedback:
     stateful: Optional[Stateful] = attr.ib()  # pragma: no mutate
     operation: APIOperation = attr.ib(repr=False)  # pragma: no mutate
     stateful_tests: Dict[str, StatefulData] = attr.ib(factory=dict, repr=False)  # pragma: no mutate
+    # Shared by all operations within the same chain of links. Not set for operations that are tested directly
+    deleted_resources: Optional[DeletedResources] = attr.ib(default=None, repr=False)  # pragma: no mutate
 
     def add_test_case(self, case: Case, response: GenericResponse) -> None:
         """Store test data to reuse it in the future additional tests."""
         for stateful_test in case.operation.get_stateful_tests(response, self.stateful):
             data = self.stateful_tests.setdefault(stateful_test.name, StatefulData(stateful_test))
             data.store(case, response)
+        if self.deleted_resources is not None:
+            self.deleted_resources.store(case, response)
+
+    def set_source(self, case: Case) -> None:
+        """Set the call that produced data for the given case if the API operation is tested via links."""
+        data = _STATEFUL_DATA.get(self.operation)
+        if data is not None and case.source is None:
+            case.source = data.find_source(case)
+            if case.source is not None and data.stateful_test.reads_created_resource(case.source.response):
+                case.created_by = case.source
+
+    def set_deleted_by(self, case: Case) -> None:
+        """Mark the case if it accesses a resource that was deleted earlier in the same chain of links."""
+        if self.deleted_resources is not None:
+            case.deleted_by = self.deleted_resources.get(case)
 
     def get_stateful_tests(
         self, test: Callable, settings: Optional[hypothesis.settings], seed: Optional[int]
@@ -125,6 +202,10 @@ class Direction:
     def set_data(self, case: Case, **kwargs: Any) -> None:
         raise NotImplementedError
 
+    def reads_created_resource(self, response: GenericResponse) -> bool:
+        """Whether this transition reads a resource that was created by the call with the given response."""
+        return False
+
 
 def _print_case(case: Case) -> str:
     operation = f"state.schema['{case.operation.path}']['{case.operation.method.upper()}']"
@@ -159,9 +240,18 @@ class APIStateMachine(RuleBasedStateMachine):
     # attribute will be renamed in the future
     bundles: ClassVar[Dict[str, CaseInsensitiveDict]]  # type: ignore
     schema: "BaseSchema"
+    # Named sets of credentials headers. API calls are made with the first one, and successful calls that use
+    # resources from previous steps are replayed with the other ones to verify that they don't have access to them
+    auth_profiles: ClassVar[Optional[AuthProfiles]] = None
 
     def __init__(self) -> None:
         super().__init__()  # type: ignore
+        self._deleted_resources = DeletedResources()
+        self._object_level_authorization_check: Optional["ObjectLevelAuthorizationCheck"] = None
+        if self.auth_profiles is not None and len(self.auth_profiles) > 1:
+            from .checks import make_object_level_authorization_check  # pylint: disable=import-outside-toplevel
+
+            self._object_level_authorization_check = make_object_level_authorization_check(self.auth_profiles)
         self.setup()
 
     def _pretty_print(self, value: Any) -> str:
@@ -206,11 +296,25 @@ class APIStateMachine(RuleBasedStateMachine):
         if previous is not None:
             result, direction = previous
             case = self.transform(result, direction, case)
+            if direction.reads_created_resource(result.response):
+                case.created_by = CaseSource(case=result.case, response=result.response)
         self.before_call(case)
         kwargs = self.get_call_kwargs(case)
+        if self.auth_profiles:
+            kwargs["headers"] = {**kwargs.get("headers", {}), **next(iter(self.auth_profiles.values()))}
+        if previous is not None and self._object_level_authorization_check is not None:
+            self._object_level_authorization_check.before_call(
+                case, kwargs.get("headers", {}), lambda headers: self.call(case, **{**kwargs, "headers": headers})
+            )
         response = self.call(case, **kwargs)
         self.after_call(response, case)
+        case.deleted_by = self._deleted_resources.get(case)
         self.validate_response(response, case)
+        if previous is not None and self._object_level_authorization_check is not None:
+            case.validate_response(response, checks=(self._object_level_authorization_check,))
+        if previous is not None:
+            # Only resources deleted via links are tracked
+            self._deleted_resources.store(case, response)
         return self.store_result(response, case)
 
     def before_call(self, case: Case) -> None:
@@ -0,0 +1,82 @@
+"""Limiting the rate of requests and retrying throttled ones."""
+import re
+import threading
+import time
+from email.utils import parsedate_to_datetime
+from typing import Callable, Optional
+
+import attr
+import requests
+
+# Responses with these status codes are retried if retries are enabled
+RETRY_STATUS_CODES = (429, 503)  # pragma: no mutate
+# Maximum delay between retries in seconds, even if the API asks for a longer one
+MAX_RETRY_DELAY = 60  # pragma: no mutate
+RATE_LIMIT_PERIODS = {"s": 1, "m": 60, "h": 3600}  # pragma: no mutate
+RATE_LIMIT_RE = re.compile(r"^\s*(\d+)\s*/\s*([smh])\s*$")
+
+
+@attr.s(slots=True)  # pragma: no mutate
+class RateLimiter:
+    """Allow at most ``max_requests`` requests per ``period`` seconds.
+
+    Requests are spread evenly over the period. The same instance can be shared between threads.
+    """
+
+    max_requests: int = attr.ib()  # pragma: no mutate
+    period: float = attr.ib()  # pragma: no mutate
+    clock: Callable[[], float] = attr.ib(default=time.monotonic, eq=False, repr=False)  # pragma: no mutate
+    sleep: Callable[[float], None] = attr.ib(default=time.sleep, eq=False, repr=False)  # pragma: no mutate
+    _lock: threading.Lock = attr.ib(factory=threading.Lock, eq=False, repr=False)  # pragma: no mutate
+    _next_at: Optional[float] = attr.ib(default=None, eq=False, repr=False)  # pragma: no mutate
+
+    @classmethod
+    def from_string(cls, value: str) -> "RateLimiter":
+        """Create a limiter from a string like ``100/s``, ``1000/m`` or ``5000/h``."""
+        match = RATE_LIMIT_RE.match(value)
+        if match is None or int(match.group(1)) == 0:
+            raise ValueError(f"Should be in N/s, N/m or N/h format, where N is a positive integer. Got: {value}")
+        return cls(max_requests=int(match.group(1)), period=RATE_LIMIT_PERIODS[match.group(2)])
+
+    @property
+    def interval(self) -> float:
+        return self.period / self.max_requests
+
+    def acquire(self) -> None:
+        """Wait until the next request is allowed."""
+        with self._lock:
+            now = self.clock()
+            if self._next_at is None or self._next_at < now:
+                self._next_at = now
+            delay = self._next_at - now
+            # Reserve the slot while holding the lock, so concurrent callers get different slots
+            self._next_at += self.interval
+        if delay > 0:
+            self.sleep(delay)
+
+
+def get_retry_delay(response: requests.Response, attempt: int) -> float:
+    """How long to wait before retrying the request that received the given response.
+
+    The ``Retry-After`` header is used if it is valid, otherwise the delay grows exponentially with each attempt.
+    """
+    delay = parse_retry_after(response.headers.get("Retry-After"))
+    if delay is None:
+        delay = 2 ** attempt
+    return min(delay, MAX_RETRY_DELAY)
+
+
+def parse_retry_after(value: Optional[str]) -> Optional[float]:
+    """Parse the ``Retry-After`` header value, that is either a number of seconds or an HTTP date."""
+    if value is None:
+        return None
+    value = value.strip()
+    if value.isdigit():
+        return float(value)
+    try:
+        retry_at = parsedate_to_datetime(value)
+    except (TypeError, ValueError, IndexError):
+        return None
+    if retry_at is None or retry_at.tzinfo is None:
+        return None
+    return max(retry_at.timestamp() - time.time(), 0.0)
@@ -29,5 +29,9 @@ Hook = Union[
 ]  # pragma: no mutate
 
 RawAuth = Tuple[str, str]  # pragma: no mutate
+# Named sets of headers with credentials. The first one is the owner of resources
+AuthProfiles = Dict[str, Dict[str, str]]  # pragma: no mutate
+# A path to a client certificate, or a pair of paths to a certificate and its private key
+RequestCert = Union[str, Tuple[str, str]]  # pragma: no mutate
 # Generic test with any arguments and no return
 GenericTest = Callable[..., None]  # pragma: no mutate
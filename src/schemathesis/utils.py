@@ -13,6 +13,7 @@ from typing import (
     Dict,
     Generator,
     Generic,
+    Iterable,
     List,
     NoReturn,
     Optional,
@@ -23,6 +24,7 @@ from typing import (
     Union,
     overload,
 )
+from urllib.parse import quote_plus
 
 import requests
 import yaml
@@ -232,6 +234,29 @@ def get_requests_auth(auth: Optional[RawAuth], auth_type: Optional[str]) -> Opti
 GenericResponse = Union[requests.Response, WSGIResponse]  # pragma: no mutate
 
 
+# Reserved characters that don't change the query string structure if left as is.
+# `+` is excluded as well, since it is decoded as a space
+QUERY_RESERVED_CHARACTERS = ":/?[]@!$'()*,;"
+
+
+def encode_query(query: Dict[str, Any], allow_reserved: Iterable[str] = ()) -> str:
+    """Encode query parameters the same way as `requests` does, keeping reserved characters for the given parameters.
+
+    `&`, `=` and `#` are always encoded, otherwise they would change the query string structure.
+    `+` is always encoded too, since it is decoded as a space.
+    """
+    allow_reserved = set(allow_reserved)
+    pairs = []
+    for name, values in query.items():
+        if not isinstance(values, (list, tuple)):
+            values = [values]
+        safe = QUERY_RESERVED_CHARACTERS if name in allow_reserved else ""
+        for value in values:
+            if value is not None:
+                pairs.append(f"{quote_plus(str(name))}={quote_plus(str(value), safe=safe)}")
+    return "&".join(pairs)
+
+
 def get_response_payload(response: GenericResponse) -> str:
     if isinstance(response, requests.Response):
         return response.text
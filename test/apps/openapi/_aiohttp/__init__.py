@@ -1,5 +1,5 @@
 from functools import wraps
-from typing import Callable, Tuple
+from typing import Any, Callable, Dict, Tuple
 
 import yaml
 from aiohttp import web
@@ -49,7 +49,11 @@ def create_app(
 
     app = web.Application()
     app.add_routes(
-        [web.get("/schema.yaml", schema), web.get("/api/cookies", set_cookies)]
+        [
+            web.get("/schema.yaml", schema),
+            web.get("/api/cookies", set_cookies),
+            web.post("/api/oauth2/token", handlers.oauth2_token),
+        ]
         + [web.route(item.value[0], item.value[1], wrapper(item.name)) for item in Operation if item.name != "all"]
     )
 
@@ -58,9 +62,14 @@ def create_app(
 
     app.add_routes([web.get("/answer.json", answer)])
     app["users"] = {}
+    app["oauth2"] = make_oauth2_state()
     app["incoming_requests"] = incoming_requests
     app["schema_requests"] = schema_requests
-    app["config"] = {"should_fail": True, "schema_data": make_openapi_schema(operations, version)}
+    app["config"] = {
+        "should_fail": True,
+        "throttled_requests": 2,
+        "schema_data": make_openapi_schema(operations, version),
+    }
     return app
 
 
@@ -71,6 +80,15 @@ def reset_app(
 ) -> None:
     """Clean up all internal containers of the application and resets its config."""
     app["users"].clear()
+    app["oauth2"].update(make_oauth2_state())
     app["incoming_requests"][:] = []
     app["schema_requests"][:] = []
-    app["config"].update({"should_fail": True, "schema_data": make_openapi_schema(operations, version)})
+    app["config"].update(
+        {"should_fail": True, "throttled_requests": 2, "schema_data": make_openapi_schema(operations, version)}
+    )
+
+
+def make_oauth2_state() -> Dict[str, Any]:
+    # Issued access tokens & requests to the token endpoint. Only the last token is valid
+    # If `max_token_uses` is set, then the token is rejected after being used that many times
+    return {"tokens": [], "requests": [], "uses": 0, "max_token_uses": None}
@@ -1,4 +1,5 @@
 import asyncio
+import base64
 import cgi
 import csv
 import io
@@ -101,6 +102,42 @@ async def headers(request: web.Request) -> web.Response:
     return web.json_response(values, headers=values)
 
 
+async def oauth2(request: web.Request) -> web.Response:
+    state = request.app["oauth2"]
+    if state["tokens"] and request.headers.get("Authorization") == f"Bearer {state['tokens'][-1]}":
+        state["uses"] += 1
+        if state["max_token_uses"] is None or state["uses"] <= state["max_token_uses"]:
+            return web.json_response({"success": True})
+    raise web.HTTPUnauthorized(text='{"detail": "Unauthorized"}', content_type="application/json")
+
+
+async def oauth2_token(request: web.Request) -> web.Response:
+    # A simplified OAuth 2 token endpoint. Only the `client` / `secret` client is known to it
+    state = request.app["oauth2"]
+    data = await request.post()
+    state["requests"].append(dict(data))
+    client_id, client_secret = data.get("client_id"), None
+    if "Authorization" in request.headers:
+        client_id, client_secret = base64.b64decode(request.headers["Authorization"][6:]).decode().split(":", 1)
+    if client_id != "client" or client_secret not in (None, "secret"):
+        return web.json_response({"error": "invalid_client"}, status=401)
+    grant_type = data.get("grant_type")
+    if grant_type == "password":
+        if (data.get("username"), data.get("password")) != ("user", "password"):
+            return web.json_response({"error": "invalid_grant"}, status=400)
+    elif grant_type == "refresh_token":
+        if not state["tokens"] or data.get("refresh_token") != f"refresh-{state['tokens'][-1]}":
+            return web.json_response({"error": "invalid_grant"}, status=400)
+    elif grant_type != "client_credentials":
+        return web.json_response({"error": "unsupported_grant_type"}, status=400)
+    token = f"token-{len(state['tokens'])}"
+    state["tokens"].append(token)
+    state["uses"] = 0
+    return web.json_response(
+        {"access_token": token, "token_type": "bearer", "expires_in": 3600, "refresh_token": f"refresh-{token}"}
+    )
+
+
 async def malformed_json(request: web.Request) -> web.Response:
     return web.Response(body="{malformed}", content_type="application/json")
 
@@ -138,6 +175,14 @@ async def flaky(request: web.Request) -> web.Response:
     return web.json_response({"result": "flaky!"})
 
 
+async def throttled(request: web.Request) -> web.Response:
+    config = request.app["config"]
+    if config["throttled_requests"] > 0:
+        config["throttled_requests"] -= 1
+        return web.json_response({"detail": "Too many requests"}, status=429, headers={"Retry-After": "0"})
+    return web.json_response({"result": "throttled!"})
+
+
 async def multiple_failures(request: web.Request) -> web.Response:
     try:
         id_value = int(request.query["id"])
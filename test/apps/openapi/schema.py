@@ -36,6 +36,8 @@ class Operation(Enum):
     missing_path_parameter = ("GET", "/api/missing_path_parameter/{id}")
     headers = ("GET", "/api/headers")
     reserved = ("GET", "/api/foo:bar")
+    oauth2 = ("GET", "/api/oauth2")
+    throttled = ("GET", "/api/throttled")
 
     create_user = ("POST", "/api/users/")
     get_user = ("GET", "/api/users/{user_id}")
@@ -393,6 +395,13 @@ def _make_openapi_3_schema(operations: Tuple[str, ...]) -> Dict:
             "securitySchemes": {
                 "api_key": {"type": "apiKey", "name": "X-Token", "in": "header"},
                 "basicAuth": {"type": "http", "scheme": "basic"},
+                "oauth2": {
+                    "type": "oauth2",
+                    "flows": {
+                        "clientCredentials": {"tokenUrl": "/api/oauth2/token", "scopes": {"read": "Read access"}},
+                        "password": {"tokenUrl": "/api/oauth2/token", "scopes": {"read": "Read access"}},
+                    },
+                },
             }
         },
     }
@@ -553,7 +562,13 @@ def _make_openapi_3_schema(operations: Tuple[str, ...]) -> Dict:
                     "200": {
                         "description": "OK",
                         "content": {"application/json": {"schema": {"type": "object"}}},
-                        "headers": {"X-Custom-Header": {"description": "Custom header", "schema": {"type": "integer"}}},
+                        "headers": {
+                            "X-Custom-Header": {
+                                "description": "Custom header",
+                                "schema": {"type": "integer"},
+                                "required": True,
+                            }
+                        },
                     },
                     "default": {"description": "Default response"},
                 },
@@ -573,6 +588,12 @@ def _make_openapi_3_schema(operations: Tuple[str, ...]) -> Dict:
                     # 401 is not described on purpose to cause a testing error
                 },
             }
+        elif name == "oauth2":
+            schema = {
+                "security": [{"oauth2": ["read"]}],
+                "parameters": [{"name": "id", "in": "query", "required": True, "schema": {"type": "integer"}}],
+                "responses": {"200": {"description": "OK"}},
+            }
         elif name == "create_user":
             schema = {
                 "requestBody": {
@@ -59,6 +59,51 @@ def test_validate_header(value):
         callbacks.validate_headers(None, None, value)
 
 
+def test_validate_auth_profiles():
+    value = ("alice:Authorization: Bearer 1", "bob:Authorization: Bearer 2", "alice:X-Tenant: 1")
+    assert callbacks.validate_auth_profiles(None, None, value) == {
+        "alice": {"Authorization": "Bearer 1", "X-Tenant": "1"},
+        "bob": {"Authorization": "Bearer 2"},
+    }
+
+
+@pytest.mark.parametrize(
+    "value, message",
+    (
+        (("alice",), "Should be in NAME:HEADER:VALUE format. Got: alice"),
+        ((":Authorization: Bearer 1", "bob:Authorization: Bearer 2"), "Profile name should not be empty"),
+        (("alice:Authorization",), "Should be in KEY:VALUE format. Got: Authorization"),
+        (("alice:Authorization: Bearer 1",), "At least two profiles are required"),
+    ),
+)
+def test_validate_auth_profiles_invalid(value, message):
+    with pytest.raises(click.BadParameter, match=message):
+        callbacks.validate_auth_profiles(None, None, value)
+
+
+def test_validate_resolve():
+    value = ("example.com:127.0.0.1", "ipv6.example.com: ::1")
+    assert callbacks.validate_resolve(None, None, value) == {"example.com": "127.0.0.1", "ipv6.example.com": "::1"}
+
+
+@pytest.mark.parametrize("value", ("example.com", "example.com:", ":127.0.0.1"))
+def test_validate_resolve_invalid(value):
+    with pytest.raises(click.BadParameter, match=f"Should be in HOST:ADDRESS format. Got: {value}"):
+        callbacks.validate_resolve(None, None, (value,))
+
+
+@pytest.mark.parametrize("value", ("127.0.0.1:8080", "proxy"))
+def test_validate_proxy_invalid(value):
+    with pytest.raises(click.BadParameter, match="Should be a URL with a scheme"):
+        callbacks.validate_proxy(None, None, value)
+
+
+@pytest.mark.parametrize("value", ("100", "100/d", "0/s", "-1/s", "a/s"))
+def test_convert_rate_limit_invalid(value):
+    with pytest.raises(click.BadParameter, match="Should be in N/s, N/m or N/h format"):
+        callbacks.convert_rate_limit(None, None, value)
+
+
 def test_reraise_format_error():
     with pytest.raises(click.BadParameter, match="Should be in KEY:VALUE format. Got: bla"):
         with callbacks.reraise_format_error("bla"):
@@ -11,6 +11,8 @@ from schemathesis.cli.cassettes import filter_cassette, get_command_representati
 from schemathesis.constants import USER_AGENT
 from schemathesis.models import Request
 
+from ..apps.openapi.schema import OpenAPIVersion
+
 
 @pytest.fixture
 def cassette_path(tmp_path):
@@ -54,6 +56,32 @@ def test_store_cassette(cli, schema_url, cassette_path, hypothesis_max_examples)
     assert len(cassette["http_interactions"][1]["checks"]) == 1
 
 
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("throttled")
+def test_retried_interactions(cli, schema_url, cassette_path, openapi_version):
+    # When the API responds with 429 and retries are enabled
+    result = cli.run(schema_url, f"--store-network-log={cassette_path}", "--request-retries=2")
+    assert result.exit_code == ExitCode.OK, result.stdout
+    # Then retried requests are stored in the cassette as well
+    cassette = load_cassette(cassette_path)
+    interactions = cassette["http_interactions"]
+    assert [interaction["response"]["status"]["code"] for interaction in interactions] == ["429", "429", "200"]
+    # And checks are not run for them
+    assert interactions[0]["checks"] == interactions[1]["checks"] == []
+    assert len(interactions[2]["checks"]) == 1
+
+
+@pytest.mark.operations("success", "failure")
+def test_cassette_shard(cli, schema_url, base_url, cassette_path):
+    # When only a part of API operations is tested
+    result = cli.run(schema_url, f"--store-network-log={cassette_path}", "--shard=2/2")
+    assert result.exit_code == ExitCode.OK, result.stdout
+    # Then the shard is stored in the cassette
+    cassette = load_cassette(cassette_path)
+    assert cassette["shard"] == "2/2"
+    assert {interaction["request"]["uri"] for interaction in cassette["http_interactions"]} == {f"{base_url}/success"}
+
+
 @pytest.mark.operations("flaky")
 def test_interaction_status(cli, openapi3_schema_url, hypothesis_max_examples, cassette_path):
     # See GH-695
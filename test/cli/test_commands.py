@@ -13,15 +13,19 @@ import pytest
 import yaml
 from _pytest.main import ExitCode
 from hypothesis import HealthCheck, Phase, Verbosity
+from requests.auth import HTTPDigestAuth
 
 from schemathesis import Case, DataGenerationMethod, fixups
-from schemathesis.checks import ALL_CHECKS
+from schemathesis.checks import ALL_CHECKS, ignored_auth
 from schemathesis.cli import reset_checks
 from schemathesis.constants import USER_AGENT
 from schemathesis.hooks import unregister_all
 from schemathesis.models import APIOperation
 from schemathesis.runner import DEFAULT_CHECKS
+from schemathesis.sessions import SessionConfig
+from schemathesis.sharding import Shard
 from schemathesis.targets import DEFAULT_TARGETS
+from schemathesis.throttling import RateLimiter
 
 PHASES = ", ".join(map(lambda x: x.name, Phase))
 HEALTH_CHECKS = "|".join(map(lambda x: x.name, HealthCheck))
@@ -132,6 +136,14 @@ def test_commands_version(cli):
             ("run", "http://127.0.0.1", "--max-response-time=0"),
             "Error: Invalid value for '--max-response-time': 0 is smaller than the minimum valid value 1.",
         ),
+        (
+            ("run", "http://127.0.0.1", "--shard=2"),
+            "Error: Invalid value for '--shard': Should be in N/M format, where N and M are positive integers. Got: 2",
+        ),
+        (
+            ("run", "http://127.0.0.1", "--shard=3/2"),
+            "Error: Invalid value for '--shard': Shard index should be between 1 and 2. Got: 3",
+        ),
     ),
 )
 def test_commands_run_errors(cli, args, error):
@@ -170,13 +182,22 @@ def test_commands_run_help(cli):
         "  --skip-deprecated-operations  Skip testing of deprecated API operations.",
         "                                [default: False]",
         "",
+        "  --shard TEXT                  Test only a part of API operations, e.g. 2/4 for",
+        "                                the second part out of four. Use it to split",
+        "                                testing between parallel jobs.",
+        "",
+        "  --shard-durations PATH        JUnit XML report of a previous run to balance",
+        "                                the parts by testing durations. Can be passed",
+        "                                multiple times.",
+        "",
         "",
         "Validation options:",
         "",
         "  Options, responsible for how responses & schemas will be checked.",
         "",
         "  -c, --checks [not_a_server_error|status_code_conformance|"
-        "content_type_conformance|response_headers_conformance|response_schema_conformance|all]",
+        "content_type_conformance|response_headers_conformance|response_schema_conformance|"
+        "negative_data_rejection|use_after_free|ensure_resource_availability|ignored_auth|all]",
         "                                  List of checks to run.  [default:",
         "                                  not_a_server_error]",
         "",
@@ -217,7 +238,7 @@ def test_commands_run_help(cli):
         "                                  Verbosity level of Hypothesis messages.",
         "",
         "Generic options:",
-        "  -D, --data-generation-method [positive]",
+        "  -D, --data-generation-method [positive|negative|boundary]",
         "                                  Defines how Schemathesis generates data for",
         "                                  tests.  [default: positive]",
         "",
@@ -226,10 +247,25 @@ def test_commands_run_help(cli):
         "  -x, --exitfirst                 Exit instantly on first error or failed test.",
         "                                  [default: False]",
         "",
+        "  --last-failed                   Test only API operations that failed in the",
+        "                                  previous run. Their failing test cases are",
+        "                                  replayed first.",
+        "",
+        "  --failed-first                  Test API operations that failed in the",
+        "                                  previous run before the other ones.",
+        "",
         "  --dry-run                       Disable sending data to the application and",
         "                                  checking responses. Helpful to verify whether",
         "                                  data is generated at all.",
         "",
+        "  --max-duration INTEGER RANGE    Maximum duration of the whole test run in",
+        "                                  seconds. When it is reached, no new tests are",
+        "                                  started, and the running ones are finished.",
+        "",
+        "  --max-operation-duration INTEGER RANGE",
+        "                                  Maximum duration of testing a single API",
+        "                                  operation in seconds.",
+        "",
         "  -a, --auth TEXT                 Server user and password. Example:",
         "                                  USER:PASSWORD",
         "",
@@ -240,7 +276,26 @@ def test_commands_run_help(cli):
         "                                  requests to the server. Example:",
         r"                                  Authorization: Bearer\ 123",
         "",
+        "  --auth-profile TEXT             Named credentials header to test access to",
+        "                                  resources of other users. Resources are",
+        "                                  created with the first profile and are",
+        "                                  accessed with the other ones. Example:",
+        r"                                  alice:Authorization: Bearer\ 123",
+        "",
+        "  --oauth2-client-id TEXT         OAuth 2 client ID. Enables OAuth 2",
+        "                                  authentication.",
+        "",
+        "  --oauth2-client-secret TEXT     OAuth 2 client secret.",
+        "  --oauth2-token-url TEXT         OAuth 2 token URL. By default, it is taken",
+        "                                  from the API schema.",
+        "",
+        "  --oauth2-scope TEXT             OAuth 2 scope to request.",
+        "  --oauth2-user TEXT              Resource owner credentials for the OAuth 2",
+        "                                  password grant. The client credentials grant",
+        "                                  is used if not set. Example: USER:PASSWORD",
+        "",
         "  -w, --workers [auto|1-64]       Number of workers to run tests.  [default: 1]",
+        "",
         "  -b, --base-url TEXT             Base URL address of the API, required for",
         "                                  SCHEMA if specified by file.",
         "",
@@ -254,12 +309,47 @@ def test_commands_run_help(cli):
         "                                  the path to a CA_BUNDLE file for private",
         "                                  certs.  [default: true]",
         "",
+        "  --request-cert PATH             File path of unencrypted client certificate",
+        "                                  for authentication. The certificate can be",
+        "                                  bundled with a private key (e.g. PEM) or the",
+        "                                  private key can be provided with the",
+        "                                  --request-cert-key argument.",
+        "",
+        "  --request-cert-key PATH         File path of the private key of the client",
+        "                                  certificate.",
+        "",
+        "  --proxy TEXT                    Proxy URL for all network requests. Hosts",
+        "                                  listed in the NO_PROXY environment variable",
+        "                                  are not proxied.",
+        "",
+        "  --request-pool-size INTEGER RANGE",
+        "                                  Maximum number of connections to keep in the",
+        "                                  pool for each host.",
+        "",
+        "  --resolve HOST:ADDRESS          Connect to ADDRESS instead of the address that",
+        "                                  HOST is resolved to. Could be used multiple",
+        "                                  times.",
+        "",
+        "  --rate-limit TEXT               The maximum rate of requests to the API, e.g.",
+        "                                  100/s, 1000/m or 5000/h. It is shared by all",
+        "                                  workers.",
+        "",
+        "  --request-retries INTEGER RANGE",
+        "                                  How many times to retry requests that receive",
+        "                                  429 or 503 responses. The Retry-After header",
+        "                                  is respected.",
+        "",
         "  --junit-xml FILENAME            Create junit-xml style report file at given",
         "                                  path.",
         "",
         "  --debug-output-file FILENAME    Save debug output as JSON lines in the given",
         "                                  file.",
         "",
+        "  --run-state-file FILE           File to store failures between runs. They are",
+        "                                  stored only if this option, --last-failed, or",
+        "                                  --failed-first is passed, in",
+        "                                  .schemathesis/run-state.json by default.",
+        "",
         "  --show-errors-tracebacks        Show full tracebacks for internal errors.",
         "                                  [default: False]",
         "",
@@ -274,7 +364,8 @@ def test_commands_run_help(cli):
         "                                  Limit recursion depth for stateful testing.",
         "                                  [default: 5]",
         "",
-        "  --force-schema-version [20|30]  Force Schemathesis to parse the input schema",
+        "  --force-schema-version [20|30|31]",
+        "                                  Force Schemathesis to parse the input schema",
         "                                  with the specified spec version.",
         "",
         "  --no-color                      Disable ANSI color escape codes.",
@@ -292,7 +383,14 @@ SCHEMA_URI = "https://example.com/swagger.json"
         ([], {}),
         (["--exitfirst"], {"exit_first": True}),
         (["--workers=2"], {"workers_num": 2}),
+        (["--max-duration=600"], {"max_duration": 600}),
+        (["--max-operation-duration=60"], {"max_operation_duration": 60}),
+        (["--shard=2/4"], {"shard": Shard(2, 4)}),
         (["--hypothesis-seed=123"], {"seed": 123}),
+        (
+            ["--auth-profile=alice:Authorization: Bearer 1", "--auth-profile=bob:Authorization: Bearer 2"],
+            {"auth_profiles": {"alice": {"Authorization": "Bearer 1"}, "bob": {"Authorization": "Bearer 2"}}},
+        ),
         (
             [
                 "--hypothesis-deadline=1000",
@@ -317,6 +415,26 @@ SCHEMA_URI = "https://example.com/swagger.json"
         ),
         (["--hypothesis-deadline=None"], {"hypothesis_settings": hypothesis.settings(deadline=None)}),
         (["--max-response-time=10"], {"max_response_time": 10}),
+        (
+            ["--proxy=http://127.0.0.1:8080", "--request-pool-size=5", "--resolve=example.com:127.0.0.1"],
+            {
+                "session_config": SessionConfig(
+                    proxy="http://127.0.0.1:8080", pool_size=5, resolve={"example.com": "127.0.0.1"}
+                )
+            },
+        ),
+        (
+            ["--rate-limit=100/m", "--request-retries=3"],
+            {
+                "session_config": SessionConfig(rate_limiter=RateLimiter(max_requests=100, period=60), retries=3),
+                # Waits for the rate limiter and before retries could be longer than the default deadline
+                "hypothesis_settings": hypothesis.settings(deadline=None),
+            },
+        ),
+        (
+            ["--request-retries=3", "--hypothesis-deadline=1000"],
+            {"session_config": SessionConfig(retries=3), "hypothesis_settings": hypothesis.settings(deadline=1000)},
+        ),
     ),
 )
 def test_from_schema_arguments(cli, mocker, swagger_20, args, expected):
@@ -331,13 +449,21 @@ def test_from_schema_arguments(cli, mocker, swagger_20, args, expected):
         "workers_num": 1,
         "exit_first": False,
         "dry_run": False,
+        "max_duration": None,
+        "max_operation_duration": None,
+        "shard": None,
+        "rerun_failed": None,
         "stateful": None,
         "stateful_recursion_limit": 5,
         "auth": None,
         "auth_type": "basic",
         "headers": {},
+        "auth_profiles": {},
+        "oauth2": None,
         "request_timeout": None,
         "request_tls_verify": True,
+        "request_cert": None,
+        "session_config": None,
         "store_interactions": False,
         "seed": None,
         "max_response_time": None,
@@ -367,6 +493,7 @@ def test_from_schema_arguments(cli, mocker, swagger_20, args, expected):
         (["--tag=foo"], {"tag": ("foo",)}),
         (["--operation-id=getUser"], {"operation_id": ("getUser",)}),
         (["--base-url=https://example.com/api/v1test"], {"base_url": "https://example.com/api/v1test"}),
+        (["--request-pool-size=5"], {"session_config": SessionConfig(pool_size=5)}),
     ),
 )
 def test_load_schema_arguments(cli, mocker, args, expected):
@@ -389,6 +516,8 @@ def test_load_schema_arguments(cli, mocker, args, expected):
         "skip_deprecated_operations": False,
         "force_schema_version": None,
         "request_tls_verify": True,
+        "request_cert": None,
+        "session_config": None,
         **expected,
     }
 
@@ -396,6 +525,24 @@ def test_load_schema_arguments(cli, mocker, args, expected):
     assert load_schema.call_args[1] == expected
 
 
+@pytest.mark.parametrize(
+    "args, expected",
+    (
+        (("--auth=test:test",), ("test", "test")),
+        (("--auth=test:test", "--auth-type=digest"), HTTPDigestAuth("test", "test")),
+        (("--auth=test:test", "--request-pool-size=5"), ("test", "test")),
+    ),
+)
+def test_auth_passed_to_loader(cli, mocker, args, expected):
+    mocker.patch("schemathesis.runner.SingleThreadRunner.execute", autospec=True)
+    from_uri = mocker.patch("schemathesis.specs.openapi.loaders.from_uri", autospec=True)
+    # When auth is passed, with or without other network settings
+    result = cli.run(SCHEMA_URI, *args)
+    assert result.exit_code == ExitCode.OK, result.stdout
+    # Then it is used to load the schema
+    assert from_uri.call_args[1]["auth"] == expected
+
+
 def test_load_schema_arguments_headers_to_loader_for_app(testdir, cli, mocker):
     from_wsgi = mocker.patch("schemathesis.specs.openapi.loaders.from_wsgi", autospec=True)
 
@@ -419,6 +566,16 @@ def test_all_checks(cli, mocker, swagger_20):
     assert execute.call_args[1]["checks"] == ALL_CHECKS
 
 
+def test_all_checks_with_extra_checks(cli, mocker, swagger_20):
+    mocker.patch("schemathesis.cli.load_schema", return_value=swagger_20)
+    execute = mocker.patch("schemathesis.runner.from_schema", autospec=True)
+    # When checks that send additional requests are selected together with `all`
+    result = cli.run(SCHEMA_URI, "--checks=all", "--checks=ignored_auth")
+    # Then they are added to all other checks
+    assert result.exit_code == ExitCode.OK, result.stdout
+    assert execute.call_args[1]["checks"] == ALL_CHECKS + (ignored_auth,)
+
+
 @pytest.mark.operations()
 def test_hypothesis_parameters(cli, schema_url):
     # When Hypothesis options are passed via command line
@@ -760,12 +917,121 @@ def test_headers_conformance_invalid(cli, cli_args):
 
 @pytest.mark.operations("headers")
 def test_headers_conformance_valid(cli, cli_args):
-    result = cli.run(*cli_args, "-c", "response_headers_conformance", "-H", "X-Custom-Header: bla")
+    result = cli.run(*cli_args, "-c", "response_headers_conformance", "-H", "X-Custom-Header: 42")
     assert result.exit_code == ExitCode.OK, result.stdout
     lines = result.stdout.split("\n")
     assert "1. Received a response with missing headers: X-Custom-Header" not in lines
 
 
+@pytest.mark.operations("headers")
+def test_headers_conformance_invalid_value(cli, cli_args):
+    # When the header value does not match its schema
+    result = cli.run(*cli_args, "-c", "response_headers_conformance", "-H", "X-Custom-Header: bla")
+    # Then it should be reported
+    assert result.exit_code == ExitCode.TESTS_FAILED, result.stdout
+    assert "1. The received `X-Custom-Header` header does not conform to the defined schema!" in result.stdout
+
+
+@pytest.mark.operations("basic")
+def test_ignored_auth_valid(cli, cli_args):
+    # When the API operation rejects requests without proper credentials
+    result = cli.run(*cli_args, "-c", "ignored_auth", "--auth", "test:test")
+    # Then the check passes
+    assert result.exit_code == ExitCode.OK, result.stdout
+
+
+@pytest.mark.operations("headers")
+def test_ignored_auth_invalid(cli, cli_args):
+    # When the API operation accepts requests without credentials
+    result = cli.run(*cli_args, "-c", "ignored_auth", "-H", "X-Token: secret")
+    # Then it should be reported
+    assert result.exit_code == ExitCode.TESTS_FAILED, result.stdout
+    assert "1. Authentication is not enforced for `GET /api/headers`" in result.stdout
+    # And both the original and modified requests are displayed
+    assert "X-Token: secret" in result.stdout
+    assert "Request without credentials:" in result.stdout
+    assert "X-Token: schemathesis-malformed-credentials" in result.stdout
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.parametrize(
+    "args, grant_type",
+    (
+        (("--oauth2-client-secret=secret",), "client_credentials"),
+        (("--oauth2-user=user:password", "--oauth2-scope=read"), "password"),
+    ),
+)
+@pytest.mark.operations("oauth2")
+def test_oauth2(cli, app, schema_url, args, grant_type, openapi_version):
+    # When OAuth 2 client credentials are passed
+    result = cli.run(schema_url, "--oauth2-client-id=client", *args)
+    # Then an access token should be obtained via the token URL from the schema and used in all API calls
+    assert result.exit_code == ExitCode.OK, result.stdout
+    assert app["oauth2"]["tokens"] == ["token-0"]
+    assert app["oauth2"]["requests"][0]["grant_type"] == grant_type
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("oauth2")
+def test_oauth2_refresh_on_unauthorized(cli, app, schema_url, openapi_version):
+    # When the API rejects access tokens after they were used once
+    app["oauth2"]["max_token_uses"] = 1
+    result = cli.run(
+        schema_url, "--oauth2-client-id=client", "--oauth2-client-secret=secret", "--hypothesis-max-examples=5"
+    )
+    # Then tokens should be refreshed and requests retried
+    assert result.exit_code == ExitCode.OK, result.stdout
+    assert len(app["oauth2"]["tokens"]) > 1
+    # And the refresh token should be used for it
+    assert app["oauth2"]["requests"][1]["grant_type"] == "refresh_token"
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("oauth2")
+def test_oauth2_ignored_auth(cli, app, schema_url, openapi_version):
+    # When OAuth 2 is used together with the `ignored_auth` check
+    result = cli.run(
+        schema_url, "--oauth2-client-id=client", "--oauth2-client-secret=secret", "--checks", "ignored_auth"
+    )
+    # Then requests with removed or modified credentials are rejected by the API
+    assert result.exit_code == ExitCode.OK, result.stdout
+    # And they are not retried with a new token
+    assert app["oauth2"]["tokens"] == ["token-0"]
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("oauth2")
+def test_oauth2_invalid_credentials(cli, server, schema_url, openapi_version):
+    # When OAuth 2 client credentials are not valid
+    token_url = f"http://127.0.0.1:{server['port']}/api/oauth2/token"
+    result = cli.run(
+        schema_url, "--oauth2-client-id=client", "--oauth2-client-secret=wrong", f"--oauth2-token-url={token_url}"
+    )
+    # Then the run should fail before testing starts
+    assert result.exit_code == ExitCode.TESTS_FAILED, result.stdout
+    assert f"Failed to obtain an OAuth 2 access token from {token_url}. Received 401" in result.stdout
+
+
+@pytest.mark.parametrize(
+    "args, message",
+    (
+        (("--oauth2-client-secret=secret",), "OAuth 2 options require `--oauth2-client-id`."),
+        (
+            ("--oauth2-client-id=client", "--auth=test:test"),
+            "Passing `--auth` together with `--oauth2-client-id` is not allowed.",
+        ),
+        (
+            ("--oauth2-client-id=client", "-H", "Authorization: Bearer 123"),
+            "Passing `--oauth2-client-id` together with `--header` that sets `Authorization` is not allowed.",
+        ),
+    ),
+)
+def test_oauth2_invalid_options(cli, args, message):
+    result = cli.run(SIMPLE_PATH, "--base-url=http://127.0.0.1:1", *args)
+    assert result.exit_code == ExitCode.INTERRUPTED, result.stdout
+    assert message in result.stdout
+
+
 @pytest.mark.operations("multiple_failures")
 def test_multiple_failures_single_check(cli, schema_url):
     result = cli.run(schema_url, "--hypothesis-seed=1", "--hypothesis-derandomize")
@@ -1144,7 +1410,9 @@ def new_check(request, testdir, cli):
     lines = result.stdout.splitlines()
     assert (
         "  -c, --checks [not_a_server_error|status_code_conformance|content_type_conformance|"
-        "response_headers_conformance|response_schema_conformance|all]" in lines
+        "response_headers_conformance|response_schema_conformance|negative_data_rejection|use_after_free|"
+        "ensure_resource_availability|ignored_auth|all]"
+        in lines
     )
 
 
@@ -1672,6 +1940,234 @@ def test_auth_and_authorization_header_are_disallowed(cli, schema_url, header, o
     )
 
 
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations()
+def test_auth_and_auth_profiles_are_disallowed(cli, schema_url, openapi_version):
+    # When ``--auth`` is passed together with ``--auth-profile``
+    result = cli.run(
+        schema_url, "--auth=test:test", "--auth-profile=alice:X-Token: 1", "--auth-profile=bob:X-Token: 2"
+    )
+    # Then it causes a validation error
+    assert result.exit_code == ExitCode.INTERRUPTED
+    assert "Invalid value: Passing `--auth` together with `--auth-profile` is not allowed." in result.stdout
+
+
+@pytest.mark.parametrize(
+    "cert_args",
+    (
+        ("--request-cert={client.pem}", "--request-cert-key={client.key}"),
+        ("--request-cert={client-bundle.pem}",),
+    ),
+)
+@pytest.mark.parametrize("workers", (1, 2))
+def test_request_cert(cli, mtls_server, tls_certificates, cert_args, workers):
+    # When the API requires a client certificate
+    schema_url = f"https://127.0.0.1:{mtls_server['port']}/schema.yaml"
+    # And it is passed via CLI
+    args = [arg.format(**tls_certificates) for arg in cert_args]
+    result = cli.run(schema_url, f"--request-tls-verify={tls_certificates['ca.pem']}", f"--workers={workers}", *args)
+    # Then the certificate is used to load the schema and to run tests
+    assert result.exit_code == ExitCode.OK, result.stdout
+    assert "== 1 passed in " in result.stdout.strip().split("\n")[-1]
+
+
+def test_request_cert_missing(cli, mtls_server, tls_certificates):
+    # When the API requires a client certificate, but it is not passed
+    schema_url = f"https://127.0.0.1:{mtls_server['port']}/schema.yaml"
+    result = cli.run(schema_url, f"--request-tls-verify={tls_certificates['ca.pem']}")
+    # Then the schema can't be loaded
+    assert result.exit_code == ExitCode.TESTS_FAILED, result.stdout
+    assert result.stdout.split("\n")[0] == f"Failed to load schema from {schema_url}"
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.parametrize("args, expected", (((), 1), (("--request-retries=2",), 3)))
+@pytest.mark.operations("throttled")
+def test_request_retries(cli, app, schema_url, openapi_version, args, expected):
+    # When the API responds with 429
+    result = cli.run(schema_url, "--hypothesis-max-examples=1", *args)
+    assert result.exit_code == ExitCode.OK, result.stdout
+    # Then requests are retried only if it is enabled
+    assert len(app["incoming_requests"]) == expected
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("path_variable")
+def test_rate_limit_default_deadline(cli, app, schema_url, openapi_version):
+    # When requests wait for the rate limiter longer than the default Hypothesis deadline
+    result = cli.run(schema_url, "--hypothesis-max-examples=3", "--rate-limit=1/s")
+    # Then tests don't fail because of it
+    assert result.exit_code == ExitCode.OK, result.stdout
+    assert "DeadlineExceeded" not in result.stdout
+
+
+@pytest.mark.parametrize("workers", (1, 2))
+@pytest.mark.operations("success")
+def test_resolve(cli, server, openapi_3_app, workers):
+    # When a host name is mapped to a specific address
+    # Then it is used to load the schema and to run tests
+    schema_url = f"http://schemathesis.test:{server['port']}/schema.yaml"
+    result = cli.run(schema_url, "--resolve=schemathesis.test:127.0.0.1", f"--workers={workers}")
+    assert result.exit_code == ExitCode.OK, result.stdout
+    assert "== 1 passed in " in result.stdout.strip().split("\n")[-1]
+    # And the original host name is sent in the `Host` header
+    assert openapi_3_app["incoming_requests"][0].headers["Host"] == f"schemathesis.test:{server['port']}"
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("path_variable", "success")
+def test_max_duration(cli, schema_url, openapi_version):
+    # When the time budget for the whole run runs out
+    result = cli.run(schema_url, "--max-duration=1", "--hypothesis-max-examples=100000")
+    # Then the run is not considered failed
+    assert result.exit_code == ExitCode.OK, result.stdout
+    lines = result.stdout.strip().split("\n")
+    # And it is displayed why the remaining API operations are not tested
+    assert any("Max duration of 1s is reached" in line for line in lines)
+    # And only the tested API operations are counted
+    assert "== 1 passed in " in lines[-1]
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("failure", "success")
+def test_shard(cli, schema_url, openapi_version):
+    # When only the second part out of two is tested
+    result = cli.run(schema_url, "--shard=2/2")
+    # Then only API operations from this part are tested
+    assert result.exit_code == ExitCode.OK, result.stdout
+    lines = result.stdout.strip().split("\n")
+    assert "Shard: 2/2" in lines
+    assert "== 1 passed in " in lines[-1]
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("failure", "success")
+def test_shard_durations(cli, schema_url, openapi_version, tmp_path):
+    # When the passing operation took most of the time in the previous run
+    report = tmp_path / "junit.xml"
+    report.write_text(
+        """<testsuites><testsuite name="schemathesis">
+<testcase name="GET /api/failure" time="1"/><testcase name="GET /api/success" time="10"/>
+</testsuite></testsuites>"""
+    )
+    result = cli.run(schema_url, "--shard=1/2", f"--shard-durations={report}")
+    # Then parts are balanced by the durations, and the first part contains only the slowest operation
+    assert result.exit_code == ExitCode.OK, result.stdout
+    assert "== 1 passed in " in result.stdout.strip().split("\n")[-1]
+
+
+@pytest.mark.parametrize(
+    "content, message",
+    (
+        (None, "Invalid value: `--shard-durations` can be used only together with `--shard`."),
+        ("<testsuites>", "Invalid value: Invalid JUnit XML report at "),
+    ),
+)
+def test_shard_durations_invalid(cli, tmp_path, content, message):
+    report = tmp_path / "junit.xml"
+    report.write_text(content or "<testsuites/>")
+    args = [f"--shard-durations={report}"]
+    if content is not None:
+        args.append("--shard=1/2")
+    result = cli.run("http://127.0.0.1", *args)
+    assert result.exit_code == ExitCode.INTERRUPTED, result.stdout
+    assert message in result.stdout
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("success", "failure")
+def test_last_failed(cli, schema_url, openapi_version, tmp_path):
+    state_path = tmp_path / "run-state.json"
+    # When a run has a failure
+    result = cli.run(schema_url, f"--run-state-file={state_path}", "--hypothesis-seed=1")
+    assert result.exit_code == ExitCode.TESTS_FAILED, result.stdout
+    # Then it is stored in the run state file
+    state = json.loads(state_path.read_text())
+    assert len(state["failed"]) == 1
+    failed = state["failed"][0]
+    assert failed["reference"] == "GET /api/failure"
+    assert failed["data_generation_method"] == "positive"
+    assert failed["seed"] == 1
+    assert len(failed["examples"]) == 1
+    # And when only the failed operations are tested again
+    result = cli.run(schema_url, f"--run-state-file={state_path}", "--last-failed")
+    assert result.exit_code == ExitCode.TESTS_FAILED, result.stdout
+    lines = result.stdout.strip().split("\n")
+    assert not any(line.startswith("GET /api/success") for line in lines)
+    assert "== 1 failed in " in lines[-1]
+    # And when they are tested first
+    result = cli.run(schema_url, f"--run-state-file={state_path}", "--failed-first")
+    assert result.exit_code == ExitCode.TESTS_FAILED, result.stdout
+    operations = [line.split(" ")[1] for line in result.stdout.split("\n") if line.startswith("GET /api/")]
+    assert operations == ["/api/failure", "/api/success"]
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("success")
+def test_last_failed_fixed(cli, schema_url, openapi_version, tmp_path):
+    # When a previously failed operation passes now
+    state_path = tmp_path / "run-state.json"
+    state_path.write_text(
+        '{"failed": [{"reference": "GET /api/success", "data_generation_method": "positive", "seed": null}]}'
+    )
+    result = cli.run(schema_url, f"--run-state-file={state_path}", "--last-failed")
+    assert result.exit_code == ExitCode.OK, result.stdout
+    # Then it is removed from the run state
+    assert json.loads(state_path.read_text()) == {"failed": []}
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("failure")
+def test_run_state_dry_run(cli, schema_url, openapi_version, tmp_path):
+    # Dry runs don't check anything, and they don't overwrite the run state
+    state_path = tmp_path / "run-state.json"
+    result = cli.run(schema_url, f"--run-state-file={state_path}", "--dry-run")
+    assert result.exit_code == ExitCode.OK, result.stdout
+    assert not state_path.exists()
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("failure")
+@pytest.mark.parametrize("args, is_stored", (((), False), (("--failed-first",), True)))
+def test_run_state_default_file(cli, schema_url, openapi_version, tmp_path, monkeypatch, args, is_stored):
+    monkeypatch.chdir(tmp_path)
+    # When the run state file is not passed
+    result = cli.run(schema_url, *args)
+    assert result.exit_code == ExitCode.TESTS_FAILED, result.stdout
+    # Then failures are stored in the default file only if the run state is used
+    assert (tmp_path / ".schemathesis" / "run-state.json").exists() is is_stored
+
+
+def test_invalid_run_state(cli, tmp_path):
+    state_path = tmp_path / "run-state.json"
+    state_path.write_text("{")
+    # When the run state should be used, but it is corrupted
+    result = cli.run("http://127.0.0.1:1", f"--run-state-file={state_path}", "--last-failed")
+    # Then it causes a validation error
+    assert result.exit_code == ExitCode.INTERRUPTED, result.stdout
+    assert "Invalid value: Invalid run state file at " in result.stdout
+
+
+@pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
+@pytest.mark.operations("success")
+def test_invalid_run_state_not_used(cli, schema_url, openapi_version, tmp_path):
+    state_path = tmp_path / "run-state.json"
+    state_path.write_text("{")
+    # When the run state is corrupted, but it is not used in this run
+    result = cli.run(schema_url, f"--run-state-file={state_path}")
+    # Then it is overwritten
+    assert result.exit_code == ExitCode.OK, result.stdout
+    assert json.loads(state_path.read_text()) == {"failed": []}
+
+
+def test_request_cert_key_without_cert(cli, tls_certificates):
+    # When ``--request-cert-key`` is passed without ``--request-cert``
+    result = cli.run("http://127.0.0.1", f"--request-cert-key={tls_certificates['client.key']}")
+    # Then it causes a validation error
+    assert result.exit_code == ExitCode.INTERRUPTED
+    assert "Invalid value: Passing `--request-cert-key` without `--request-cert` is not allowed." in result.stdout
+
+
 @pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
 @pytest.mark.operations("failure", "success")
 def test_exit_first(cli, schema_url, openapi_version):
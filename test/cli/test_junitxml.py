@@ -65,3 +65,15 @@ def test_junitxml_file(cli, schema_url, hypothesis_max_examples, tmp_path):
     assert testcases[2][0].tag == "error"
     assert testcases[2][0].attrib["type"] == "error"
     assert "Unable to satisfy schema parameters for this API operation" in testcases[2][0].attrib["message"]
+
+
+@pytest.mark.operations("success", "failure")
+def test_junitxml_shard(cli, schema_url, tmp_path):
+    xml_path = tmp_path / "junit.xml"
+    cli.run(schema_url, f"--junit-xml={xml_path}", "--shard=2/2")
+    testsuite = ElementTree.parse(xml_path).getroot()[0]
+    # The shard is stored as a test suite property
+    properties = testsuite.find("properties")
+    assert [(item.attrib["name"], item.attrib["value"]) for item in properties] == [("shard", "2/2")]
+    # And only operations from this shard are reported
+    assert [testcase.attrib["name"] for testcase in testsuite.iter("testcase")] == ["GET /api/success"]
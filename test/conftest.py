@@ -1,3 +1,5 @@
+import ssl
+import subprocess
 from textwrap import dedent
 
 import pytest
@@ -124,6 +126,41 @@ def server(_app):
     yield {"port": port}
 
 
+@pytest.fixture(scope="session")
+def tls_certificates(tmp_path_factory):
+    """A CA with server and client certificates, signed by it."""
+    directory = tmp_path_factory.mktemp("tls")
+
+    def openssl(command):
+        subprocess.run(
+            ["openssl", *command.split()], cwd=directory, check=True, stdout=subprocess.PIPE, stderr=subprocess.PIPE
+        )
+
+    openssl("req -x509 -newkey rsa:2048 -nodes -days 1 -subj /CN=ca -keyout ca.key -out ca.pem")
+    for name, extension in (("server", "subjectAltName=IP:127.0.0.1"), ("client", "extendedKeyUsage=clientAuth")):
+        (directory / f"{name}.ext").write_text(extension)
+        openssl(f"req -newkey rsa:2048 -nodes -subj /CN={name} -keyout {name}.key -out {name}.csr")
+        openssl(
+            f"x509 -req -days 1 -in {name}.csr -CA ca.pem -CAkey ca.key -CAcreateserial "
+            f"-extfile {name}.ext -out {name}.pem"
+        )
+    # A certificate bundled together with its private key
+    bundle = (directory / "client.pem").read_text() + (directory / "client.key").read_text()
+    (directory / "client-bundle.pem").write_text(bundle)
+    return {path.name: str(path) for path in directory.iterdir()}
+
+
+@pytest.fixture(scope="session")
+def mtls_server(tls_certificates):
+    """Run an app that requires clients to present a certificate, signed by the test CA."""
+    app = openapi._aiohttp.create_app(("success",), OpenAPIVersion("3.0"))
+    context = ssl.create_default_context(ssl.Purpose.CLIENT_AUTH, cafile=tls_certificates["ca.pem"])
+    context.load_cert_chain(tls_certificates["server.pem"], tls_certificates["server.key"])
+    context.verify_mode = ssl.CERT_REQUIRED
+    port = run_aiohttp_server(app, ssl_context=context)
+    yield {"port": port}
+
+
 @pytest.fixture()
 def base_url(server, app):
     """Base URL for the running application."""
@@ -255,6 +292,37 @@ def empty_open_api_3_schema():
     }
 
 
+@pytest.fixture
+def open_api_3_schema_with_discriminator(empty_open_api_3_schema):
+    pet = {
+        "oneOf": [{"$ref": "#/components/schemas/Cat"}, {"$ref": "#/components/schemas/Dog"}],
+        "discriminator": {"propertyName": "petType", "mapping": {"cat": "#/components/schemas/Cat", "dog": "Dog"}},
+    }
+    empty_open_api_3_schema["paths"] = {
+        "/pets": {
+            "post": {
+                "requestBody": {"required": True, "content": {"application/json": {"schema": pet}}},
+                "responses": {"200": {"description": "OK", "content": {"application/json": {"schema": pet}}}},
+            }
+        }
+    }
+    empty_open_api_3_schema["components"] = {
+        "schemas": {
+            "Cat": {
+                "type": "object",
+                "properties": {"petType": {"type": "string"}, "lives": {"type": "integer"}},
+                "required": ["petType", "lives"],
+            },
+            "Dog": {
+                "type": "object",
+                "properties": {"petType": {"type": "string"}, "bark": {"type": "boolean"}},
+                "required": ["petType", "bark"],
+            },
+        }
+    }
+    return empty_open_api_3_schema
+
+
 @pytest.fixture
 def open_api_3_schema_with_recoverable_errors(empty_open_api_3_schema):
     empty_open_api_3_schema["paths"] = {
@@ -493,6 +561,17 @@ def schema_with_recursive_references():
     }
 
 
+@pytest.fixture
+def schema_with_required_recursive_references(schema_with_recursive_references):
+    # Every node requires another node - there is no way to generate a finite value
+    schema_with_recursive_references["components"]["schemas"]["Node"] = {
+        "type": "object",
+        "required": ["child"],
+        "properties": {"child": {"$ref": "#/components/schemas/Node"}},
+    }
+    return schema_with_recursive_references
+
+
 @pytest.fixture(name="get_schema_path")
 def _get_schema_path():
     return get_schema_path
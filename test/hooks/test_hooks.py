@@ -2,6 +2,7 @@ import pytest
 from hypothesis import HealthCheck, given, settings
 
 import schemathesis
+from schemathesis.auth import OAuth2Auth, OAuth2Config
 from schemathesis.hooks import HookContext, HookDispatcher, HookScope
 
 
@@ -368,3 +369,101 @@ def test_deprecated_attribute():
     assert str(records[0].message) == (
         "Property `endpoint` is deprecated and will be removed in Schemathesis 4.0. Use `operation` instead."
     )
+
+
+def sign_request(context, request):
+    request.headers["X-Signature"] = f"{request.method} {request.path_url}"
+    request.headers["User-Agent"] = "Signed"
+
+
+@pytest.mark.operations("headers")
+def test_sign_request(openapi3_schema_url):
+    schema = schemathesis.from_uri(openapi3_schema_url)
+    schema.hooks.register("sign_request")(sign_request)
+    case = schema["/headers"]["GET"].make_case(query={"key": "value"})
+    response = case.call()
+    # The hook receives the final request
+    assert response.json()["X-Signature"] == "GET /api/headers?key=value"
+    assert response.json()["User-Agent"] == "Signed"
+    assert response.request.headers["X-Signature"] == "GET /api/headers?key=value"
+
+
+@pytest.mark.operations("headers")
+def test_sign_replayed_request(openapi3_schema_url):
+    schema = schemathesis.from_uri(openapi3_schema_url)
+    schema.hooks.register("sign_request")(sign_request)
+    case = schema["/headers"]["GET"].make_case(query={"key": "value"})
+    request = case.call().request.copy()
+    request.headers["X-Signature"] = "outdated"
+    # When a modified request is replayed, e.g. by a check
+    response = case.replay(request)
+    # Then it is signed again
+    assert response.json()["X-Signature"] == "GET /api/headers?key=value"
+
+
+@pytest.mark.operations("headers")
+def test_replay_without_signing(openapi3_schema_url):
+    schema = schemathesis.from_uri(openapi3_schema_url)
+    schema.hooks.register("sign_request")(sign_request)
+    case = schema["/headers"]["GET"].make_case()
+    request = case.call().request.copy()
+    del request.headers["X-Signature"]
+    # When a request that should not carry valid credentials is replayed
+    response = case.replay(request, sign=False)
+    # Then the signers are not called
+    assert "X-Signature" not in response.json()
+
+
+def sign_authorization(context, request):
+    request.headers["X-Signature"] = request.headers.get("Authorization", "")
+
+
+@pytest.mark.operations("oauth2")
+def test_sign_request_oauth2_retry(openapi_3_app, openapi3_schema_url):
+    # When the API rejects access tokens after they were used once
+    openapi_3_app["oauth2"]["max_token_uses"] = 1
+    schema = schemathesis.from_uri(openapi3_schema_url)
+    schema.hooks.register("sign_request")(sign_authorization)
+    auth = OAuth2Auth.from_schema(schema, OAuth2Config(client_id="client", client_secret="secret"))
+    case = schema["/oauth2"]["GET"].make_case()
+    case.call(auth=auth)
+    response = case.call(auth=auth)
+    # Then the request is retried with a new token
+    assert response.status_code == 200
+    assert response.history[0].status_code == 401
+    # And it is signed again
+    assert response.request.headers["X-Signature"] == response.request.headers["Authorization"] == "Bearer token-1"
+
+
+@pytest.mark.operations("oauth2")
+def test_oauth2_no_retry_with_other_credentials(openapi_3_app, openapi3_schema_url):
+    schema = schemathesis.from_uri(openapi3_schema_url)
+    auth = OAuth2Auth.from_schema(schema, OAuth2Config(client_id="client", client_secret="secret"))
+    case = schema["/oauth2"]["GET"].make_case()
+    # The copy keeps the authentication hooks of the original request
+    request = case.call(auth=auth).request.copy()
+    request.headers["Authorization"] = "Bearer unknown"
+    # When a request with a token that was not issued by `OAuth2Auth` is rejected
+    response = case.replay(request)
+    # Then it is not retried with a new token
+    assert response.status_code == 401
+    assert response.history == []
+    assert openapi_3_app["oauth2"]["tokens"] == ["token-0"]
+
+
+@pytest.mark.operations("headers")
+def test_sign_request_wsgi(schema):
+    schemathesis.hooks.register("sign_request")(sign_request)
+    case = schema["/headers"]["GET"].make_case()
+    response = case.call_wsgi()
+    assert response.json["X-Signature"] == "GET /api/headers"
+    assert response.json["User-Agent"] == "Signed"
+    assert response.request.headers["X-Signature"] == "GET /api/headers"
+
+
+@pytest.mark.operations("headers")
+def test_no_request_signers(openapi3_schema_url):
+    schema = schemathesis.from_uri(openapi3_schema_url)
+    case = schema["/headers"]["GET"].make_case()
+    response = case.call()
+    assert "X-Signature" not in response.json()
@@ -4,7 +4,7 @@ import json
 import pytest
 
 from schemathesis.specs.openapi import loaders
-from schemathesis.specs.openapi.schemas import OpenApi30, SwaggerV20
+from schemathesis.specs.openapi.schemas import OpenApi30, OpenApi31, SwaggerV20
 
 
 def test_openapi_asgi_loader(fastapi_app, run_asgi_test):
@@ -28,6 +28,7 @@ def test_openapi_wsgi_loader(flask_app, run_wsgi_test):
     (
         ("20", SwaggerV20),
         ("30", OpenApi30),
+        ("31", OpenApi31),
     ),
 )
 def test_force_open_api_version(version, expected):
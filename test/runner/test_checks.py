@@ -3,19 +3,29 @@ from typing import Any, Dict, Optional
 
 import pytest
 import requests
+from requests.auth import HTTPDigestAuth
+from requests.hooks import default_hooks
 from hypothesis import given, settings
 
 import schemathesis
 from schemathesis import models
 from schemathesis.checks import (
+    ALL_CHECKS,
     content_type_conformance,
+    ensure_resource_availability,
+    ignored_auth,
+    make_object_level_authorization_check,
+    negative_data_rejection,
     not_a_server_error,
+    response_headers_conformance,
     response_schema_conformance,
     status_code_conformance,
+    use_after_free,
 )
 from schemathesis.exceptions import CheckFailed, InvalidSchema
-from schemathesis.models import OperationDefinition
+from schemathesis.models import CaseSource, OperationDefinition
 from schemathesis.schemas import BaseSchema
+from schemathesis.stateful import DeletedResources
 
 
 def make_case(schema: BaseSchema, definition: Dict[str, Any]) -> models.Case:
@@ -208,6 +218,322 @@ def test_not_a_server_error(value, swagger_20):
     assert exc_info.type.__name__ == "CheckFailed"
 
 
+@pytest.mark.parametrize("value", (200, 201))
+def test_negative_data_rejection_accepted(value, swagger_20):
+    response = make_response(status_code=value)
+    case = make_case(swagger_20, {})
+    case.mutation = "property `age` had type string instead of integer"
+    with pytest.raises(AssertionError, match="property `age` had type string instead of integer") as exc_info:
+        negative_data_rejection(response, case)
+    assert exc_info.type.__name__ == "CheckFailed"
+
+
+@pytest.mark.parametrize(
+    "status_code, mutation",
+    (
+        (400, "required header `X-Token` was missing"),
+        (302, "required header `X-Token` was missing"),
+        (200, None),
+    ),
+)
+def test_negative_data_rejection_passed(status_code, mutation, swagger_20):
+    # When the invalid data is not accepted, or the data is not invalid
+    response = make_response(status_code=status_code)
+    case = make_case(swagger_20, {})
+    case.mutation = mutation
+    # Then the check passes
+    assert negative_data_rejection(response, case) is None
+
+
+def test_negative_data_rejection_different_mutations(swagger_20):
+    # Different mutations should be reported as different failures
+    response = make_response()
+    case = make_case(swagger_20, {})
+    exceptions = []
+    for mutation in ("required header `X-Token` was missing", "property `age` was less than the minimum of 0"):
+        case.mutation = mutation
+        with pytest.raises(AssertionError) as exc_info:
+            negative_data_rejection(response, case)
+        exceptions.append(exc_info.type)
+    assert exceptions[0] is not exceptions[1]
+
+
+def make_deleted_by(schema: BaseSchema) -> CaseSource:
+    operation = models.APIOperation(
+        "/users/{user_id}", "DELETE", definition=OperationDefinition({}, {}, None, []), schema=schema
+    )
+    case = models.Case(operation, path_parameters={"user_id": 42})
+    return CaseSource(case=case, response=make_response(status_code=204))
+
+
+@pytest.mark.parametrize("value", (200, 201))
+def test_use_after_free_failed(value, swagger_20):
+    response = make_response(status_code=value)
+    case = make_case(swagger_20, {})
+    case.deleted_by = make_deleted_by(swagger_20)
+    # When a deleted resource is successfully accessed
+    # Then it should be reported together with the call sequence
+    with pytest.raises(CheckFailed, match="Resource `/users/42` is available after it was deleted") as exc_info:
+        use_after_free(response, case)
+    assert f"1. DELETE /users/42 -> 204\n    2. GET /path -> {value}" in str(exc_info.value)
+
+
+@pytest.mark.parametrize("status_code, deleted", ((404, True), (410, True), (200, False)))
+def test_use_after_free_passed(status_code, deleted, swagger_20):
+    # When the deleted resource is not available, or nothing was deleted
+    response = make_response(status_code=status_code)
+    case = make_case(swagger_20, {})
+    if deleted:
+        case.deleted_by = make_deleted_by(swagger_20)
+    # Then the check passes
+    assert use_after_free(response, case) is None
+
+
+@pytest.mark.parametrize(
+    "method, path, status_code, expected",
+    (
+        ("GET", "/users/42", 204, True),
+        ("GET", "/users/42/posts", 204, True),
+        ("GET", "/users/421", 204, False),
+        ("GET", "/users/42", 404, False),
+        ("DELETE", "/users/42", 204, False),
+    ),
+)
+def test_deleted_resources(swagger_20, method, path, status_code, expected):
+    deleted_by = make_deleted_by(swagger_20)
+    resources = DeletedResources()
+    resources.store(deleted_by.case, make_response(status_code=status_code))
+    operation = models.APIOperation(path, method, definition=OperationDefinition({}, {}, None, []), schema=swagger_20)
+    # Only successful deletions are tracked and the resource's sub-paths are considered deleted too
+    assert (resources.get(models.Case(operation)) is not None) is expected
+
+
+def test_deleted_resources_recreated(swagger_20):
+    deleted_by = make_deleted_by(swagger_20)
+    resources = DeletedResources()
+    resources.store(deleted_by.case, deleted_by.response)
+    # When the deleted resource is created again via `PUT`
+    put = models.APIOperation("/users/42", "PUT", definition=OperationDefinition({}, {}, None, []), schema=swagger_20)
+    resources.store(models.Case(put), make_response(status_code=201))
+    # Then it is not considered deleted anymore
+    get = models.APIOperation("/users/42", "GET", definition=OperationDefinition({}, {}, None, []), schema=swagger_20)
+    assert resources.get(models.Case(get)) is None
+
+
+def make_linked_case(schema: BaseSchema, created: bool) -> models.Case:
+    create = models.APIOperation("/items", "POST", definition=OperationDefinition({}, {}, None, []), schema=schema)
+    operation = models.APIOperation(
+        "/items/{item_id}", "GET", definition=OperationDefinition({}, {}, None, []), schema=schema
+    )
+    case = models.Case(operation, path_parameters={"item_id": 5})
+    case.set_source(make_response(status_code=201 if created else 200), models.Case(create))
+    if created:
+        # Set by the stateful testing machinery for links from creating a resource to reading it
+        case.created_by = case.source
+    return case
+
+
+def test_ensure_resource_availability_failed(swagger_20):
+    case = make_linked_case(swagger_20, created=True)
+    # When a freshly created resource is not found
+    # Then it should be reported together with both calls
+    with pytest.raises(CheckFailed, match="Resource created by `POST /items` is not available") as exc_info:
+        ensure_resource_availability(make_response(status_code=404), case)
+    assert "1. POST /items -> 201\n    2. GET /items/5 -> 404" in str(exc_info.value)
+
+
+@pytest.mark.parametrize("created, status_code", ((True, 200), (False, 404)))
+def test_ensure_resource_availability_passed(swagger_20, created, status_code):
+    # When the resource is available, or the linked call doesn't read a created resource
+    case = make_linked_case(swagger_20, created)
+    # Then the check passes
+    assert ensure_resource_availability(make_response(status_code=status_code), case) is None
+
+
+def test_ensure_resource_availability_no_source(swagger_20):
+    # Non-stateful tests are not affected
+    case = make_case(swagger_20, {})
+    assert ensure_resource_availability(make_response(status_code=404), case) is None
+
+
+AUTH_PROFILES = {
+    "alice": {"Authorization": "Bearer alice"},
+    "bob": {"Authorization": "Bearer bob"},
+    "eve": {"X-Token": "eve"},
+}
+
+
+def make_owner_response() -> requests.Response:
+    response = make_response()
+    response.request = requests.Request(
+        "GET", "http://127.0.0.1/items/5", headers={"Authorization": "Bearer alice", "X-Trace": "1"}
+    ).prepare()
+    return response
+
+
+def test_broken_object_level_authorization_failed(mocker, swagger_20):
+    replay = mocker.patch(
+        "schemathesis.models.Case.replay", side_effect=[make_response(status_code=403), make_response()]
+    )
+    check = make_object_level_authorization_check(AUTH_PROFILES)
+    case = make_linked_case(swagger_20, "GET", 201)
+    # When a resource of the first identity is available to another identity
+    # Then it should be reported together with the name of that identity
+    with pytest.raises(CheckFailed, match="`eve` has access to a resource of `alice`") as exc_info:
+        check(make_owner_response(), case)
+    assert "1. POST /items -> 201\n    2. GET /items/5 -> 200" in str(exc_info.value)
+    # And the request should be replayed with credentials of each other identity instead of the original ones
+    bob, eve = (call[0][0] for call in replay.call_args_list)
+    assert bob.headers["Authorization"] == "Bearer bob"
+    assert "Authorization" not in eve.headers
+    assert eve.headers["X-Token"] == "eve"
+    assert eve.headers["X-Trace"] == "1"
+
+
+def test_broken_object_level_authorization_digest(mocker, swagger_20):
+    replay = mocker.patch("schemathesis.models.Case.replay", return_value=make_response(status_code=404))
+    check = make_object_level_authorization_check(AUTH_PROFILES)
+    response = make_owner_response()
+    # When the owner's request was sent with Digest authentication
+    response.request.prepare_auth(HTTPDigestAuth("alice", "secret"))
+    assert check(response, make_linked_case(swagger_20, "GET", 201)) is None
+    # Then requests of other identities are sent without its hooks, which would re-authenticate them as the owner
+    for call in replay.call_args_list:
+        assert call[0][0].hooks == default_hooks()
+
+
+@pytest.mark.parametrize(
+    "method, status_code, linked",
+    (
+        ("GET", 403, True),
+        ("DELETE", 204, True),
+        ("POST", 200, True),
+        ("GET", 200, False),
+    ),
+)
+def test_broken_object_level_authorization_not_applicable(mocker, swagger_20, method, status_code, linked):
+    replay = mocker.patch("schemathesis.models.Case.replay", return_value=make_response())
+    check = make_object_level_authorization_check(AUTH_PROFILES)
+    case = make_linked_case(swagger_20, method, 201) if linked else make_case(swagger_20, {})
+    response = make_owner_response()
+    response.status_code = status_code
+    # When the original call failed, deleted the resource, or doesn't use any resources from previous calls
+    # Then the request is not replayed
+    assert check(response, case) is None
+    replay.assert_not_called()
+
+
+def test_broken_object_level_authorization_passed(mocker, swagger_20):
+    mocker.patch("schemathesis.models.Case.replay", return_value=make_response(status_code=404))
+    check = make_object_level_authorization_check(AUTH_PROFILES)
+    # When other identities don't have access to the resource
+    # Then the check passes
+    assert check(make_owner_response(), make_linked_case(swagger_20, "PATCH", 201)) is None
+
+
+@pytest.mark.parametrize("status_codes, is_failed", (((403, 404), False), ((403, 204), True)))
+def test_broken_object_level_authorization_delete(swagger_20, status_codes, is_failed):
+    check = make_object_level_authorization_check(AUTH_PROFILES)
+    case = make_linked_case(swagger_20, "DELETE", 201)
+    sent = []
+    responses = iter(status_codes)
+
+    def send(headers):
+        sent.append(headers)
+        return make_response(status_code=next(responses))
+
+    # When a linked resource is deleted
+    # Then the request is sent with credentials of each other identity before the owner's call
+    check.before_call(case, {"Authorization": "Bearer alice", "X-Trace": "1"}, send)
+    assert sent == [
+        {"Authorization": "Bearer bob", "X-Trace": "1"},
+        {"X-Token": "eve", "X-Trace": "1"},
+    ]
+    # And if another identity deleted the resource, it should be reported even if the owner's call failed
+    response = make_owner_response()
+    response.status_code = 404
+    if is_failed:
+        with pytest.raises(CheckFailed, match="`eve` has access to a resource of `alice`"):
+            check(response, case)
+    else:
+        assert check(response, case) is None
+
+
+@pytest.fixture
+def secured_case(empty_open_api_3_schema):
+    empty_open_api_3_schema["paths"] = {
+        "/items": {"get": {"security": [{"token": []}], "responses": {"200": {"description": "OK"}}}}
+    }
+    empty_open_api_3_schema["components"] = {
+        "securitySchemes": {"token": {"type": "apiKey", "in": "header", "name": "X-Token"}}
+    }
+    return schemathesis.from_dict(empty_open_api_3_schema)["/items"]["GET"].make_case()
+
+
+def make_authenticated_response(status_code: int = 200) -> requests.Response:
+    response = make_response(status_code=status_code)
+    response.request = requests.Request("GET", "http://127.0.0.1/items", headers={"X-Token": "secret"}).prepare()
+    return response
+
+
+def test_ignored_auth_failed(mocker, secured_case):
+    replay = mocker.patch(
+        "schemathesis.models.Case.replay",
+        side_effect=[make_response(status_code=401), make_response(), make_response(status_code=401)],
+    )
+    # When the API accepts a request with malformed credentials
+    # Then it should be reported together with that request
+    with pytest.raises(CheckFailed, match="Authentication is not enforced for `GET /items`") as exc_info:
+        ignored_auth(make_authenticated_response(), secured_case)
+    assert "    - with malformed credentials -> 200" in str(exc_info.value)
+    assert "X-Token: schemathesis-malformed-credentials" in str(exc_info.value)
+    # And credentials are modified in each replayed request
+    without, malformed, foreign = (call[0][0] for call in replay.call_args_list)
+    assert "X-Token" not in without.headers
+    assert malformed.headers["X-Token"] == "schemathesis-malformed-credentials"
+    assert "X-Token" not in foreign.headers
+    assert foreign.headers["Authorization"].startswith("Basic ")
+
+
+def test_ignored_auth_digest(mocker, secured_case):
+    replay = mocker.patch("schemathesis.models.Case.replay", return_value=make_response(status_code=401))
+    response = make_authenticated_response()
+    # When the original request was sent with Digest authentication
+    response.request.prepare_auth(HTTPDigestAuth("test", "test"))
+    # Then the check passes if the API rejects modified requests
+    assert ignored_auth(response, secured_case) is None
+    assert replay.call_count == 3
+    for call in replay.call_args_list:
+        # And they are sent without the Digest hooks, which would answer the 401 response with valid credentials
+        assert call[0][0].hooks == default_hooks()
+        # And they are not signed, as signers could add valid credentials back
+        assert call[1] == {"sign": False}
+    # And the original request keeps its hooks
+    assert response.request.hooks != default_hooks()
+
+
+def test_ignored_auth_passed(mocker, secured_case):
+    mocker.patch("schemathesis.models.Case.replay", return_value=make_response(status_code=401))
+    # When all modified requests are rejected
+    # Then the check passes
+    assert ignored_auth(make_authenticated_response(), secured_case) is None
+
+
+@pytest.mark.parametrize("is_secured, status_code", ((True, 401), (False, 200)))
+def test_ignored_auth_not_applicable(mocker, request, swagger_20, is_secured, status_code):
+    replay = mocker.patch("schemathesis.models.Case.replay", return_value=make_response())
+    case = request.getfixturevalue("secured_case") if is_secured else make_case(swagger_20, {})
+    # When the original request failed, or the API operation has no security requirements
+    # Then no requests are replayed
+    assert ignored_auth(make_authenticated_response(status_code), case) is None
+    replay.assert_not_called()
+
+
+def test_ignored_auth_not_in_all_checks():
+    # It sends additional requests for every test case, therefore it should be selected explicitly
+    assert ignored_auth not in ALL_CHECKS
+
+
 @pytest.mark.parametrize("value", (400, 405))
 def test_status_code_conformance_valid(value, swagger_20):
     response = make_response()
@@ -480,3 +806,151 @@ def test_response_schema_conformance_references_valid(complex_schema, value):
         case.validate_response(response)
 
     test()
+
+
+@pytest.mark.parametrize("spec", ("swagger_20", "openapi_30"))
+def test_response_schema_conformance_write_only(request, spec):
+    # When a response schema contains a required write-only property
+    schema = request.getfixturevalue(spec)
+    response_schema = {
+        "type": "object",
+        "properties": {"id": {"type": "integer"}, "password": {"type": "string", "writeOnly": True}},
+        "required": ["id", "password"],
+        "additionalProperties": False,
+    }
+    if spec == "swagger_20":
+        definition = {"responses": {"200": {"description": "text", "schema": response_schema}}}
+    else:
+        definition = {
+            "responses": {"200": {"description": "text", "content": {"application/json": {"schema": response_schema}}}}
+        }
+    case = make_case(schema, definition)
+    # Then it is not expected in responses
+    response_schema_conformance(make_response(b'{"id": 1}'), case)
+    with pytest.raises(AssertionError):
+        response_schema_conformance(make_response(b'{"id": 1, "password": "secret"}'), case)
+
+
+@pytest.mark.parametrize(
+    "data, expected",
+    (
+        ({"petType": "dog", "lives": "many"}, "'bark' is a required property"),
+        ({"petType": "cat", "bark": 1}, "'lives' is a required property"),
+    ),
+)
+def test_response_schema_conformance_discriminator(open_api_3_schema_with_discriminator, data, expected):
+    # When the response schema uses `oneOf` with a discriminator
+    schema = schemathesis.from_dict(open_api_3_schema_with_discriminator)
+    case = schema["/pets"]["POST"].make_case(body={"petType": "cat", "lives": 9})
+    response = make_response(json.dumps(data).encode())
+    # Then the error comes from the variant selected by the discriminator
+    with pytest.raises(CheckFailed, match=expected):
+        response_schema_conformance(response, case)
+
+
+def make_headers_case(schema, headers):
+    if "swagger" in schema.raw_schema:
+        # Header objects in Open API 2.0 are schema-like themselves
+        headers = {name: {**definition.pop("schema"), **definition} for name, definition in headers.items()}
+    return make_case(schema, {"responses": {"200": {"description": "OK", "headers": headers}}})
+
+
+def make_headers_response(**headers):
+    response = make_response()
+    response.headers.update(headers)
+    return response
+
+
+@pytest.mark.parametrize("spec", ("swagger_20", "openapi_30"))
+@pytest.mark.parametrize(
+    "header_schema, value",
+    (
+        ({"type": "integer"}, "42"),
+        ({"type": "number"}, "4.2"),
+        ({"type": "boolean"}, "true"),
+        ({"type": "array", "items": {"type": "integer"}}, "1, 2,3"),
+        ({"type": "string", "enum": ["foo"]}, "foo"),
+        ({"type": "string", "format": "uri"}, "https://example.com/items/1"),
+    ),
+)
+def test_response_headers_conformance_valid(request, spec, header_schema, value):
+    schema = request.getfixturevalue(spec)
+    case = make_headers_case(schema, {"X-Header": {"schema": header_schema}})
+    # When the header value conforms to its schema
+    # Then there is no error
+    response_headers_conformance(make_headers_response(**{"X-Header": value}), case)
+
+
+@pytest.mark.parametrize("spec", ("swagger_20", "openapi_30"))
+@pytest.mark.parametrize(
+    "header_schema, value, expected",
+    (
+        ({"type": "integer"}, "foo", "'foo' is not of type 'integer'"),
+        ({"type": "integer", "maximum": 10}, "42", "42 is greater than the maximum of 10"),
+        ({"type": "array", "items": {"type": "integer"}}, "1,foo", "'foo' is not of type 'integer'"),
+        ({"type": "string", "format": "date"}, "yesterday", "'yesterday' is not a 'date'"),
+        ({"type": "string", "format": "uri"}, "not a uri", "'not a uri' is not a 'uri'"),
+    ),
+)
+def test_response_headers_conformance_invalid(request, spec, header_schema, value, expected):
+    schema = request.getfixturevalue(spec)
+    case = make_headers_case(schema, {"X-Header": {"schema": header_schema}})
+    # When the header value does not conform to its schema
+    # Then it should be reported
+    with pytest.raises(CheckFailed, match=expected):
+        response_headers_conformance(make_headers_response(**{"X-Header": value}), case)
+
+
+COLOR_HEADER_SCHEMA = {"type": "object", "properties": {"r": {"type": "integer"}, "g": {"type": "integer"}}}
+
+
+@pytest.mark.parametrize(
+    "definition, valid, invalid",
+    (
+        ({"schema": COLOR_HEADER_SCHEMA, "explode": True}, "r=100,g=200", "r=100,g=foo"),
+        ({"schema": COLOR_HEADER_SCHEMA}, "r,100,g,200", "r,100,g,foo"),
+        (
+            {"content": {"application/json": {"schema": COLOR_HEADER_SCHEMA}}},
+            '{"r": 100, "g": 200}',
+            '{"r": 100, "g": "foo"}',
+        ),
+    ),
+)
+def test_response_headers_conformance_objects(openapi_30, definition, valid, invalid):
+    case = make_headers_case(openapi_30, {"X-Color": definition})
+    # When the header contains a serialized object
+    # Then it should be deserialized according to its definition before validation
+    response_headers_conformance(make_headers_response(**{"X-Color": valid}), case)
+    with pytest.raises(CheckFailed, match="'foo' is not of type 'integer'"):
+        response_headers_conformance(make_headers_response(**{"X-Color": invalid}), case)
+
+
+@pytest.mark.parametrize("required", (True, False))
+def test_response_headers_conformance_required(openapi_30, required):
+    case = make_headers_case(openapi_30, {"X-Header": {"schema": {"type": "integer"}, "required": required}})
+    response = make_response()
+    # When a declared header is missing
+    if required:
+        # Then it is reported only if it is required
+        with pytest.raises(CheckFailed, match="Received a response with missing headers: X-Header"):
+            response_headers_conformance(response, case)
+    else:
+        response_headers_conformance(response, case)
+
+
+def test_response_headers_conformance_content_type(openapi_30):
+    case = make_headers_case(openapi_30, {"Content-Type": {"schema": {"type": "integer"}, "required": True}})
+    # When the `Content-Type` response header is defined
+    # Then its definition is ignored
+    response_headers_conformance(make_response(), case)
+    response_headers_conformance(make_response(content_type=None), case)
+
+
+def test_response_headers_conformance_reference(openapi_30):
+    # When the header schema contains a non-inlined reference
+    openapi_30.raw_schema["components"] = {"schemas": {"Id": {"type": "integer"}}}
+    case = make_headers_case(openapi_30, {"X-Header": {"schema": {"$ref": "#/components/schemas/Id"}}})
+    # Then it is resolved during validation
+    response_headers_conformance(make_headers_response(**{"X-Header": "42"}), case)
+    with pytest.raises(CheckFailed, match="'foo' is not of type 'integer'"):
+        response_headers_conformance(make_headers_response(**{"X-Header": "foo"}), case)
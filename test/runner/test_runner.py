@@ -15,10 +15,12 @@ from requests.auth import HTTPDigestAuth
 import schemathesis
 from schemathesis._hypothesis import add_examples
 from schemathesis.checks import content_type_conformance, response_schema_conformance, status_code_conformance
-from schemathesis.constants import RECURSIVE_REFERENCE_ERROR_MESSAGE, USER_AGENT
+from schemathesis.constants import RECURSIVE_REFERENCE_ERROR_MESSAGE, USER_AGENT, DataGenerationMethod
 from schemathesis.models import Status
 from schemathesis.runner import ThreadPoolRunner, events, from_schema, get_requests_auth
 from schemathesis.runner.impl.core import get_wsgi_auth, reraise
+from schemathesis.runstate import FailedTest, RerunFailed, RunState
+from schemathesis.sharding import Shard
 from schemathesis.specs.graphql import loaders as gql_loaders
 from schemathesis.specs.openapi import loaders as oas_loaders
 
@@ -648,9 +650,20 @@ def test_url_joining(request, server, get_schema_path, schema_path):
     )
 
 
-def test_skip_operations_with_recursive_references(schema_with_recursive_references):
+def test_recursive_references(schema_with_recursive_references):
     # When the test schema contains recursive references
     schema = oas_loaders.from_dict(schema_with_recursive_references)
+    *_, after, finished = from_schema(
+        schema, dry_run=True, hypothesis_settings=hypothesis.settings(max_examples=10, deadline=None)
+    ).execute()
+    # Then data is generated with a limited recursion depth
+    assert after.status != Status.error, after.result.errors
+    assert not finished.has_errors
+
+
+def test_skip_operations_with_required_recursive_references(schema_with_required_recursive_references):
+    # When the test schema contains recursive references that can't be cut off
+    schema = oas_loaders.from_dict(schema_with_required_recursive_references)
     *_, after, finished = from_schema(schema).execute()
     # Then it causes an error with a proper error message
     assert after.status == Status.error
@@ -827,3 +840,97 @@ def test_graphql(graphql_url):
     )
     assert initialized.operations_count == 2
     assert finished.passed_count == 2
+
+
+@pytest.mark.operations("path_variable", "success")
+def test_max_duration(any_app, any_app_schema):
+    # When the time budget runs out while an API operation is tested
+    settings = hypothesis.settings(max_examples=100000, deadline=None)
+    *_, after, reached, finished = from_schema(any_app_schema, hypothesis_settings=settings, max_duration=0.5).execute()
+    # Then the test cases that were already executed are reported
+    assert after.status == Status.success
+    assert after.path == "/api/path_variable/{key}"
+    # And handlers are notified that the time is up
+    assert isinstance(reached, events.MaxDurationReached)
+    assert reached.max_duration == 0.5
+    # And the remaining API operations are not tested
+    assert_not_request(any_app, "GET", "/api/success")
+    assert finished.passed_count == 1
+
+
+@pytest.mark.operations("path_variable", "success")
+def test_max_duration_concurrent(real_app_schema):
+    # When the time budget runs out while multiple API operations are tested at the same time
+    settings = hypothesis.settings(max_examples=100000, deadline=None)
+    *_, reached, finished = from_schema(
+        real_app_schema, hypothesis_settings=settings, max_duration=0.5, workers_num=2
+    ).execute()
+    # Then tests that are in progress are finished and counted
+    assert isinstance(reached, events.MaxDurationReached)
+    assert finished.passed_count == 2
+
+
+@pytest.mark.operations("path_variable", "success")
+def test_max_operation_duration(real_app_schema):
+    # When the time for testing a single API operation is limited
+    settings = hypothesis.settings(max_examples=100000, deadline=None)
+    all_events = list(from_schema(real_app_schema, hypothesis_settings=settings, max_operation_duration=0.2).execute())
+    # Then each API operation is tested until its time is up
+    after = [event for event in all_events if isinstance(event, events.AfterExecution)]
+    assert [event.status for event in after] == [Status.success, Status.success]
+    assert after[0].elapsed_time < 1
+    # And the run is not stopped
+    assert not any(isinstance(event, events.MaxDurationReached) for event in all_events)
+
+
+@pytest.mark.parametrize("workers", (1, 2))
+@pytest.mark.operations("success", "failure", "multiple_failures")
+def test_shard(real_app_schema, workers):
+    tested = []
+    for index in (1, 2):
+        # When API operations are split into shards
+        initialized, *others, _ = from_schema(real_app_schema, shard=Shard(index, 2), workers_num=workers).execute()
+        paths = [event.path for event in others if isinstance(event, events.AfterExecution)]
+        # Then only the operations of the given shard are counted
+        assert initialized.operations_count == len(paths)
+        assert initialized.shard == f"{index}/2"
+        tested.extend(paths)
+    # And all operations are tested exactly once by all shards together
+    assert sorted(tested) == ["/api/failure", "/api/multiple_failures", "/api/success"]
+
+
+def make_previous_failures(only_failed):
+    # `GET /api/path_variable/{key}` failed in the previous run
+    failed = FailedTest(
+        reference="GET /api/path_variable/{key}",
+        data_generation_method=DataGenerationMethod.positive,
+        seed=42,
+        examples=[{"path_parameters": {"key": "replayed"}}],
+    )
+    return RerunFailed(state=RunState(failed=[failed]), only_failed=only_failed)
+
+
+@pytest.mark.parametrize("workers", (1, 2))
+@pytest.mark.operations("success", "path_variable")
+def test_last_failed(app, real_app_schema, workers):
+    # When only previously failed API operations are tested
+    rerun_failed = make_previous_failures(only_failed=True)
+    initialized, *others, _ = from_schema(real_app_schema, rerun_failed=rerun_failed, workers_num=workers).execute()
+    after = [event for event in others if isinstance(event, events.AfterExecution)]
+    # Then other operations are not tested
+    assert initialized.operations_count == 1
+    assert [event.path for event in after] == ["/api/path_variable/{key}"]
+    # And the previous seed is used
+    assert after[0].result.seed == 42
+    # And the failing test case is replayed before the generated ones
+    assert get_incoming_requests(app)[0].path == "/api/path_variable/replayed"
+
+
+@pytest.mark.operations("success", "path_variable")
+def test_failed_first(real_app_schema):
+    # When previously failed API operations are tested first
+    rerun_failed = make_previous_failures(only_failed=False)
+    all_events = from_schema(real_app_schema, rerun_failed=rerun_failed).execute()
+    # Then all operations are tested, starting from the failed ones
+    paths = [event.path for event in all_events if isinstance(event, events.AfterExecution)]
+    assert paths == ["/api/path_variable/{key}", "/api/success"]
@@ -2,7 +2,9 @@ import pytest
 from hypothesis import given, settings
 
 import schemathesis
-from schemathesis.specs.openapi._hypothesis import get_case_strategy
+from schemathesis import DataGenerationMethod
+from schemathesis._hypothesis import create_test, get_single_example
+from schemathesis.specs.openapi._hypothesis import get_boundary_values, get_case_strategy
 
 
 @pytest.fixture
@@ -51,3 +53,120 @@ def test_explicit_attributes(operation, values, expected):
             assert value == expected_values
 
     test()
+
+
+@pytest.mark.parametrize(
+    "schema, expected",
+    (
+        ({"type": "integer", "minimum": 1, "maximum": 10}, [1, 2, 10, 9]),
+        ({"type": "integer", "minimum": 1, "exclusiveMinimum": True}, [2, 3]),
+        ({"type": "number", "minimum": 0, "maximum": 1}, [0, 0.25, 1, 0.75]),
+        ({"type": "string", "minLength": 2, "maxLength": 3}, ["aa", "aaa"]),
+        ({"type": "string", "minLength": 2, "pattern": "^[0-9]+$"}, []),
+        ({"type": "string", "enum": ["A", "B"]}, ["A", "B"]),
+        ({"type": "array", "items": {"enum": ["A"]}, "maxItems": 1}, [["A"], []]),
+        ({"type": "object"}, []),
+    ),
+)
+def test_boundary_values(schema, expected):
+    assert get_boundary_values(schema) == expected
+
+
+def test_boundary_strategies(make_openapi_3_schema):
+    # When an API operation has constrained parameters and payload properties
+    raw_schema = make_openapi_3_schema(
+        body={
+            "required": True,
+            "content": {
+                "application/json": {
+                    "schema": {
+                        "type": "object",
+                        "properties": {"name": {"type": "string", "maxLength": 5}},
+                        "required": ["name"],
+                    }
+                }
+            },
+        },
+        parameters=[{"in": "query", "name": "limit", "schema": {"type": "integer", "minimum": 1, "maximum": 100}}],
+    )
+    operation = schemathesis.from_dict(raw_schema)["/users"]["POST"]
+    # Then each boundary value is used in a separate case
+    cases = [get_single_example(strategy) for strategy in operation.get_strategies_from_boundaries()]
+    assert [case.query["limit"] for case in cases[:4]] == [1, 2, 100, 99]
+    assert [case.body["name"] for case in cases[4:]] == ["aaaaa", "aaaa"]
+    # And the rest of the data is generated
+    assert all(isinstance(case.body, dict) and "name" in case.body for case in cases)
+    assert all(case.media_type == "application/json" for case in cases)
+
+
+def test_boundary_examples_run_first(make_openapi_3_schema):
+    raw_schema = make_openapi_3_schema(
+        parameters=[{"in": "query", "name": "id", "required": True, "schema": {"type": "integer", "minimum": 5}}],
+    )
+    operation = schemathesis.from_dict(raw_schema)["/users"]["POST"]
+    values = []
+
+    def test(case):
+        values.append(case.query["id"])
+
+    # When the boundary data generation method is used
+    test = create_test(
+        operation=operation,
+        test=test,
+        settings=settings(max_examples=1, database=None),
+        data_generation_method=DataGenerationMethod.boundary,
+    )
+    test()
+    # Then boundary values are tested before the randomly generated ones
+    assert values[:2] == [5, 6]
+
+
+def test_read_only_properties(make_openapi_3_schema):
+    # When a request body contains read-only properties
+    schema = make_openapi_3_schema(
+        body={
+            "required": True,
+            "content": {
+                "application/json": {
+                    "schema": {
+                        "type": "object",
+                        "properties": {
+                            "id": {"type": "integer", "readOnly": True},
+                            "name": {"type": "string"},
+                            "password": {"type": "string", "writeOnly": True},
+                        },
+                        "required": ["id", "name", "password"],
+                        "additionalProperties": False,
+                    }
+                }
+            },
+        }
+    )
+    operation = schemathesis.from_dict(schema)["/users"]["POST"]
+
+    @given(case=operation.as_strategy())
+    @settings(max_examples=10, deadline=None)
+    def test(case):
+        # Then they are not generated
+        assert "id" not in case.body
+        # And write-only properties are
+        assert set(case.body) == {"name", "password"}
+
+    test()
+
+
+def test_discriminator(open_api_3_schema_with_discriminator):
+    # When a request body uses `oneOf` with a discriminator
+    operation = schemathesis.from_dict(open_api_3_schema_with_discriminator)["/pets"]["POST"]
+
+    @given(case=operation.as_strategy())
+    @settings(max_examples=30, deadline=None)
+    def test(case):
+        # Then the discriminator value matches the generated variant
+        if case.body["petType"] == "cat":
+            assert isinstance(case.body["lives"], int)
+        else:
+            assert case.body["petType"] == "dog"
+            assert isinstance(case.body["bark"], bool)
+
+    test()
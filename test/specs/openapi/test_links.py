@@ -9,7 +9,7 @@ from schemathesis.models import APIOperation, Case, OperationDefinition
 from schemathesis.parameters import ParameterSet, PayloadAlternatives
 from schemathesis.specs.openapi.links import Link, get_container
 from schemathesis.specs.openapi.parameters import OpenAPI20Body, OpenAPI30Body, OpenAPI30Parameter
-from schemathesis.stateful import ParsedData, Stateful
+from schemathesis.stateful import Feedback, ParsedData, Stateful, StatefulData
 
 API_OPERATION = APIOperation(
     path="/users/{user_id}",
@@ -235,6 +235,52 @@ def test_make_operation_single():
             assert schema == {"type": "integer"}
 
 
+@pytest.mark.parametrize(
+    "path_parameters, query, expected",
+    (
+        ({"user_id": 5}, {"user_id": 5, "code": 7}, True),
+        ({"user_id": 5}, {"user_id": 6, "code": 7}, False),
+        ({"user_id": 5}, {"user_id": 5, "code": 8}, False),
+        ({"user_id": 5}, None, False),
+    ),
+)
+def test_is_used_in(path_parameters, query, expected):
+    # Parameters without a location prefix might be in any location
+    parsed = ParsedData({"path.user_id": 5, "query.user_id": 5, "code": 7})
+    case = Case(API_OPERATION, path_parameters=path_parameters, query=query)
+    assert LINK.is_used_in(parsed, case) is expected
+
+
+def test_find_source(case, response):
+    data = StatefulData(LINK)
+    data.store(case, response)
+    # When a case uses data parsed from a stored response
+    operation = data.make_operation()
+    linked = Case(operation, path_parameters={"user_id": 5}, query={"user_id": 5})
+    # Then that response should be found as the source of the case
+    source = data.find_source(linked)
+    assert source.case is case
+    assert source.response is response
+    # And cases with other data don't have a source
+    assert data.find_source(Case(operation, path_parameters={"user_id": 6}, query={"user_id": 6})) is None
+
+
+@pytest.mark.parametrize("status_code, expected", ((201, True), (200, False)))
+def test_created_by(case, status_code, expected):
+    response = requests.Response()
+    response._content = b'{"id": 5}'
+    response.status_code = status_code
+    data = StatefulData(LINK)
+    data.store(case, response)
+    feedback = Feedback(Stateful.links, data.make_operation())
+    linked = Case(feedback.operation, path_parameters={"user_id": 5}, query={"user_id": 5})
+    # When a case reads a resource via a link
+    feedback.set_source(linked)
+    # Then it is known whether that resource was created by the source call
+    assert linked.source is not None
+    assert (linked.created_by is linked.source) is expected
+
+
 BODY_SCHEMA = {"required": ["foo"], "type": "object", "properties": {"foo": {"type": "string"}}}
 
 
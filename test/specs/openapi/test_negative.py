@@ -0,0 +1,149 @@
+import jsonschema
+import pytest
+from hypothesis import HealthCheck, given, settings
+
+import schemathesis
+from schemathesis import DataGenerationMethod
+from schemathesis.specs.openapi._hypothesis import get_case_strategy, get_mutations
+from schemathesis.specs.openapi.negative.mutations import UNKNOWN_PROPERTY, iter_mutations
+from schemathesis.utils import NOT_SET
+
+USER_SCHEMA = {
+    "type": "object",
+    "properties": {
+        "name": {"type": "string", "minLength": 3, "pattern": "^[a-z]+$"},
+        "age": {"type": "integer", "minimum": 0, "maximum": 150},
+        "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 3},
+    },
+    "required": ["name", "age"],
+    "additionalProperties": False,
+}
+
+
+@pytest.fixture
+def operation(make_openapi_3_schema):
+    schema = make_openapi_3_schema(
+        body={"required": True, "content": {"application/json": {"schema": USER_SCHEMA}}},
+        parameters=[
+            {"in": "query", "name": "limit", "required": True, "schema": {"type": "integer", "minimum": 1}},
+            {"in": "header", "name": "X-Token", "required": True, "schema": {"type": "string", "maxLength": 8}},
+        ],
+    )
+    return schemathesis.from_dict(schema)["/users"]["POST"]
+
+
+def test_negative_cases(operation):
+    # When the negative data generation method is used
+    strategy = get_case_strategy(operation=operation, data_generation_method=DataGenerationMethod.negative)
+
+    @given(strategy)
+    @settings(max_examples=50, suppress_health_check=HealthCheck.all(), deadline=None)
+    def test(case):
+        # Then each case has a description of the applied mutation
+        assert case.mutation is not None
+        # And the mutated component doesn't match the schema
+        if case.mutation.startswith("required request body"):
+            assert case.body is NOT_SET
+        elif "query parameter" in case.mutation:
+            assert "limit" not in case.query or not isinstance(case.query["limit"], int) or case.query["limit"] < 1
+        elif "header" in case.mutation:
+            assert "X-Token" not in case.headers or len(case.headers["X-Token"]) > 8
+        else:
+            assert not jsonschema.Draft4Validator(USER_SCHEMA).is_valid(case.body)
+
+    test()
+
+
+def test_explicit_components_are_not_mutated(operation):
+    # When some components are passed explicitly
+    body = {"name": "alice", "age": 30}
+    strategy = get_case_strategy(
+        operation=operation, data_generation_method=DataGenerationMethod.negative, body=body, query={"limit": 5}
+    )
+
+    @given(strategy)
+    @settings(max_examples=20, suppress_health_check=HealthCheck.all(), deadline=None)
+    def test(case):
+        # Then only the remaining components are made invalid
+        assert case.body == body
+        assert case.query == {"limit": 5}
+        assert "header" in case.mutation
+
+    test()
+
+
+def test_positive_cases_have_no_mutation(operation):
+    strategy = get_case_strategy(operation=operation)
+
+    @given(strategy)
+    @settings(max_examples=5, suppress_health_check=HealthCheck.all(), deadline=None)
+    def test(case):
+        assert case.mutation is None
+
+    test()
+
+
+def test_body_mutation_descriptions():
+    descriptions = {mutation.description for mutation in iter_mutations(USER_SCHEMA, "body")}
+    assert "property `age` had type string instead of integer" in descriptions
+    assert "property `age` was less than the minimum of 0" in descriptions
+    assert "property `age` was greater than the maximum of 150" in descriptions
+    assert "property `name` was shorter than 3 characters" in descriptions
+    assert "property `name` did not match the pattern `^[a-z]+$`" in descriptions
+    assert "property `tags` had more than 3 items" in descriptions
+    assert "required property `name` was missing" in descriptions
+    assert f"request body had an unexpected property `{UNKNOWN_PROPERTY}`" in descriptions
+    assert "request body had type array instead of object" in descriptions
+
+
+def test_parameter_mutation_descriptions(operation):
+    descriptions = {mutation.description for mutation in get_mutations(operation) if mutation.location != "body"}
+    assert descriptions == {
+        "query parameter `limit` had type string instead of integer",
+        "query parameter `limit` was less than the minimum of 1",
+        "required query parameter `limit` was missing",
+        "header `X-Token` was longer than 8 characters",
+        "required header `X-Token` was missing",
+    }
+
+
+@pytest.mark.parametrize(
+    "schema, expected",
+    (
+        ({"type": "object", "required": ["id"], "properties": {"id": {"type": "string"}}}, {"id": "foo"}),
+        ({"type": "integer", "enum": [1, 2]}, 1),
+    ),
+)
+def test_mutations_break_valid_values(schema, expected):
+    # When any mutation is applied to a schema
+    for mutation in iter_mutations(schema, "body"):
+        mutated = mutation.apply(schema)
+        # Then a value that is valid against the original schema is not valid against the mutated one
+        assert not jsonschema.Draft4Validator(mutated).is_valid(expected), mutation.description
+
+
+def test_references():
+    # When the mutated sub-schema is behind a reference
+    schema = {
+        "$ref": "#/definitions/User",
+        "definitions": {
+            "User": {"type": "object", "properties": {"id": {"$ref": "#/definitions/Id"}}},
+            "Id": {"type": "integer", "minimum": 1},
+        },
+    }
+    mutation = next(
+        mutation
+        for mutation in iter_mutations(schema, "body")
+        if mutation.description == "property `id` was less than the minimum of 1"
+    )
+    mutated = mutation.apply(schema)
+    # Then the reference is inlined and mutated
+    assert mutated["properties"]["id"] == {"type": "integer", "maximum": 1, "exclusiveMaximum": True}
+    # And the original schema is not modified
+    assert schema["definitions"]["Id"] == {"type": "integer", "minimum": 1}
+
+
+def test_nothing_to_mutate(empty_open_api_3_schema):
+    empty_open_api_3_schema["paths"] = {"/health": {"get": {"responses": {"200": {"description": "OK"}}}}}
+    operation = schemathesis.from_dict(empty_open_api_3_schema)["/health"]["GET"]
+    assert get_mutations(operation) == []
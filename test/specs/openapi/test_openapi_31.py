@@ -0,0 +1,205 @@
+import json
+
+import pytest
+import requests
+from hypothesis import HealthCheck, given, settings
+
+import schemathesis
+from schemathesis.exceptions import CheckFailed, SchemaConversionError
+from schemathesis.specs.openapi.converter import to_draft_7, to_draft_7_recursive
+from schemathesis.specs.openapi.definitions import OPENAPI_31_VALIDATOR
+from schemathesis.specs.openapi.parameters import OpenAPI31Body, OpenAPI31Parameter
+from schemathesis.specs.openapi.schemas import OpenApi31
+
+ITEM_SCHEMA = {
+    "type": "object",
+    "properties": {
+        "kind": {"const": "item"},
+        "point": {"type": "array", "prefixItems": [{"type": "integer"}, {"type": "string"}], "items": False},
+        "note": {"type": ["string", "null"]},
+        "author": {"type": "string"},
+        "email": {"type": "string"},
+    },
+    "required": ["kind", "point"],
+    "dependentRequired": {"author": ["email"]},
+    "unevaluatedProperties": False,
+}
+
+
+@pytest.fixture
+def raw_schema():
+    return {
+        "openapi": "3.1.0",
+        "info": {"title": "Test", "version": "0.1.0"},
+        "paths": {
+            "/items": {
+                "post": {
+                    "parameters": [{"name": "limit", "in": "query", "schema": {"type": ["integer", "null"]}}],
+                    "requestBody": {
+                        "required": True,
+                        "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Item"}}},
+                    },
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Item"}}},
+                        }
+                    },
+                }
+            }
+        },
+        "components": {"schemas": {"Item": ITEM_SCHEMA}},
+    }
+
+
+@pytest.fixture
+def operation(raw_schema):
+    return schemathesis.from_dict(raw_schema)["/items"]["POST"]
+
+
+def make_response(data):
+    response = requests.Response()
+    response._content = json.dumps(data).encode()
+    response.status_code = 200
+    response.headers["Content-Type"] = "application/json"
+    return response
+
+
+def test_loader(raw_schema):
+    # When the schema is an Open API 3.1 document
+    schema = schemathesis.from_dict(raw_schema)
+    # Then it is loaded as such
+    assert isinstance(schema, OpenApi31)
+    assert schema.verbose_name == "Open API 3.1.0"
+
+
+def test_parameters(operation):
+    assert isinstance(operation.query[0], OpenAPI31Parameter)
+    assert isinstance(operation.body[0], OpenAPI31Body)
+    # Then type arrays are kept as is
+    assert operation.query[0].as_json_schema() == {"type": ["integer", "null"]}
+
+
+def test_no_paths(raw_schema):
+    # When the document has only webhooks
+    del raw_schema["paths"]
+    raw_schema["webhooks"] = {
+        "newItem": {"post": {"requestBody": {"content": {"application/json": {"schema": ITEM_SCHEMA}}}}}
+    }
+    schema = schemathesis.from_dict(raw_schema)
+    # Then it is valid, but there is nothing to test
+    assert schema.operations_count == 0
+
+
+def test_generation(operation):
+    validator = OpenApi31.response_validator_cls(to_draft_7_recursive(ITEM_SCHEMA))
+
+    @given(case=operation.as_strategy())
+    @settings(max_examples=20, suppress_health_check=HealthCheck.all(), deadline=None)
+    def test(case):
+        # Then generated data is valid according to JSON Schema 2020-12
+        assert validator.is_valid(case.body)
+        assert case.query["limit"] is None or isinstance(case.query["limit"], int)
+
+    test()
+
+
+@pytest.mark.parametrize(
+    "data, is_valid",
+    (
+        ({"kind": "item", "point": [1, "a"], "note": None}, True),
+        ({"kind": "other", "point": [1, "a"]}, False),
+        ({"kind": "item", "point": [1, "a"], "note": 42}, False),
+        # `prefixItems` violations
+        ({"kind": "item", "point": ["a", 1]}, False),
+        ({"kind": "item", "point": [1, "a", "extra"]}, False),
+        # `dependentRequired` violation
+        ({"kind": "item", "point": [1, "a"], "author": "Alice"}, False),
+    ),
+)
+def test_validate_response(operation, data, is_valid):
+    response = make_response(data)
+    case = operation.make_case(body={"kind": "item", "point": [1, "a"]})
+    if is_valid:
+        case.validate_response(response)
+    else:
+        with pytest.raises(CheckFailed):
+            case.validate_response(response)
+
+
+@pytest.mark.parametrize(
+    "schema, expected",
+    (
+        (
+            {"prefixItems": [{"type": "integer"}], "items": {"type": "string"}},
+            {"items": [{"type": "integer"}], "additionalItems": {"type": "string"}},
+        ),
+        ({"dependentRequired": {"a": ["b"]}}, {"dependencies": {"a": ["b"]}}),
+        (
+            {"properties": {"a": {}}, "unevaluatedProperties": False},
+            {"properties": {"a": {}}, "additionalProperties": False},
+        ),
+        (
+            {"allOf": [{"properties": {"a": {}}}], "unevaluatedProperties": False},
+            {"allOf": [{"properties": {"a": {}}}], "properties": {"a": {}}, "additionalProperties": False},
+        ),
+        (
+            {"properties": {"a": {}}, "additionalProperties": {"type": "string"}, "unevaluatedProperties": False},
+            {"properties": {"a": {}}, "additionalProperties": {"type": "string"}},
+        ),
+        (
+            {"dependentSchemas": {"a": {"required": ["b"]}}},
+            {"dependentSchemas": {"a": {"required": ["b"]}}, "dependencies": {"a": {"required": ["b"]}}},
+        ),
+        # Keywords next to `$ref` are applied together with it
+        (
+            {"$ref": "#/$defs/A", "required": ["a"]},
+            {"required": ["a"], "allOf": [{"$ref": "#/$defs/A"}]},
+        ),
+        # Reference Objects are not changed
+        ({"$ref": "#/$defs/A", "description": "Test"}, {"$ref": "#/$defs/A", "description": "Test"}),
+        ({"type": ["string", "null"], "const": "a"}, {"type": ["string", "null"], "const": "a"}),
+    ),
+)
+def test_to_draft_7(schema, expected):
+    assert to_draft_7(schema) == expected
+
+
+def test_to_draft_7_reference():
+    # When `unevaluatedProperties` is next to a local reference
+    root = {"$defs": {"A": {"properties": {"a": {}}, "patternProperties": {"^x-": {}}}}}
+    schema = {"$ref": "#/$defs/A", "properties": {"b": {}}, "unevaluatedProperties": False}
+    # Then properties of the referenced schema are evaluated too
+    assert to_draft_7(schema, root=root) == {
+        "properties": {"b": {}, "a": {}},
+        "patternProperties": {"^x-": {}},
+        "additionalProperties": False,
+        "allOf": [{"$ref": "#/$defs/A"}],
+    }
+
+
+@pytest.mark.parametrize(
+    "schema",
+    (
+        # Properties are evaluated only if the subschema is valid
+        {"anyOf": [{"properties": {"a": {}}}, {"properties": {"b": {}}}], "unevaluatedProperties": False},
+        {"if": {"properties": {"a": {}}}, "unevaluatedProperties": False},
+        {"$ref": "https://example.com/schema.json", "unevaluatedProperties": False},
+        {"$ref": "#/$defs/Unknown", "unevaluatedProperties": False},
+    ),
+)
+def test_to_draft_7_unconvertible(schema):
+    # When `unevaluatedProperties` can't be expressed via Draft 7 keywords
+    # Then it is an error, instead of allowing any properties
+    with pytest.raises(SchemaConversionError):
+        to_draft_7(schema, root={"$defs": {}})
+
+
+def test_meta_schema(raw_schema):
+    # Open API 3.1 documents are validated with the official schema
+    assert OPENAPI_31_VALIDATOR.is_valid(raw_schema)
+    raw_schema["paths"]["/items"]["post"]["unknown"] = 42
+    assert not OPENAPI_31_VALIDATOR.is_valid(raw_schema)
+    del raw_schema["paths"]["/items"]["post"]["unknown"]
+    raw_schema["paths"]["/items"]["post"]["parameters"][0]["in"] = "body"
+    assert not OPENAPI_31_VALIDATOR.is_valid(raw_schema)
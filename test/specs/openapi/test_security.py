@@ -1,3 +1,8 @@
+import pytest
+from hypothesis import given, settings
+
+import schemathesis
+from schemathesis._hypothesis import get_single_example
 from schemathesis.specs.openapi.references import InliningResolver
 from schemathesis.specs.openapi.security import OpenAPISecurityProcessor
 
@@ -13,3 +18,151 @@ def test_ref_resolving():
     }
     resolver = InliningResolver("", schema)
     assert OpenAPISecurityProcessor().get_security_definitions(schema, resolver) == http_schema
+
+
+def make_security_schema(security):
+    return {
+        "openapi": "3.0.0",
+        "info": {"title": "Blank API", "version": "1.0"},
+        "servers": [{"url": "http://localhost/api"}],
+        "paths": {"/foo": {"get": {"security": security, "responses": {"200": {"description": "OK"}}}}},
+        "components": {
+            "securitySchemes": {
+                "api_key": {"type": "apiKey", "name": "X-API-Key", "in": "header"},
+                "basic_auth": {"type": "http", "scheme": "basic"},
+                "token": {"type": "apiKey", "name": "token", "in": "query"},
+            }
+        },
+    }
+
+
+def get_used_credentials(case):
+    used = set()
+    if case.headers:
+        used.update(name for name in ("X-API-Key", "Authorization") if name in case.headers)
+    if case.query and "token" in case.query:
+        used.add("token")
+    return frozenset(used)
+
+
+@pytest.mark.parametrize(
+    "security, expected",
+    (
+        ([{"api_key": []}, {"basic_auth": []}], {frozenset(("X-API-Key",)), frozenset(("Authorization",))}),
+        (
+            [{"api_key": [], "basic_auth": []}, {"token": []}],
+            {frozenset(("X-API-Key", "Authorization")), frozenset(("token",))},
+        ),
+        ([{"api_key": []}, {}], {frozenset(("X-API-Key",)), frozenset()}),
+    ),
+)
+def test_security_alternatives(security, expected):
+    # When the API operation has alternative security requirements
+    operation = schemathesis.from_dict(make_security_schema(security))["/foo"]["GET"]
+    seen = set()
+
+    @given(case=operation.as_strategy())
+    @settings(max_examples=50, deadline=None)
+    def test(case):
+        seen.add(get_used_credentials(case))
+
+    test()
+    # Then each test case should satisfy exactly one of them
+    # And all of them should be generated
+    assert seen == expected
+
+
+def test_security_alternatives_hooks():
+    schema = schemathesis.from_dict(make_security_schema([{"api_key": []}, {"token": []}]))
+
+    @schema.hooks.register("before_generate_headers")
+    def before_generate_headers(context, strategy):
+        return strategy.map(lambda headers: {**headers, "X-API-Key": "hooked"} if headers else headers)
+
+    seen = set()
+
+    @given(case=schema["/foo"]["GET"].as_strategy())
+    @settings(max_examples=50, deadline=None)
+    def test(case):
+        seen.add(get_used_credentials(case))
+        # Then security parameters are passed to hooks as other parameters
+        if case.headers and "X-API-Key" in case.headers:
+            assert case.headers["X-API-Key"] == "hooked"
+
+    test()
+    assert seen == {frozenset(("X-API-Key",)), frozenset(("token",))}
+
+
+def test_security_alternatives_examples():
+    # When the API operation has alternative security requirements
+    operation = schemathesis.from_dict(make_security_schema([{"api_key": []}, {"token": []}]))["/foo"]["GET"]
+    examples = [get_single_example(strategy) for strategy in operation.get_strategies_from_examples()]
+    # Then each of them has an explicit example
+    assert [get_used_credentials(example) for example in examples] == [frozenset(("X-API-Key",)), frozenset(("token",))]
+
+
+def test_single_security_requirement():
+    # When there is only one security requirement
+    operation = schemathesis.from_dict(make_security_schema([{"api_key": [], "basic_auth": []}]))["/foo"]["GET"]
+    # Then its parameters are regular required parameters
+    assert not operation.security
+    assert [parameter.name for parameter in operation.headers] == ["X-API-Key", "Authorization"]
+
+
+@pytest.mark.parametrize(
+    "security, expected",
+    (
+        ([{"api_key": []}, {"basic_auth": []}], [("header", "X-API-Key"), ("header", "Authorization")]),
+        ([{"api_key": []}, {}], []),
+    ),
+)
+def test_credentials_locations(security, expected):
+    schema = schemathesis.from_dict(make_security_schema(security))
+    assert schema.get_credentials_locations(schema["/foo"]["GET"]) == expected
+
+
+OAUTH2_FLOWS = {
+    "clientCredentials": {"tokenUrl": "oauth/token", "scopes": {}},
+    "password": {"tokenUrl": "https://auth.example.com/token", "scopes": {}},
+}
+
+
+@pytest.mark.parametrize(
+    "flows, grant_type, expected",
+    (
+        # Relative URLs are resolved against the base URL
+        (OAUTH2_FLOWS, "client_credentials", "http://localhost/api/oauth/token"),
+        (OAUTH2_FLOWS, "password", "https://auth.example.com/token"),
+        ({"implicit": {"authorizationUrl": "https://auth.example.com/authorize", "scopes": {}}}, "password", None),
+    ),
+)
+def test_oauth2_token_url(flows, grant_type, expected):
+    raw_schema = make_security_schema([{"oauth2": []}])
+    raw_schema["components"]["securitySchemes"]["oauth2"] = {"type": "oauth2", "flows": flows}
+    schema = schemathesis.from_dict(raw_schema, base_url="http://localhost/api")
+    assert schema.get_oauth2_token_url(grant_type) == expected
+
+
+@pytest.mark.parametrize(
+    "grant_type, expected",
+    (
+        ("client_credentials", "https://auth.example.com/token"),
+        ("password", None),
+    ),
+)
+def test_oauth2_token_url_swagger(grant_type, expected):
+    raw_schema = {
+        "swagger": "2.0",
+        "info": {"title": "Blank API", "version": "1.0"},
+        "paths": {},
+        "securityDefinitions": {
+            "oauth2": {
+                "type": "oauth2",
+                "flow": "application",
+                "tokenUrl": "https://auth.example.com/token",
+                "scopes": {},
+            }
+        },
+    }
+    schema = schemathesis.from_dict(raw_schema, base_url="http://localhost/api")
+    assert schema.get_oauth2_token_url(grant_type) == expected
@@ -5,6 +5,7 @@ import pytest
 from hypothesis import given
 
 import schemathesis
+from schemathesis.exceptions import SerializationNotPossible
 from schemathesis.specs.openapi.serialization import (
     comma_delimited_object,
     conversion,
@@ -12,6 +13,7 @@ from schemathesis.specs.openapi.serialization import (
     delimited,
     delimited_object,
     extracted_object,
+    flat_delimited_object,
     label_array,
     label_object,
     label_primitive,
@@ -178,6 +180,21 @@ def test_cookie_serialization_styles_openapi3(schema, explode, expected):
     assert_generates(raw_schema, (expected,), "cookies")
 
 
+@pytest.mark.hypothesis_nested
+@pytest.mark.parametrize(
+    "schema, expected",
+    (
+        (ARRAY_SCHEMA, {"SessionID": "blue,black,brown"}),
+        (OBJECT_SCHEMA, {"SessionID": CommaDelimitedObject("r,100,g,200,b,150")}),
+    ),
+)
+def test_cookie_without_explode_openapi3(schema, expected):
+    # When `explode` is not specified for an array or object cookie
+    raw_schema = make_openapi_schema({"name": "SessionID", "in": "cookie", "required": True, "schema": schema})
+    # Then the cookie is still sent and serialized as with `explode=false`
+    assert_generates(raw_schema, (expected,), "cookies")
+
+
 @pytest.mark.hypothesis_nested
 @pytest.mark.parametrize(
     "schema, style, explode, expected",
@@ -349,6 +366,73 @@ def test_content_serialization():
     assert_generates(raw_schema, ({"filter": JSONString('{"r":100, "g": 200, "b": 150}')},), "query")
 
 
+@pytest.mark.hypothesis_nested
+@pytest.mark.parametrize(
+    "media_type, schema, expected",
+    (
+        ("application/vnd.api+json", OBJECT_SCHEMA, {"filter": JSONString('{"r":100, "g": 200, "b": 150}')}),
+        ("application/x-www-form-urlencoded", OBJECT_SCHEMA, {"filter": "r=100&g=200&b=150"}),
+        ("text/plain", PRIMITIVE_SCHEMA, {"filter": "1"}),
+    ),
+)
+def test_content_serialization_media_types(media_type, schema, expected):
+    raw_schema = make_openapi_schema(
+        {"in": "query", "name": "filter", "required": True, "content": {media_type: {"schema": schema}}}
+    )
+    assert_generates(raw_schema, (expected,), "query")
+
+
+@pytest.mark.hypothesis_nested
+@pytest.mark.parametrize("location", ("query", "headers", "cookies"))
+def test_content_serialization_custom_serializer(location):
+    # When there is a custom serializer for the media type
+    @schemathesis.serializers.register("text/csv")
+    class CSVSerializer:
+        def as_requests(self, context, value):
+            return {"data": ",".join(map(str, value))}
+
+        def as_werkzeug(self, context, value):
+            return {"data": ",".join(map(str, value))}
+
+    try:
+        raw_schema = make_openapi_schema(
+            {
+                "in": location.rstrip("s"),
+                "name": "X-Colors",
+                "required": True,
+                "content": {"text/csv": {"schema": ARRAY_SCHEMA}},
+            }
+        )
+        # Then it is used for parameters
+        assert_generates(raw_schema, ({"X-Colors": "blue,black,brown"},), location)
+    finally:
+        schemathesis.serializers.unregister("text/csv")
+
+
+def test_content_serialization_unknown_media_type():
+    raw_schema = make_openapi_schema(
+        {"in": "query", "name": "filter", "required": True, "content": {"application/xml": {"schema": OBJECT_SCHEMA}}}
+    )
+    # When there is no serializer for the parameter's media type
+    # Then it should be reported instead of sending the raw value
+    with pytest.raises(SerializationNotPossible):
+        assert_generates(raw_schema, (), "query")
+
+
+def test_content_serialization_multipart():
+    raw_schema = make_openapi_schema(
+        {
+            "in": "query",
+            "name": "filter",
+            "required": True,
+            "content": {"multipart/form-data": {"schema": OBJECT_SCHEMA}},
+        }
+    )
+    # Multipart serialization requires a test case
+    with pytest.raises(SerializationNotPossible, match="`multipart/form-data` can't be used for parameters"):
+        assert_generates(raw_schema, (), "query")
+
+
 def make_array_schema(location, style):
     return {
         "name": "bbox",
@@ -407,6 +491,7 @@ def test_non_string_serialization(parameter, expected):
         (deep_object, {}),
         (comma_delimited_object, {}),
         (delimited_object, {}),
+        (flat_delimited_object, {"delimiter": "|"}),
         (extracted_object, {}),
         (label_primitive, {}),
         (label_array, {"explode": True}),
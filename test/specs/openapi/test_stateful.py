@@ -213,6 +213,74 @@ def test_all_operations_with_links(openapi3_base_url):
     )
 
 
+def make_items_schema(status_code):
+    return {
+        "openapi": "3.0.2",
+        "info": {"title": "Test", "description": "Test", "version": "0.1.0"},
+        "paths": {
+            "/items": {
+                "post": {
+                    "responses": {
+                        status_code: {
+                            "description": "OK",
+                            "links": {
+                                "GetItem": {"operationId": "getItem", "parameters": {"itemId": "$response.body#/id"}}
+                            },
+                        }
+                    },
+                }
+            },
+            "/items/{itemId}": {
+                "get": {
+                    "operationId": "getItem",
+                    "parameters": [{"in": "path", "name": "itemId", "required": True, "schema": {"type": "integer"}}],
+                    "responses": {"200": {"description": "OK"}, "404": {"description": "Not found"}},
+                }
+            },
+        },
+    }
+
+
+def run_items_state_machine(raw_schema, base_url):
+    schema = schemathesis.from_dict(raw_schema, base_url=base_url)
+    status_code = int(next(iter(raw_schema["paths"]["/items"]["post"]["responses"])))
+
+    class APIWorkflow(schema.as_state_machine()):
+        def call(self, case, **kwargs):
+            # Created items are never found
+            response = requests.Response()
+            response.request = requests.Request(case.method, f"{base_url}{case.formatted_path}").prepare()
+            if case.method == "POST":
+                response.status_code = status_code
+                response._content = b'{"id": 42}'
+            else:
+                response.status_code = 404
+            return response
+
+    run_state_machine_as_test(
+        APIWorkflow,
+        settings=settings(
+            max_examples=100, deadline=None, suppress_health_check=HealthCheck.all(), stateful_step_count=3
+        ),
+    )
+
+
+def test_resource_availability_after_creation(openapi3_base_url):
+    # When a link goes from a response that created a resource to reading it
+    raw_schema = make_items_schema("201")
+    # And the created resource is not found
+    # Then it should be reported
+    with pytest.raises(CheckFailed, match="Resource created by `POST /items` is not available"):
+        run_items_state_machine(raw_schema, openapi3_base_url)
+
+
+def test_resource_availability_no_creation(openapi3_base_url):
+    # When the source call of a link didn't create a resource
+    raw_schema = make_items_schema("200")
+    # Then not found resources are not reported
+    run_items_state_machine(raw_schema, openapi3_base_url)
+
+
 @pytest.mark.parametrize("openapi_version", (OpenAPIVersion("3.0"),))
 @pytest.mark.operations("create_user", "get_user", "update_user")
 def test_step_override(testdir, app_schema, base_url, openapi_version):
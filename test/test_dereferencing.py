@@ -3,6 +3,7 @@ from pathlib import Path
 import pytest
 
 import schemathesis
+from schemathesis.specs.openapi.references import remove_optional_references
 
 from .utils import as_param, get_schema, integer
 
@@ -566,3 +567,60 @@ def test_remote_reference_to_yaml(swagger_20, schema_url):
     scope, resolved = swagger_20.resolver.resolve(f"{schema_url}#/info/title")
     assert scope.endswith("#/info/title")
     assert resolved == "Example API"
+
+
+@pytest.mark.parametrize(
+    "schema, expected",
+    (
+        (
+            {"properties": {"child": {"$ref": "#/definitions/Node"}, "id": {"type": "integer"}}},
+            {"properties": {"id": {"type": "integer"}}},
+        ),
+        (
+            {"properties": {"child": {"$ref": "#/definitions/Node"}}, "required": ["child"]},
+            {"properties": {"child": {"$ref": "#/definitions/Node"}}, "required": ["child"]},
+        ),
+        (
+            {"type": "array", "items": {"$ref": "#/definitions/Node"}},
+            {"type": "array", "items": {"$ref": "#/definitions/Node"}, "maxItems": 0},
+        ),
+        (
+            {"type": "array", "items": {"$ref": "#/definitions/Node"}, "minItems": 1},
+            {"type": "array", "items": {"$ref": "#/definitions/Node"}, "minItems": 1},
+        ),
+        (
+            {"type": "object", "additionalProperties": {"$ref": "#/definitions/Node"}},
+            {"type": "object", "additionalProperties": False},
+        ),
+        (
+            {"anyOf": [{"properties": {"child": {"$ref": "#/definitions/Node"}}}]},
+            {"anyOf": [{"properties": {}}]},
+        ),
+        (
+            # The property is required by another `allOf` branch
+            {"allOf": [{"properties": {"child": {"$ref": "#/definitions/Node"}}}, {"required": ["child"]}]},
+            {"allOf": [{"properties": {"child": {"$ref": "#/definitions/Node"}}}, {"required": ["child"]}]},
+        ),
+        (
+            {"required": ["child"], "allOf": [{"properties": {"child": {"$ref": "#/definitions/Node"}}}]},
+            {"required": ["child"], "allOf": [{"properties": {"child": {"$ref": "#/definitions/Node"}}}]},
+        ),
+    ),
+)
+def test_remove_optional_references(schema, expected):
+    # When a schema contains references that are not inlined
+    remove_optional_references(schema)
+    # Then they are removed from optional positions
+    assert schema == expected
+
+
+@pytest.mark.parametrize("recursion_depth", (0, 2))
+def test_recursion_depth(schema_with_recursive_references, recursion_depth):
+    # When the inlining depth for recursive references is configured
+    schema = schemathesis.from_dict(schema_with_recursive_references, recursion_depth=recursion_depth)
+    operation = schema["/foo"]["POST"]
+    node = operation.body[0].definition["schema"]["properties"]["nodes"]["additionalProperties"]
+    # Then references are inlined exactly that many times
+    for _ in range(recursion_depth):
+        node = node["properties"]["children"]["items"]
+    assert node["properties"]["children"]["items"] == {"$ref": "#/components/schemas/Node"}
@@ -107,6 +107,23 @@ def test_call(override, base_url, swagger_20):
     assert not records
 
 
+@pytest.mark.parametrize("with_session", (True, False))
+def test_replay(mocker, base_url, swagger_20, with_session):
+    operation = APIOperation("/success", "GET", {}, swagger_20, base_url=base_url)
+    case = operation.make_case()
+    session = requests.Session() if with_session else None
+    response = case.call(session=session, timeout=5, verify=False)
+    send = mocker.spy(requests.Session, "send")
+    # When a request derived from the original one is replayed
+    replayed = case.replay(response.request.copy())
+    assert replayed.status_code == 200
+    # Then it is sent with the same session and options as the original request
+    if with_session:
+        assert send.call_args[0][0] is session
+    assert send.call_args[1]["timeout"] == 5
+    assert send.call_args[1]["verify"] is False
+
+
 @pytest.mark.operations("success")
 def test_call_and_validate(openapi3_schema_url):
     api_schema = schemathesis.from_uri(openapi3_schema_url)
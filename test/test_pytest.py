@@ -247,15 +247,30 @@ def test(case):
     )
 
 
-def test_skip_operations_with_recursive_references(testdir, schema_with_recursive_references):
+def test_recursive_references(testdir, schema_with_recursive_references):
     # When the test schema contains recursive references
     testdir.make_test(
         """
 @schema.parametrize()
+@settings(max_examples=10)
 def test(case):
-    pass""",
+    assert isinstance(case.body["nodes"], dict)""",
         schema=schema_with_recursive_references,
     )
+    result = testdir.runpytest()
+    # Then data is generated with a limited recursion depth
+    result.assert_outcomes(passed=1)
+
+
+def test_skip_operations_with_required_recursive_references(testdir, schema_with_required_recursive_references):
+    # When the test schema contains recursive references that can't be cut off
+    testdir.make_test(
+        """
+@schema.parametrize()
+def test(case):
+    pass""",
+        schema=schema_with_required_recursive_references,
+    )
     result = testdir.runpytest("-rs")
     # Then this test should be skipped with a proper error message
     result.assert_outcomes(skipped=1)
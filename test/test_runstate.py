@@ -0,0 +1,88 @@
+import pytest
+
+import schemathesis
+from schemathesis.constants import DataGenerationMethod
+from schemathesis.runstate import FailedTest, RerunFailed, RunState
+from schemathesis.sharding import get_operation_reference
+
+
+@pytest.fixture
+def operations(empty_open_api_3_schema):
+    empty_open_api_3_schema["paths"] = {
+        f"/items/{idx}": {"get": {"responses": {"200": {"description": "OK"}}}} for idx in range(4)
+    }
+    schema = schemathesis.from_dict(empty_open_api_3_schema)
+    return list(schema.get_all_operations())
+
+
+def failed_test(reference, data_generation_method=DataGenerationMethod.positive):
+    return FailedTest(reference=reference, data_generation_method=data_generation_method)
+
+
+def references(operations):
+    return [get_operation_reference(result) for result in operations]
+
+
+def test_load_missing_file(tmp_path):
+    # Nothing failed, if there were no previous runs
+    assert RunState.load(str(tmp_path / "missing.json")) == RunState()
+
+
+def test_save_and_load(tmp_path):
+    path = str(tmp_path / "nested" / "state.json")
+    state = RunState(
+        failed=[
+            FailedTest(
+                reference="POST /api/users",
+                data_generation_method=DataGenerationMethod.negative,
+                seed=42,
+                examples=[{"path_parameters": None, "headers": None, "cookies": None, "query": {"id": 1}}],
+            )
+        ]
+    )
+    state.save(path)
+    assert RunState.load(path) == state
+
+
+@pytest.mark.parametrize(
+    "content",
+    ("{", "[]", '{"failed": [{}]}', '{"failed": [{"reference": "GET /", "data_generation_method": "unknown"}]}'),
+)
+def test_load_invalid(tmp_path, content):
+    path = tmp_path / "state.json"
+    path.write_text(content)
+    with pytest.raises(ValueError):
+        RunState.load(str(path))
+
+
+def test_update():
+    state = RunState(
+        failed=[failed_test("GET /a"), failed_test("GET /b"), failed_test("GET /b", DataGenerationMethod.negative)]
+    )
+    # When some of the previously failed tests are passing now
+    tested = {("GET /a", DataGenerationMethod.positive), ("GET /b", DataGenerationMethod.positive)}
+    new_state = state.update(tested, [failed_test("GET /c")])
+    # Then only the new failures and failures that were not tested in this run are kept
+    assert new_state.failed == [failed_test("GET /b", DataGenerationMethod.negative), failed_test("GET /c")]
+
+
+@pytest.mark.parametrize(
+    "only_failed, expected",
+    (
+        (True, ["GET /items/0", "GET /items/2"]),
+        (False, ["GET /items/0", "GET /items/2", "GET /items/1", "GET /items/3"]),
+    ),
+)
+def test_select(operations, only_failed, expected):
+    state = RunState(failed=[failed_test("GET /items/2"), failed_test("GET /items/0"), failed_test("GET /unknown")])
+    selected = RerunFailed(state=state, only_failed=only_failed).select(operations)
+    # Failed operations come first, in their original order
+    assert references(selected) == expected
+
+
+@pytest.mark.parametrize("only_failed", (True, False))
+def test_select_no_failures(operations, only_failed):
+    # When none of the previously failed operations are in the schema
+    state = RunState(failed=[failed_test("GET /unknown")])
+    # Then all operations are tested
+    assert RerunFailed(state=state, only_failed=only_failed).select(operations) == operations
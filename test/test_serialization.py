@@ -3,7 +3,9 @@ from io import StringIO
 from test.utils import assert_requests_call
 
 import pytest
+import requests
 from hypothesis import given, settings
+from werkzeug.test import EnvironBuilder
 
 import schemathesis
 from schemathesis.exceptions import SerializationNotPossible
@@ -159,3 +161,231 @@ def test_binary_data(empty_open_api_3_schema, media_type):
         assert werkzeug_kwargs["headers"]["Content-Type"] == media_type
     # And it is OK to send it over the network
     assert_requests_call(case)
+
+
+def make_query_schema(*parameters):
+    return {
+        "openapi": "3.0.2",
+        "info": {"title": "Test", "description": "Test", "version": "0.1.0"},
+        "paths": {"/teapot": {"get": {"parameters": list(parameters), "responses": {"200": {"description": "OK"}}}}},
+    }
+
+
+COLOR_SCHEMA = {
+    "type": "object",
+    "properties": {"r": {"type": "integer", "enum": [100]}, "g": {"type": "integer", "enum": [200]}},
+    "required": ["r", "g"],
+    "additionalProperties": False,
+}
+FILTER_SCHEMA = {
+    "type": "object",
+    "properties": {
+        "status": {"type": "string", "enum": ["active"]},
+        "owner": {
+            "type": "object",
+            "properties": {"name": {"type": "string", "enum": ["me"]}},
+            "required": ["name"],
+            "additionalProperties": False,
+        },
+    },
+    "required": ["status", "owner"],
+    "additionalProperties": False,
+}
+LIST_SCHEMA = {"type": "array", "enum": [["a", "b"]]}
+
+
+@pytest.mark.hypothesis_nested
+@pytest.mark.parametrize(
+    "parameter, expected",
+    (
+        # `explode` is `true` by default for the `form` style
+        ({"in": "query", "name": "color", "schema": COLOR_SCHEMA}, {"r": 100, "g": 200}),
+        ({"in": "query", "name": "color", "schema": LIST_SCHEMA}, {"color": ["a", "b"]}),
+        (
+            {"in": "query", "name": "filter", "schema": FILTER_SCHEMA, "style": "deepObject", "explode": True},
+            {"filter[status]": "active", "filter[owner][name]": "me"},
+        ),
+        (
+            {"in": "query", "name": "color", "schema": COLOR_SCHEMA, "style": "pipeDelimited", "explode": False},
+            {"color": "r|100|g|200"},
+        ),
+        (
+            {"in": "query", "name": "color", "schema": COLOR_SCHEMA, "style": "spaceDelimited", "explode": False},
+            {"color": "r 100 g 200"},
+        ),
+        # `explode` is `false` by default for the `simple` style
+        ({"in": "header", "name": "X-Color", "schema": COLOR_SCHEMA}, {"X-Color": "r,100,g,200"}),
+    ),
+)
+def test_parameter_styles_defaults(parameter, expected):
+    parameter["required"] = True
+    schema = schemathesis.from_dict(make_query_schema(parameter))
+
+    @given(case=schema["/teapot"]["GET"].as_strategy())
+    @settings(max_examples=1)
+    def test(case):
+        assert (case.query if parameter["in"] == "query" else case.headers) == expected
+
+    test()
+
+
+@pytest.mark.hypothesis_nested
+@pytest.mark.parametrize(
+    "collection_format, expected",
+    (
+        ("csv", "a,b"),
+        ("ssv", "a b"),
+        ("tsv", "a\tb"),
+        ("pipes", "a|b"),
+    ),
+)
+def test_swagger_collection_format_headers(collection_format, expected):
+    raw_schema = {
+        "swagger": "2.0",
+        "info": {"title": "Test", "description": "Test", "version": "0.1.0"},
+        "paths": {
+            "/teapot": {
+                "get": {
+                    "parameters": [
+                        {
+                            "in": "header",
+                            "name": "X-Tags",
+                            "required": True,
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "collectionFormat": collection_format,
+                            "enum": [["a", "b"]],
+                        }
+                    ],
+                    "responses": {"200": {"description": "OK"}},
+                }
+            }
+        },
+    }
+    schema = schemathesis.from_dict(raw_schema)
+
+    @given(case=schema["/teapot"]["GET"].as_strategy())
+    @settings(max_examples=1)
+    def test(case):
+        assert case.headers == {"X-Tags": expected}
+
+    test()
+
+
+@pytest.mark.parametrize("allow_reserved, expected", ((True, "path=/a/b:c%26d&other=%2Fe"), (False, None)))
+def test_allow_reserved(allow_reserved, expected):
+    raw_schema = make_query_schema(
+        {"in": "query", "name": "path", "schema": {"type": "string"}, "allowReserved": allow_reserved},
+        {"in": "query", "name": "other", "schema": {"type": "string"}},
+    )
+    operation = schemathesis.from_dict(raw_schema)["/teapot"]["GET"]
+    query = {"path": "/a/b:c&d", "other": "/e"}
+    case = operation.make_case(query=query)
+    if allow_reserved:
+        # Then reserved characters are not encoded for parameters that allow it
+        assert case.as_requests_kwargs()["params"] == expected
+        assert case.as_werkzeug_kwargs()["query_string"] == expected
+        assert case.get_full_url() == f"http://localhost/teapot?{expected}"
+    else:
+        # Otherwise, the query is passed as is
+        assert case.as_requests_kwargs()["params"] == query
+        assert case.get_full_url() == "http://localhost/teapot?path=%2Fa%2Fb%3Ac%26d&other=%2Fe"
+
+
+def make_form_schema(empty_open_api_3_schema, media_type, schema, encoding):
+    empty_open_api_3_schema["paths"] = {
+        "/upload": {
+            "post": {
+                "requestBody": {
+                    "required": True,
+                    "content": {media_type: {"schema": schema, "encoding": encoding}},
+                },
+                "responses": {"200": {"description": "OK"}},
+            },
+        },
+    }
+    return schemathesis.from_dict(empty_open_api_3_schema)["/upload"]["POST"]
+
+
+UPLOAD_SCHEMA = {
+    "type": "object",
+    "properties": {
+        "metadata": {"type": "object", "properties": {"name": {"type": "string"}}},
+        "image": {"type": "string", "format": "binary"},
+    },
+    "required": ["metadata", "image"],
+}
+UPLOAD_ENCODING = {
+    "metadata": {"contentType": "application/json"},
+    "image": {
+        "contentType": "image/png, image/jpeg",
+        "headers": {"X-Rate-Limit-Limit": {"schema": {"type": "integer", "example": 42}}},
+    },
+}
+UPLOAD_BODY = {"metadata": {"name": "Test"}, "image": b"\x89PNG"}
+
+
+def assert_multipart_parts(body, boundary):
+    parts = body.split(b"--" + boundary.encode())[1:-1]
+    metadata, image = sorted(parts, reverse=True)
+    assert b'name="metadata"' in metadata
+    assert b"Content-Type: application/json" in metadata
+    assert metadata.endswith(b'{"name": "Test"}\r\n')
+    assert b'name="image"; filename="image"' in image
+    assert b"Content-Type: image/png" in image
+    assert b"X-Rate-Limit-Limit: 42" in image
+    assert image.endswith(b"\x89PNG\r\n")
+
+
+def test_multipart_encoding_requests(empty_open_api_3_schema):
+    # When the `multipart/form-data` payload has the `encoding` object
+    operation = make_form_schema(empty_open_api_3_schema, "multipart/form-data", UPLOAD_SCHEMA, UPLOAD_ENCODING)
+    case = operation.make_case(body=UPLOAD_BODY, media_type="multipart/form-data")
+    kwargs = case.as_requests_kwargs(base_url="http://127.0.0.1")
+    # Then every part should have the content type and headers from it
+    request = requests.Request(**kwargs).prepare()
+    boundary = request.headers["Content-Type"].split("boundary=")[1]
+    assert_multipart_parts(request.body, boundary)
+
+
+def test_multipart_encoding_werkzeug(empty_open_api_3_schema):
+    operation = make_form_schema(empty_open_api_3_schema, "multipart/form-data", UPLOAD_SCHEMA, UPLOAD_ENCODING)
+    case = operation.make_case(body=UPLOAD_BODY, media_type="multipart/form-data")
+    kwargs = case.as_werkzeug_kwargs()
+    # Then the payload should be encoded explicitly
+    boundary = kwargs["content_type"].split("boundary=")[1]
+    assert_multipart_parts(kwargs["data"], boundary)
+    # And the boundary should be in the resulting header
+    request = EnvironBuilder(**kwargs).get_request()
+    assert request.files["image"].content_type == "image/png"
+    assert request.form["metadata"] == '{"name": "Test"}'
+
+
+@pytest.mark.parametrize("method", ("as_requests_kwargs", "as_werkzeug_kwargs"))
+@pytest.mark.parametrize(
+    "encoding, expected",
+    (
+        ({}, {"tags": ["a", "b"], "filter": {"status": "active"}}),
+        ({"tags": {"style": "form", "explode": False}}, {"tags": "a,b", "filter": {"status": "active"}}),
+        ({"tags": {"style": "pipeDelimited", "explode": False}}, {"tags": "a|b", "filter": {"status": "active"}}),
+        ({"filter": {"style": "deepObject", "explode": True}}, {"tags": ["a", "b"], "filter[status]": "active"}),
+        ({"filter": {"contentType": "application/json"}}, {"tags": ["a", "b"], "filter": '{"status": "active"}'}),
+    ),
+)
+def test_urlencoded_encoding(empty_open_api_3_schema, method, encoding, expected):
+    # When the `application/x-www-form-urlencoded` payload has the `encoding` object
+    schema = {
+        "type": "object",
+        "properties": {
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "filter": {"type": "object", "properties": {"status": {"type": "string"}}},
+        },
+    }
+    media_type = "application/x-www-form-urlencoded"
+    operation = make_form_schema(empty_open_api_3_schema, media_type, schema, encoding)
+    body = {"tags": ["a", "b"], "filter": {"status": "active"}}
+    case = operation.make_case(body=body, media_type=media_type)
+    # Then properties should be serialized according to their styles
+    assert getattr(case, method)()["data"] == expected
+    # And the generated payload should stay intact
+    assert case.body == {"tags": ["a", "b"], "filter": {"status": "active"}}
@@ -0,0 +1,86 @@
+import pytest
+import requests
+
+from schemathesis.sessions import SessionConfig, create_session
+from schemathesis.throttling import RateLimiter
+
+
+@pytest.fixture
+def proxy_url(server):
+    # The test app handles requests in the absolute form as well, therefore it can act as a proxy
+    return f"http://127.0.0.1:{server['port']}"
+
+
+@pytest.mark.operations("success")
+def test_proxy(openapi_3_app, proxy_url, monkeypatch):
+    monkeypatch.delenv("NO_PROXY", raising=False)
+    monkeypatch.delenv("no_proxy", raising=False)
+    # When a proxy is configured
+    with create_session(SessionConfig(proxy=proxy_url)) as session:
+        # Then requests are sent through it
+        response = session.get("http://schemathesis.test/api/success", timeout=1)
+    assert response.status_code == 200
+    assert openapi_3_app["incoming_requests"][0].headers["Host"] == "schemathesis.test"
+
+
+@pytest.mark.parametrize("name", ("NO_PROXY", "no_proxy"))
+def test_proxy_no_proxy(proxy_url, monkeypatch, name):
+    # When the target host is listed in `NO_PROXY`
+    monkeypatch.setenv(name, "schemathesis.test")
+    with create_session(SessionConfig(proxy=proxy_url)) as session:
+        # Then the proxy is not used
+        with pytest.raises(requests.ConnectionError):
+            session.get("http://schemathesis.test/api/success", timeout=1)
+
+
+def test_pool_size():
+    with create_session(SessionConfig(pool_size=3)) as session:
+        assert session.get_adapter("http://127.0.0.1")._pool_maxsize == 3
+        assert session.get_adapter("https://127.0.0.1")._pool_maxsize == 3
+
+
+@pytest.mark.operations("success")
+def test_resolve(openapi_3_app, server):
+    # When a host name is mapped to an address
+    with create_session(SessionConfig(resolve={"schemathesis.test": "127.0.0.1"})) as session:
+        response = session.get(f"http://schemathesis.test:{server['port']}/api/success", timeout=1)
+    # Then the connection is made to this address
+    assert response.status_code == 200
+    # And the original URL is kept
+    assert response.url == f"http://schemathesis.test:{server['port']}/api/success"
+    assert openapi_3_app["incoming_requests"][0].headers["Host"] == f"schemathesis.test:{server['port']}"
+
+
+def test_resolve_tls(mtls_server, tls_certificates):
+    # When a host name is mapped to an address of a server with a certificate for a different name
+    with create_session(SessionConfig(resolve={"schemathesis.test": "127.0.0.1"})) as session:
+        # Then TLS verification uses the original host name
+        with pytest.raises(requests.exceptions.SSLError, match="CERTIFICATE_VERIFY_FAILED"):
+            session.get(
+                f"https://schemathesis.test:{mtls_server['port']}/schema.yaml",
+                verify=tls_certificates["ca.pem"],
+                cert=(tls_certificates["client.pem"], tls_certificates["client.key"]),
+            )
+
+
+@pytest.mark.parametrize("retries, expected", ((0, 429), (1, 429), (2, 200)))
+@pytest.mark.operations("throttled")
+def test_retries(openapi_3_app, openapi3_base_url, retries, expected):
+    # When the API responds with 429 to the first two requests
+    with create_session(SessionConfig(retries=retries)) as session:
+        response = session.get(f"{openapi3_base_url}/throttled", timeout=1)
+    # Then the request is retried at most the given number of times
+    assert response.status_code == expected
+    assert len(openapi_3_app["incoming_requests"]) == retries + 1
+    # And retried responses are available in the history
+    assert [item.status_code for item in response.history] == [429] * retries
+
+
+@pytest.mark.operations("success")
+def test_rate_limit_not_in_elapsed(openapi3_base_url):
+    # When the second request has to wait for the rate limiter
+    with create_session(SessionConfig(rate_limiter=RateLimiter(max_requests=1, period=0.5))) as session:
+        session.get(f"{openapi3_base_url}/success", timeout=1)
+        response = session.get(f"{openapi3_base_url}/success", timeout=1)
+    # Then the waiting time is not included in the response time
+    assert response.elapsed.total_seconds() < 0.5
@@ -0,0 +1,96 @@
+import pytest
+
+import schemathesis
+from schemathesis.exceptions import InvalidSchema
+from schemathesis.sharding import Shard, distribute, get_operation_reference, load_junit_durations
+from schemathesis.utils import Err
+
+
+@pytest.fixture
+def operations(empty_open_api_3_schema):
+    empty_open_api_3_schema["paths"] = {
+        f"/items/{idx}": {"get": {"responses": {"200": {"description": "OK"}}}} for idx in range(10)
+    }
+    schema = schemathesis.from_dict(empty_open_api_3_schema)
+    return list(schema.get_all_operations())
+
+
+def references(results):
+    return {get_operation_reference(result) for result in results}
+
+
+@pytest.mark.parametrize("value, index, total", (("1/1", 1, 1), ("2/4", 2, 4), (" 3 / 5 ", 3, 5)))
+def test_shard_from_string(value, index, total):
+    shard = Shard.from_string(value)
+    assert shard.index == index
+    assert shard.total == total
+    assert str(shard) == f"{index}/{total}"
+
+
+@pytest.mark.parametrize(
+    "value, message",
+    (
+        ("2", "Should be in N/M format, where N and M are positive integers. Got: 2"),
+        ("a/b", "Should be in N/M format, where N and M are positive integers. Got: a/b"),
+        ("0/2", "Shard index should be between 1 and 2. Got: 0"),
+        ("3/2", "Shard index should be between 1 and 2. Got: 3"),
+    ),
+)
+def test_shard_from_string_invalid(value, message):
+    with pytest.raises(ValueError, match=message):
+        Shard.from_string(value)
+
+
+@pytest.mark.parametrize("total", (1, 2, 3, 7, 12))
+def test_shards_cover_all_operations(operations, total):
+    # When operations are split into shards
+    shards = [Shard(index, total).select(operations) for index in range(1, total + 1)]
+    # Then every operation is selected exactly once
+    assert sorted(reference for shard in shards for reference in references(shard)) == sorted(references(operations))
+    # And shards contain the same number of operations
+    sizes = [len(shard) for shard in shards]
+    assert max(sizes) - min(sizes) <= 1
+
+
+def test_shard_does_not_depend_on_order(operations):
+    # When operations come in a different order
+    shard = Shard(2, 3)
+    # Then the same operations are selected
+    assert references(shard.select(reversed(operations))) == references(shard.select(operations))
+    # And their original order is kept
+    selected = shard.select(operations)
+    assert selected == [result for result in operations if result in selected]
+
+
+def test_shard_generic_errors(operations):
+    # Errors that are not related to a specific operation
+    error = Err(InvalidSchema("Unresolvable reference"))
+    # Are selected only by the first shard
+    assert error in Shard(1, 2).select([error, *operations])
+    assert error not in Shard(2, 2).select([error, *operations])
+
+
+def test_distribute_by_durations():
+    # The longest operations are placed first, then shorter ones fill the least loaded shards
+    assert distribute(["a", "b", "c", "d"], 2, {"a": 3, "b": 1, "c": 1, "d": 1}) == {"a": 0, "b": 1, "c": 1, "d": 1}
+
+
+def test_distribute_unknown_durations():
+    # Operations without durations take the average duration
+    assert distribute(["a", "b", "c"], 2, {"a": 4, "b": 2}) == {"a": 0, "b": 1, "c": 1}
+
+
+def test_load_junit_durations(tmp_path):
+    report = tmp_path / "junit.xml"
+    report.write_text(
+        """<?xml version="1.0" ?>
+<testsuites>
+    <testsuite name="schemathesis">
+        <testcase name="GET /api/users" time="1.5"/>
+        <testcase name="GET /api/users" time="0.5"/>
+        <testcase name="POST /api/users" time="3"/>
+    </testsuite>
+</testsuites>"""
+    )
+    # Durations of the same operation are summed
+    assert load_junit_durations(str(report)) == {"GET /api/users": 2.0, "POST /api/users": 3.0}
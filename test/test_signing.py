@@ -0,0 +1,93 @@
+import datetime
+
+import pytest
+import requests
+
+from schemathesis.signing import AWSSigV4Signer, HMACSigner
+
+
+@pytest.fixture
+def sigv4():
+    # Credentials from the AWS Signature Version 4 test suite
+    return AWSSigV4Signer(
+        access_key="AKIDEXAMPLE",
+        secret_key="wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+        region="us-east-1",
+        service="service",
+        clock=lambda: datetime.datetime(2015, 8, 30, 12, 36, tzinfo=datetime.timezone.utc),
+    )
+
+
+def prepare(method, url, **kwargs):
+    return requests.Request(method, url, **kwargs).prepare()
+
+
+@pytest.mark.parametrize(
+    "url, expected",
+    (
+        # `get-vanilla`
+        ("https://example.amazonaws.com/", "5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"),
+        # `get-vanilla-query-order-key-case`
+        (
+            "https://example.amazonaws.com/?Param2=value2&Param1=value1",
+            "b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500",
+        ),
+    ),
+)
+def test_sigv4(sigv4, url, expected):
+    request = prepare("GET", url)
+    sigv4(None, request)
+    assert request.headers["X-Amz-Date"] == "20150830T123600Z"
+    assert request.headers["Authorization"] == (
+        "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, "
+        f"SignedHeaders=host;x-amz-date, Signature={expected}"
+    )
+
+
+@pytest.mark.parametrize(
+    "service, expected",
+    (
+        ("service", "/a%2520b/%25E2%2582%25AC"),
+        ("s3", "/a%20b/%E2%82%AC"),
+    ),
+)
+def test_sigv4_canonical_uri(sigv4, service, expected):
+    sigv4.service = service
+    # Non-ASCII characters & spaces are percent-encoded in the prepared URL
+    request = prepare("GET", "https://example.amazonaws.com/a b/€")
+    # Then they are not encoded one more time
+    assert sigv4._get_canonical_uri(request) == expected
+
+
+def test_sigv4_session_token(sigv4):
+    sigv4.session_token = "TOKEN"
+    request = prepare("POST", "https://example.amazonaws.com/", json={"key": "value"})
+    sigv4(None, request)
+    assert request.headers["X-Amz-Security-Token"] == "TOKEN"
+    assert "SignedHeaders=content-type;host;x-amz-date;x-amz-security-token," in request.headers["Authorization"]
+
+
+def test_sigv4_resign(sigv4):
+    # Signing the same request twice gives the same result
+    request = prepare("GET", "https://example.amazonaws.com/")
+    sigv4(None, request)
+    first = request.headers["Authorization"]
+    sigv4(None, request)
+    assert request.headers["Authorization"] == first
+
+
+def test_hmac():
+    signer = HMACSigner(key="k", clock=lambda: 1)
+    request = prepare("POST", "http://127.0.0.1/a?b=1", json={"a": 1})
+    signer(None, request)
+    assert request.headers["X-Timestamp"] == "1"
+    assert request.headers["X-Content-SHA256"] == "f9d86028c6e0d64e225186f96acb69338b2c59764df79162107f5c4bb34d1310"
+    assert request.headers["X-Signature"] == "66799eb0c89d3176867e0452720c82d21441667967fd1a2b7696505b23bcbb2e"
+
+
+def test_hmac_custom_headers():
+    signer = HMACSigner(key=b"k", signature_header="Signature", timestamp_header="Date", digest_header="Digest")
+    request = prepare("GET", "http://127.0.0.1/")
+    signer(None, request)
+    assert {"Signature", "Date", "Digest"} <= set(request.headers)
+    assert "X-Signature" not in request.headers
@@ -0,0 +1,72 @@
+import datetime
+from email.utils import format_datetime
+
+import pytest
+import requests
+
+from schemathesis.throttling import MAX_RETRY_DELAY, RateLimiter, get_retry_delay, parse_retry_after
+
+
+@pytest.mark.parametrize(
+    "value, max_requests, period",
+    (("100/s", 100, 1), ("1000/m", 1000, 60), (" 5 / h ", 5, 3600)),
+)
+def test_rate_limiter_from_string(value, max_requests, period):
+    limiter = RateLimiter.from_string(value)
+    assert limiter.max_requests == max_requests
+    assert limiter.period == period
+
+
+def test_rate_limiter():
+    now = 100.0
+    delays = []
+
+    def sleep(delay):
+        nonlocal now
+        delays.append(delay)
+        now += delay
+
+    limiter = RateLimiter(max_requests=2, period=1, clock=lambda: now, sleep=sleep)
+    # The first request is not delayed, the next ones are spread evenly
+    for _ in range(3):
+        limiter.acquire()
+    assert delays == [0.5, 0.5]
+    # If there were no requests for a while, then there is no delay
+    now += 10
+    limiter.acquire()
+    assert delays == [0.5, 0.5]
+
+
+def response_with(retry_after):
+    response = requests.Response()
+    response.status_code = 429
+    if retry_after is not None:
+        response.headers["Retry-After"] = retry_after
+    return response
+
+
+@pytest.mark.parametrize(
+    "retry_after, attempt, expected",
+    (
+        ("3", 0, 3),
+        (None, 0, 1),
+        (None, 2, 4),
+        ("invalid", 1, 2),
+        ("3600", 0, MAX_RETRY_DELAY),
+        (None, 10, MAX_RETRY_DELAY),
+    ),
+)
+def test_get_retry_delay(retry_after, attempt, expected):
+    assert get_retry_delay(response_with(retry_after), attempt) == expected
+
+
+def test_parse_retry_after_date():
+    retry_at = datetime.datetime.now(datetime.timezone.utc) + datetime.timedelta(seconds=30)
+    assert 25 < parse_retry_after(format_datetime(retry_at, usegmt=True)) <= 30
+    # Dates in the past mean no delay
+    assert parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT") == 0
+
+
+@pytest.mark.parametrize("value", ("", "-1", "1.5", "tomorrow"))
+def test_parse_retry_after_invalid(value):
+    assert parse_retry_after(value) is None